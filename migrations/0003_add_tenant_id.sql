@@ -0,0 +1,16 @@
+-- Tenant ownership. Rows written before multi-tenancy belong to the default tenant.
+ALTER TABLE telemetry ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX IF NOT EXISTS telemetry_tenant_timestamp_idx ON telemetry (tenant_id, "timestamp");
+CREATE INDEX IF NOT EXISTS telemetry_tenant_source_timestamp_idx
+    ON telemetry (tenant_id, source_id, "timestamp");
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+
+-- Optional defence in depth, inert until row-level security is enabled on the table
+-- (see docs/persistence.md). With RUSTPULSE_TENANT_RLS=1 the repository sets
+-- `rustpulse.tenant_id` at the start of every transaction.
+DROP POLICY IF EXISTS telemetry_tenant_isolation ON telemetry;
+CREATE POLICY telemetry_tenant_isolation ON telemetry
+    USING (tenant_id = current_setting('rustpulse.tenant_id', true))
+    WITH CHECK (tenant_id = current_setting('rustpulse.tenant_id', true));
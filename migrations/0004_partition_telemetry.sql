@@ -0,0 +1,60 @@
+-- Range-partition telemetry on "timestamp" without copying existing rows.
+--
+-- The current table becomes the partition `telemetry_legacy`, covering everything before the
+-- first Monday (UTC) after both now and its newest row; Mondays are day and week boundaries,
+-- so daily or weekly partitions can follow it. Attaching only scans it to validate the range,
+-- and builds the new (source_id, timestamp) index on it.
+-- Later partitions `telemetry_pYYYYMMDD_YYYYMMDD` are created by the partition manager;
+-- rows outside every partition land in `telemetry_default` until one is.
+ALTER TABLE telemetry RENAME TO telemetry_legacy;
+ALTER INDEX telemetry_timestamp_idx RENAME TO telemetry_legacy_timestamp_idx;
+ALTER INDEX telemetry_tenant_timestamp_idx RENAME TO telemetry_legacy_tenant_timestamp_idx;
+ALTER INDEX telemetry_tenant_source_timestamp_idx
+    RENAME TO telemetry_legacy_tenant_source_timestamp_idx;
+DROP POLICY IF EXISTS telemetry_tenant_isolation ON telemetry_legacy;
+
+CREATE TABLE telemetry (
+    source_id UUID NOT NULL,
+    server_id UUID NOT NULL,
+    "timestamp" TIMESTAMPTZ NOT NULL,
+    cpu DOUBLE PRECISION NULL,
+    memory DOUBLE PRECISION NULL,
+    temperature REAL NULL,
+    extras JSONB NOT NULL,
+    tenant_id TEXT NOT NULL DEFAULT 'default'
+) PARTITION BY RANGE ("timestamp");
+
+CREATE INDEX telemetry_tenant_timestamp_idx ON telemetry (tenant_id, "timestamp");
+CREATE INDEX telemetry_tenant_source_timestamp_idx ON telemetry (tenant_id, source_id, "timestamp");
+CREATE INDEX telemetry_source_timestamp_idx ON telemetry (source_id, "timestamp");
+
+DO $$
+DECLARE
+    cutover TIMESTAMPTZ;
+BEGIN
+    SELECT (date_trunc('week', greatest(now(), max("timestamp")) AT TIME ZONE 'UTC')
+            + INTERVAL '7 days') AT TIME ZONE 'UTC'
+    INTO cutover
+    FROM telemetry_legacy;
+
+    EXECUTE format(
+        'ALTER TABLE telemetry ATTACH PARTITION telemetry_legacy FOR VALUES FROM (MINVALUE) TO (%L)',
+        cutover
+    );
+END
+$$;
+
+CREATE TABLE telemetry_default PARTITION OF telemetry DEFAULT;
+
+CREATE POLICY telemetry_tenant_isolation ON telemetry
+    USING (tenant_id = current_setting('rustpulse.tenant_id', true))
+    WITH CHECK (tenant_id = current_setting('rustpulse.tenant_id', true));
+
+-- Keep row-level security on if it had been enabled on the old table.
+DO $$
+BEGIN
+    IF (SELECT relrowsecurity FROM pg_class WHERE oid = 'telemetry_legacy'::regclass) THEN
+        ALTER TABLE telemetry ENABLE ROW LEVEL SECURITY;
+    END IF;
+END
+$$;
@@ -0,0 +1,5 @@
+-- Unique row id, the last key of the (timestamp, source_id, id) order used by cursors, so
+-- rows sharing a timestamp and source are never skipped at a page boundary.
+-- Partitions created later (`LIKE telemetry INCLUDING DEFAULTS`) share the sequence.
+-- Adding the column rewrites every partition once.
+ALTER TABLE telemetry ADD COLUMN IF NOT EXISTS id BIGSERIAL;
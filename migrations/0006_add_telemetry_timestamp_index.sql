@@ -0,0 +1,5 @@
+-- Time-range scans without a tenant or source (retention, partition moves, global queries)
+-- lost their index when 0004 made telemetry partitioned: only `telemetry_legacy` kept one.
+-- Declared on the parent, it is built on every partition, reusing the legacy partition's
+-- existing ("timestamp") index, and on partitions attached later.
+CREATE INDEX IF NOT EXISTS telemetry_timestamp_idx ON telemetry ("timestamp");
@@ -0,0 +1,18 @@
+-- Telemetry for the embedded SQLite backend.
+-- Timestamps are Unix nanoseconds so range filters and ordering compare integers;
+-- UUIDs are 16-byte blobs, which sort like `Uuid`.
+CREATE TABLE IF NOT EXISTS telemetry (
+    tenant_id TEXT NOT NULL,
+    source_id BLOB NOT NULL,
+    server_id BLOB NOT NULL,
+    timestamp_ns INTEGER NOT NULL,
+    cpu REAL NULL,
+    memory REAL NULL,
+    temperature REAL NULL,
+    extras TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS telemetry_tenant_timestamp_idx
+    ON telemetry (tenant_id, timestamp_ns, source_id);
+CREATE INDEX IF NOT EXISTS telemetry_tenant_source_timestamp_idx
+    ON telemetry (tenant_id, source_id, timestamp_ns);
@@ -1,7 +1,15 @@
 //! HTTP transport adapters (Axum routes and middleware).
 
+pub mod api_key_handler;
+pub mod auth;
 pub mod favicon_handler;
 pub mod health_handler;
+pub mod internal_metrics_handler;
+pub mod rate_limit;
+pub mod request_signing;
 pub mod request_tracing;
 pub mod root_handler;
+pub mod telemetry_format;
 pub mod telemetry_handler;
+pub mod telemetry_proto;
+pub mod telemetry_stream_handler;
@@ -0,0 +1,239 @@
+//! HTTP handlers for API key administration under `/admin/api-keys`.
+
+use crate::adapters::input::http::telemetry_handler::ErrorResponse;
+use crate::core::application::api_keys::{ApiKeyAdminCase, ApiKeyError, NewApiKey};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get};
+use axum::{Json, Router, middleware};
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::request_tracing;
+
+#[instrument(level = "info", skip(service))]
+/// Router for `POST/GET /admin/api-keys` and `DELETE /admin/api-keys/{id}`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::api_key_handler;
+/// use rustpulse::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+/// use rustpulse::core::application::api_keys::{ApiKeyAdminCase, ApiKeyService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ApiKeyAdminCase> =
+///     Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+/// let _router = api_key_handler::routes(service);
+/// ```
+pub fn routes(service: Arc<dyn ApiKeyAdminCase>) -> Router {
+    Router::new()
+        .route(
+            "/admin/api-keys",
+            get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route("/admin/api-keys/{id}", delete(revoke_api_key_handler))
+        .with_state(service)
+        .route_layer(middleware::from_fn(request_tracing::trace_middleware))
+}
+
+#[derive(Debug)]
+/// Errors returned by the API key admin endpoints.
+pub enum ApiKeyHttpError {
+    /// The request body failed validation.
+    InvalidRequest(String),
+    /// No active key has the given id.
+    NotFound,
+    /// The API key use case returned an error.
+    Internal,
+}
+
+impl IntoResponse for ApiKeyHttpError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, code, message) = match self {
+            Self::InvalidRequest(message) => (StatusCode::BAD_REQUEST, "invalid_request", message),
+            Self::NotFound => (
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "No active api key with this id".to_string(),
+            ),
+            Self::Internal => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "Failed to process api key request".to_string(),
+            ),
+        };
+
+        (status, Json(ErrorResponse { code, message })).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiKeyHttpError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<ApiKeyError>() {
+            Some(e) => Self::InvalidRequest(e.to_string()),
+            None => {
+                tracing::error!(error = %err, "api key request failed");
+                Self::Internal
+            }
+        }
+    }
+}
+
+#[instrument(name = "create api key", skip(service, request))]
+/// Handles `POST /admin/api-keys`.
+///
+/// Body: `{"name": "...", "scopes": ["telemetry:write"], "server_id": "<uuid>", "tenant_id": "acme"}`.
+/// Responds `201` with the key metadata and its `secret`, which is shown only once.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::api_key_handler;
+/// use rustpulse::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+/// use rustpulse::core::application::api_keys::{ApiKeyAdminCase, ApiKeyService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ApiKeyAdminCase> =
+///     Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+/// let _router = api_key_handler::routes(service);
+/// ```
+pub async fn create_api_key_handler(
+    State(service): State<Arc<dyn ApiKeyAdminCase>>,
+    Json(request): Json<NewApiKey>,
+) -> Result<impl IntoResponse, ApiKeyHttpError> {
+    let created = service.create(request).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[instrument(name = "list api keys", skip(service))]
+/// Handles `GET /admin/api-keys` (metadata only, never secrets).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::api_key_handler;
+/// use rustpulse::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+/// use rustpulse::core::application::api_keys::{ApiKeyAdminCase, ApiKeyService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ApiKeyAdminCase> =
+///     Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+/// let _router = api_key_handler::routes(service);
+/// ```
+pub async fn list_api_keys_handler(
+    State(service): State<Arc<dyn ApiKeyAdminCase>>,
+) -> Result<impl IntoResponse, ApiKeyHttpError> {
+    Ok(Json(service.list().await?))
+}
+
+#[instrument(name = "revoke api key", skip(service))]
+/// Handles `DELETE /admin/api-keys/{id}`; responds `204` once revoked.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::api_key_handler;
+/// use rustpulse::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+/// use rustpulse::core::application::api_keys::{ApiKeyAdminCase, ApiKeyService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ApiKeyAdminCase> =
+///     Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+/// let _router = api_key_handler::routes(service);
+/// ```
+pub async fn revoke_api_key_handler(
+    State(service): State<Arc<dyn ApiKeyAdminCase>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiKeyHttpError> {
+    match service.revoke(id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiKeyHttpError::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+    use crate::core::application::api_keys::{ApiKeyAdminCase, ApiKeyService};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn send(
+        app: axum::Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut req = Request::builder().method(method).uri(uri);
+        let body = match body {
+            Some(body) => {
+                req = req.header(header::CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+        let resp = app.oneshot(req.body(body).unwrap()).await.unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_list_and_revoke_api_key() {
+        let service: Arc<dyn ApiKeyAdminCase> =
+            Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+        let app = super::routes(service);
+
+        let (status, created) = send(
+            app.clone(),
+            "POST",
+            "/admin/api-keys",
+            Some(json!({ "name": "edge-01", "scopes": ["telemetry:write"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(created["secret"].as_str().unwrap().starts_with("rpk_"));
+        let id = created["key"]["id"].as_str().unwrap().to_string();
+
+        let (status, listed) = send(app.clone(), "GET", "/admin/api-keys", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed[0]["id"], id.as_str());
+        assert!(listed[0].get("secret").is_none());
+
+        let uri = format!("/admin/api-keys/{id}");
+        let (status, _) = send(app.clone(), "DELETE", &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = send(app, "DELETE", &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_disallowed_scope() {
+        let service: Arc<dyn ApiKeyAdminCase> =
+            Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepo::default())));
+
+        let (status, body) = send(
+            super::routes(service),
+            "POST",
+            "/admin/api-keys",
+            Some(json!({ "name": "edge-01", "scopes": ["admin:api-keys"] })),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_request");
+    }
+}
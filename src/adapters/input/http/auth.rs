@@ -0,0 +1,565 @@
+//! Bearer-token authentication (HS256 JWT or API key) and scope checks.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use axum::{Router, middleware, routing::get};
+//! use rustpulse::adapters::input::http::auth::{
+//!     self, Authenticator, JwtVerifier, RequireScope, Scope,
+//! };
+//! use std::sync::Arc;
+//!
+//! let authenticator = Arc::new(Authenticator::new(JwtVerifier::new(
+//!     "a-secret-of-at-least-32-characters!",
+//! )));
+//! let _app = Router::<()>::new()
+//!     .route("/metrics", get(|| async { "[]" }))
+//!     .route_layer(middleware::from_fn_with_state(
+//!         RequireScope::new(authenticator, Scope::TelemetryRead),
+//!         auth::require_scope,
+//!     ));
+//! ```
+
+use crate::adapters::input::http::telemetry_handler::ErrorResponse;
+use crate::core::application::api_keys::{API_KEY_PREFIX, ApiKeyAuthCase};
+use crate::core::domains::tenant::TenantId;
+use axum::Json;
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::convert::Infallible;
+use std::sync::Arc;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Clock skew tolerated when checking `exp` and `nbf`, in seconds.
+pub const CLOCK_SKEW_LEEWAY_SECS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Permission carried by a token.
+pub enum Scope {
+    /// May query telemetry (`telemetry:read`).
+    TelemetryRead,
+    /// May ingest telemetry (`telemetry:write`).
+    TelemetryWrite,
+    /// May manage API keys (`admin:api-keys`); never granted to API keys.
+    ApiKeysAdmin,
+}
+
+impl Scope {
+    /// Returns the scope as it appears in token claims.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::input::http::auth::Scope;
+    ///
+    /// assert_eq!(Scope::TelemetryWrite.as_str(), "telemetry:write");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TelemetryRead => "telemetry:read",
+            Self::TelemetryWrite => "telemetry:write",
+            Self::ApiKeysAdmin => "admin:api-keys",
+        }
+    }
+
+    /// Parses a claim value; unknown scopes yield `None`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "telemetry:read" => Some(Self::TelemetryRead),
+            "telemetry:write" => Some(Self::TelemetryWrite),
+            "admin:api-keys" => Some(Self::ApiKeysAdmin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Identity of an authenticated caller, inserted into request extensions.
+pub struct AuthContext {
+    /// The token subject (`sub`), if any.
+    pub subject: Option<String>,
+    /// Scopes granted to the caller.
+    pub scopes: Vec<Scope>,
+    /// Server an API key is bound to; ingest rejects telemetry for other servers.
+    pub server_id: Option<Uuid>,
+    /// Tenant whose telemetry the caller may read and write.
+    pub tenant: TenantId,
+}
+
+impl AuthContext {
+    /// Returns `true` when the caller holds `scope`.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Extractor for the tenant a request acts for.
+///
+/// Taken from the [`AuthContext`]; routes served without authentication act
+/// for [`TenantId::DEFAULT`].
+pub struct CallerTenant(pub TenantId);
+
+impl<S: Send + Sync> FromRequestParts<S> for CallerTenant {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .extensions
+                .get::<AuthContext>()
+                .map(|context| context.tenant.clone())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a request is rejected by the auth layer.
+pub enum AuthError {
+    /// No `Authorization: Bearer` header was sent.
+    MissingToken,
+    /// The token is malformed, uses another algorithm, has a bad signature,
+    /// or is an unknown/revoked API key.
+    InvalidToken,
+    /// The token's `exp` is in the past.
+    Expired,
+    /// The token's `nbf` is in the future.
+    NotYetValid,
+    /// The token is valid but lacks the required scope.
+    InsufficientScope(Scope),
+    /// The API key store could not be reached.
+    Unavailable,
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match self {
+            Self::MissingToken => (
+                StatusCode::UNAUTHORIZED,
+                "missing_token",
+                "Missing bearer token".to_string(),
+            ),
+            Self::InvalidToken => (
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "Bearer token is malformed or its signature is invalid".to_string(),
+            ),
+            Self::Expired => (
+                StatusCode::UNAUTHORIZED,
+                "token_expired",
+                "Bearer token has expired".to_string(),
+            ),
+            Self::NotYetValid => (
+                StatusCode::UNAUTHORIZED,
+                "token_not_yet_valid",
+                "Bearer token is not valid yet".to_string(),
+            ),
+            Self::InsufficientScope(scope) => (
+                StatusCode::FORBIDDEN,
+                "insufficient_scope",
+                format!("Token lacks required scope `{}`", scope.as_str()),
+            ),
+            Self::Unavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "auth_unavailable",
+                "Credentials could not be checked, retry later".to_string(),
+            ),
+        };
+
+        let mut response = (status, Json(ErrorResponse { code, message })).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    sub: Option<String>,
+    exp: i64,
+    nbf: Option<i64>,
+    /// Space-separated scopes (RFC 8693 style).
+    #[serde(default)]
+    scope: String,
+    /// Owning tenant; tokens without it act for [`TenantId::DEFAULT`].
+    #[serde(default)]
+    tenant_id: TenantId,
+}
+
+/// Verifies HS256-signed JWTs against a shared secret.
+pub struct JwtVerifier {
+    secret: Vec<u8>,
+}
+
+impl JwtVerifier {
+    /// Creates a verifier for tokens signed with `secret`.
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    /// Verifies `token` at Unix time `now` and returns the caller's identity.
+    ///
+    /// `exp` is required; `nbf` is optional. Both allow [`CLOCK_SKEW_LEEWAY_SECS`].
+    /// The optional `tenant_id` claim selects the tenant; a malformed one rejects the token.
+    pub fn verify(&self, token: &str, now: i64) -> Result<AuthContext, AuthError> {
+        let (signing_input, signature) = token.rsplit_once('.').ok_or(AuthError::InvalidToken)?;
+        let (header, payload) = signing_input
+            .split_once('.')
+            .ok_or(AuthError::InvalidToken)?;
+
+        let header: JwtHeader = decode_segment(header)?;
+        if header.alg != "HS256" {
+            return Err(AuthError::InvalidToken);
+        }
+
+        // Verify the signature before trusting anything in the payload.
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| AuthError::InvalidToken)?;
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).map_err(|_| AuthError::InvalidToken)?;
+        mac.update(signing_input.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| AuthError::InvalidToken)?;
+
+        let claims: JwtClaims = decode_segment(payload)?;
+        if now >= claims.exp.saturating_add(CLOCK_SKEW_LEEWAY_SECS) {
+            return Err(AuthError::Expired);
+        }
+        if claims
+            .nbf
+            .is_some_and(|nbf| now.saturating_add(CLOCK_SKEW_LEEWAY_SECS) < nbf)
+        {
+            return Err(AuthError::NotYetValid);
+        }
+
+        Ok(AuthContext {
+            subject: claims.sub,
+            scopes: claims
+                .scope
+                .split_whitespace()
+                .filter_map(Scope::parse)
+                .collect(),
+            server_id: None,
+            tenant: claims.tenant_id,
+        })
+    }
+}
+
+/// Resolves bearer credentials: `rpk_`-prefixed API keys or HS256 JWTs.
+pub struct Authenticator {
+    jwt: JwtVerifier,
+    api_keys: Option<Arc<dyn ApiKeyAuthCase>>,
+}
+
+impl Authenticator {
+    /// Accepts JWTs only.
+    pub fn new(jwt: JwtVerifier) -> Self {
+        Self {
+            jwt,
+            api_keys: None,
+        }
+    }
+
+    /// Also accepts API keys resolved by `api_keys`.
+    pub fn with_api_keys(mut self, api_keys: Arc<dyn ApiKeyAuthCase>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    /// Authenticates `token` at Unix time `now`.
+    pub async fn authenticate(&self, token: &str, now: i64) -> Result<AuthContext, AuthError> {
+        if !token.starts_with(API_KEY_PREFIX) {
+            return self.jwt.verify(token, now);
+        }
+
+        let api_keys = self.api_keys.as_ref().ok_or(AuthError::InvalidToken)?;
+        let key = api_keys
+            .authenticate(token)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "api key lookup failed");
+                AuthError::Unavailable
+            })?
+            .ok_or(AuthError::InvalidToken)?;
+
+        Ok(AuthContext {
+            subject: Some(format!("api-key:{}", key.prefix)),
+            scopes: key
+                .scopes
+                .iter()
+                .filter_map(|s| Scope::parse(s))
+                .filter(|s| *s != Scope::ApiKeysAdmin)
+                .collect(),
+            server_id: key.server_id,
+            tenant: key.tenant_id,
+        })
+    }
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, AuthError> {
+    let raw = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| AuthError::InvalidToken)?;
+    serde_json::from_slice(&raw).map_err(|_| AuthError::InvalidToken)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, AuthError> {
+    let value = headers
+        .get(header::AUTHORIZATION)
+        .ok_or(AuthError::MissingToken)?
+        .to_str()
+        .map_err(|_| AuthError::InvalidToken)?;
+
+    match value.split_once(' ') {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => Ok(token.trim()),
+        _ => Err(AuthError::MissingToken),
+    }
+}
+
+#[derive(Clone)]
+/// Middleware state for [`require_scope`]: the authenticator and the scope a route needs.
+pub struct RequireScope {
+    authenticator: Arc<Authenticator>,
+    scope: Scope,
+}
+
+impl RequireScope {
+    /// Requires a valid token carrying `scope`.
+    pub fn new(authenticator: Arc<Authenticator>, scope: Scope) -> Self {
+        Self {
+            authenticator,
+            scope,
+        }
+    }
+}
+
+/// Axum middleware that authenticates the bearer token and enforces a scope.
+///
+/// On success the [`AuthContext`] is available to handlers via request extensions.
+pub async fn require_scope(
+    State(required): State<RequireScope>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let now = chrono::Utc::now().timestamp();
+    let token = bearer_token(req.headers())?;
+    let context = required
+        .authenticator
+        .authenticate(token, now)
+        .await
+        .inspect_err(|e| tracing::info!(reason = ?e, "auth.rejected"))?;
+
+    if !context.has_scope(required.scope) {
+        tracing::info!(scope = required.scope.as_str(), "auth.forbidden");
+        return Err(AuthError::InsufficientScope(required.scope));
+    }
+
+    req.extensions_mut().insert(context);
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domains::api_key::ApiKey;
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::{Router, middleware, routing::get};
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-secret-test-secret-test-secret";
+    const NOW: i64 = 1_700_000_000;
+
+    fn sign(header: Value, claims: Value, secret: &str) -> String {
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(signing_input.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{signing_input}.{signature}")
+    }
+
+    fn token(claims: Value) -> String {
+        sign(json!({ "alg": "HS256", "typ": "JWT" }), claims, SECRET)
+    }
+
+    #[test]
+    fn test_verify_accepts_valid_token_and_maps_scopes() {
+        let verifier = JwtVerifier::new(SECRET);
+        let ctx = verifier
+            .verify(
+                &token(
+                    json!({ "sub": "agent-1", "exp": NOW + 60, "scope": "telemetry:write other" }),
+                ),
+                NOW,
+            )
+            .unwrap();
+
+        assert_eq!(ctx.subject.as_deref(), Some("agent-1"));
+        assert_eq!(ctx.scopes, vec![Scope::TelemetryWrite]);
+        assert!(ctx.tenant.is_default());
+    }
+
+    #[test]
+    fn test_verify_maps_tenant_claim_and_rejects_malformed_one() {
+        let verifier = JwtVerifier::new(SECRET);
+
+        let ctx = verifier
+            .verify(&token(json!({ "exp": NOW + 60, "tenant_id": "acme" })), NOW)
+            .unwrap();
+        assert_eq!(ctx.tenant.as_str(), "acme");
+
+        let bad = token(json!({ "exp": NOW + 60, "tenant_id": "../acme" }));
+        assert_eq!(verifier.verify(&bad, NOW), Err(AuthError::InvalidToken));
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_and_not_yet_valid_tokens() {
+        let verifier = JwtVerifier::new(SECRET);
+
+        let expired = token(json!({ "exp": NOW - CLOCK_SKEW_LEEWAY_SECS - 1 }));
+        assert_eq!(verifier.verify(&expired, NOW), Err(AuthError::Expired));
+
+        let early = token(json!({ "exp": NOW + 600, "nbf": NOW + CLOCK_SKEW_LEEWAY_SECS + 1 }));
+        assert_eq!(verifier.verify(&early, NOW), Err(AuthError::NotYetValid));
+    }
+
+    #[test]
+    fn test_verify_does_not_overflow_on_extreme_exp() {
+        let verifier = JwtVerifier::new(SECRET);
+
+        let forever = token(json!({ "exp": i64::MAX }));
+        assert!(verifier.verify(&forever, NOW).is_ok());
+
+        let past = token(json!({ "exp": i64::MIN }));
+        assert_eq!(verifier.verify(&past, NOW), Err(AuthError::Expired));
+    }
+
+    #[test]
+    fn test_verify_rejects_bad_signature_and_other_algorithms() {
+        let verifier = JwtVerifier::new(SECRET);
+        let claims = json!({ "exp": NOW + 60 });
+
+        let wrong_key = sign(json!({ "alg": "HS256" }), claims.clone(), "another-secret");
+        assert_eq!(
+            verifier.verify(&wrong_key, NOW),
+            Err(AuthError::InvalidToken)
+        );
+
+        let none_alg = sign(json!({ "alg": "none" }), claims, SECRET);
+        assert_eq!(
+            verifier.verify(&none_alg, NOW),
+            Err(AuthError::InvalidToken)
+        );
+
+        assert_eq!(
+            verifier.verify("garbage", NOW),
+            Err(AuthError::InvalidToken)
+        );
+    }
+
+    async fn call(authorization: Option<String>) -> (StatusCode, Value) {
+        let required = RequireScope::new(
+            Arc::new(Authenticator::new(JwtVerifier::new(SECRET))),
+            Scope::TelemetryRead,
+        );
+        let app = Router::new()
+            .route("/metrics", get(|| async { "[]" }))
+            .route_layer(middleware::from_fn_with_state(required, require_scope));
+
+        let mut req = Request::builder().uri("/metrics");
+        if let Some(value) = authorization {
+            req = req.header(header::AUTHORIZATION, value);
+        }
+        let resp = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_middleware_returns_structured_401_and_403() {
+        let (status, body) = call(None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "missing_token");
+
+        let now = chrono::Utc::now().timestamp();
+        let write_only = token(json!({ "exp": now + 60, "scope": "telemetry:write" }));
+        let (status, body) = call(Some(format!("Bearer {write_only}"))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "insufficient_scope");
+
+        let reader = token(json!({ "exp": now + 60, "scope": "telemetry:read" }));
+        let (status, _) = call(Some(format!("Bearer {reader}"))).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    struct OneKey(ApiKey);
+
+    #[async_trait::async_trait]
+    impl ApiKeyAuthCase for OneKey {
+        async fn authenticate(&self, presented: &str) -> anyhow::Result<Option<ApiKey>> {
+            Ok((presented == "rpk_00000000_secret").then(|| self.0.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_maps_scopes_tenant_and_server_binding_but_never_admin() {
+        let server_id = Uuid::new_v4();
+        let authenticator =
+            Authenticator::new(JwtVerifier::new(SECRET)).with_api_keys(Arc::new(OneKey(ApiKey {
+                id: Uuid::new_v4(),
+                name: "agent".to_string(),
+                prefix: "00000000".to_string(),
+                scopes: vec!["telemetry:write".to_string(), "admin:api-keys".to_string()],
+                server_id: Some(server_id),
+                tenant_id: TenantId::parse("acme").unwrap(),
+                created_at: chrono::Utc::now(),
+                revoked_at: None,
+            })));
+
+        let ctx = authenticator
+            .authenticate("rpk_00000000_secret", NOW)
+            .await
+            .unwrap();
+        assert_eq!(ctx.scopes, vec![Scope::TelemetryWrite]);
+        assert_eq!(ctx.server_id, Some(server_id));
+        assert_eq!(ctx.tenant.as_str(), "acme");
+
+        assert_eq!(
+            authenticator.authenticate("rpk_00000000_wrong", NOW).await,
+            Err(AuthError::InvalidToken)
+        );
+    }
+}
@@ -1,11 +1,16 @@
-//! Health-check endpoint.
+//! Health-check endpoints: `/health`, `/health/live` and `/health/ready`.
 
+use crate::core::application::health::ReadinessCase;
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{Router, middleware, routing::get};
+use std::sync::Arc;
 
 use super::request_tracing;
 
-/// Router for the `GET /health` endpoint.
+/// Router for the `GET /health` and `GET /health/live` endpoints.
 ///
 /// # Examples
 ///
@@ -17,6 +22,26 @@ use super::request_tracing;
 pub fn routes() -> Router {
     Router::new()
         .route("/health", get(health_check))
+        .route("/health/live", get(liveness_check))
+        .route_layer(middleware::from_fn(request_tracing::trace_middleware))
+}
+
+/// Router for `GET /health/ready`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::adapters::input::http::health_handler;
+/// use rustpulse::core::application::health::{ReadinessCase, ReadinessService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ReadinessCase> = Arc::new(ReadinessService::new(Vec::new()));
+/// let _router = health_handler::readiness_routes(service);
+/// ```
+pub fn readiness_routes(service: Arc<dyn ReadinessCase>) -> Router {
+    Router::new()
+        .route("/health/ready", get(readiness_check))
+        .with_state(service)
         .route_layer(middleware::from_fn(request_tracing::trace_middleware))
 }
 
@@ -34,3 +59,116 @@ pub fn routes() -> Router {
 pub async fn health_check() -> impl IntoResponse {
     "OK"
 }
+
+/// Handles `GET /health/live`: the process is up and serving requests.
+///
+/// Never touches dependencies, so a database outage does not get the instance restarted.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() {
+/// use rustpulse::adapters::input::http::health_handler;
+///
+/// let _router = health_handler::routes();
+/// # }
+/// ```
+pub async fn liveness_check() -> impl IntoResponse {
+    Json(serde_json::json!({ "status": "up" }))
+}
+
+/// Handles `GET /health/ready`.
+///
+/// Responds `200` with a [`ReadinessReport`](crate::core::application::health::ReadinessReport)
+/// when every critical dependency is up, `503` with the same body otherwise.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() {
+/// use rustpulse::adapters::input::http::health_handler;
+/// use rustpulse::core::application::health::{ReadinessCase, ReadinessService};
+/// use std::sync::Arc;
+///
+/// let service: Arc<dyn ReadinessCase> = Arc::new(ReadinessService::new(Vec::new()));
+/// let _router = health_handler::readiness_routes(service);
+/// # }
+/// ```
+pub async fn readiness_check(State(service): State<Arc<dyn ReadinessCase>>) -> impl IntoResponse {
+    let report = service.readiness().await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::application::health::{DependencyProbe, ReadinessService};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    struct Probe {
+        up: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl DependencyProbe for Probe {
+        fn name(&self) -> &'static str {
+            "postgres"
+        }
+
+        async fn probe(&self) -> anyhow::Result<()> {
+            match self.up {
+                true => Ok(()),
+                false => anyhow::bail!("db ping failed"),
+            }
+        }
+    }
+
+    async fn get(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_each_dependency_and_503_when_critical_one_is_down() {
+        let up = super::readiness_routes(Arc::new(ReadinessService::new(vec![Arc::new(Probe {
+            up: true,
+        })])));
+        let (status, body) = get(up, "/health/ready").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "up");
+        assert_eq!(body["checks"][0]["name"], "postgres");
+        assert!(body["checks"][0]["latency_ms"].is_u64());
+
+        let down =
+            super::readiness_routes(Arc::new(ReadinessService::new(vec![Arc::new(Probe {
+                up: false,
+            })])));
+        let (status, body) = get(down, "/health/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "down");
+        assert_eq!(body["checks"][0]["status"], "down");
+        // Probe errors are logged, never returned to unauthenticated callers.
+        assert!(!body.to_string().contains("db ping failed"));
+    }
+
+    #[tokio::test]
+    async fn test_live_does_not_depend_on_anything() {
+        let (status, body) = get(super::routes(), "/health/live").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "up");
+    }
+}
@@ -0,0 +1,84 @@
+//! Prometheus scrape endpoint for RustPulse's own metrics (`GET /internal/metrics`).
+//!
+//! Not to be confused with `GET /metrics`, which serves ingested telemetry.
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::{Router, routing::get};
+use metrics_exporter_prometheus::PrometheusHandle;
+
+/// Content type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Router for `GET /internal/metrics`.
+///
+/// The route is deliberately left out of request tracing so scrapes do not
+/// show up in the HTTP duration histogram they report.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::adapters::input::http::internal_metrics_handler;
+/// use rustpulse::infra::metrics;
+///
+/// let _router = internal_metrics_handler::routes(metrics::install_recorder());
+/// ```
+pub fn routes(handle: PrometheusHandle) -> Router {
+    Router::new()
+        .route("/internal/metrics", get(render_metrics))
+        .with_state(handle)
+}
+
+/// Handles `GET /internal/metrics`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::internal_metrics_handler;
+/// use rustpulse::infra::metrics;
+///
+/// let _router = internal_metrics_handler::routes(metrics::install_recorder());
+/// ```
+pub async fn render_metrics(State(handle): State<PrometheusHandle>) -> impl IntoResponse {
+    handle.run_upkeep();
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        handle.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infra::metrics::build_recorder;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_renders_prometheus_text() {
+        let recorder = build_recorder();
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("rustpulse_ingest_records_total", "outcome" => "ok").increment(3);
+        });
+
+        let app = super::routes(recorder.handle());
+        let req = Request::builder()
+            .uri("/internal/metrics")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            super::PROMETHEUS_CONTENT_TYPE
+        );
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(body.contains(r#"rustpulse_ingest_records_total{outcome="ok"} 3"#));
+    }
+}
@@ -0,0 +1,268 @@
+//! Token-bucket rate limiting for the ingest routes.
+//!
+//! Callers are keyed by the most specific identity available: the `server_id` their
+//! API key is bound to, then the API key (or token subject), then the client IP.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use axum::{Router, middleware, routing::post};
+//! use rustpulse::adapters::input::http::rate_limit::{self, RateLimitConfig, RateLimiter};
+//! use std::sync::Arc;
+//!
+//! let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+//!     per_second: 10,
+//!     burst: 20,
+//! }));
+//! let _app = Router::<()>::new()
+//!     .route("/telemetry", post(|| async { "" }))
+//!     .route_layer(middleware::from_fn_with_state(limiter, rate_limit::rate_limit));
+//! ```
+
+use crate::adapters::input::http::auth::AuthContext;
+use crate::adapters::input::http::telemetry_handler::TelemetryIngestHttpError;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Number of callers tracked; past it the least recently seen caller is forgotten.
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Sustained rate and burst size allowed per caller.
+pub struct RateLimitConfig {
+    /// Requests refilled per second.
+    pub per_second: u32,
+    /// Requests a caller may send back to back (bucket capacity).
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    /// Position in [`Buckets::by_use`].
+    used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    /// Keys by last use, least recent first.
+    by_use: BTreeMap<u64, String>,
+    uses: u64,
+}
+
+impl Buckets {
+    /// Returns `key`'s bucket, marked as the most recently used.
+    ///
+    /// A new key evicts the least recently used one once [`MAX_TRACKED_KEYS`] are tracked.
+    fn touch(&mut self, key: &str, new: impl FnOnce() -> Bucket) -> &mut Bucket {
+        self.uses += 1;
+        let used = self.uses;
+        if let Some(bucket) = self.by_key.get_mut(key) {
+            let key = self
+                .by_use
+                .remove(&bucket.used)
+                .unwrap_or_else(|| key.to_string());
+            self.by_use.insert(used, key);
+            bucket.used = used;
+        } else {
+            if self.by_key.len() >= MAX_TRACKED_KEYS
+                && let Some((_, oldest)) = self.by_use.pop_first()
+            {
+                self.by_key.remove(&oldest);
+            }
+            self.by_use.insert(used, key.to_string());
+            self.by_key
+                .insert(key.to_string(), Bucket { used, ..new() });
+        }
+        self.by_key.get_mut(key).expect("bucket was just touched")
+    }
+}
+
+#[derive(Debug)]
+/// In-memory token buckets keyed by caller.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter; every caller starts with a full bucket.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// Takes one token for `key` at `now`, or returns the seconds until one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::input::http::rate_limit::{RateLimitConfig, RateLimiter};
+    /// use std::time::Instant;
+    ///
+    /// let limiter = RateLimiter::new(RateLimitConfig { per_second: 1, burst: 1 });
+    /// let now = Instant::now();
+    /// assert!(limiter.check("ip:10.0.0.1", now).is_ok());
+    /// assert_eq!(limiter.check("ip:10.0.0.1", now), Err(1));
+    /// ```
+    pub fn check(&self, key: &str, now: Instant) -> Result<(), u64> {
+        let capacity = f64::from(self.config.burst.max(1));
+        let rate = f64::from(self.config.per_second.max(1));
+        let mut buckets = self.buckets.lock().expect("rate limit lock poisoned");
+
+        let bucket = buckets.touch(key, || Bucket {
+            tokens: capacity,
+            refilled_at: now,
+            used: 0,
+        });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / rate).ceil().max(1.0) as u64)
+        }
+    }
+}
+
+/// Identity the caller is rate limited under.
+fn rate_limit_key(req: &Request) -> String {
+    let context = req.extensions().get::<AuthContext>();
+    if let Some(server_id) = context.and_then(|c| c.server_id) {
+        return format!("server:{server_id}");
+    }
+    if let Some(subject) = context.and_then(|c| c.subject.as_deref()) {
+        return format!("subject:{subject}");
+    }
+    match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "anonymous".to_string(),
+    }
+}
+
+/// Axum middleware that answers `429 rate_limited` with `Retry-After` once a caller's bucket is empty.
+///
+/// Install it inside the auth layer so the [`AuthContext`] is available for keying.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, TelemetryIngestHttpError> {
+    let key = rate_limit_key(&req);
+    if let Err(retry_after_secs) = limiter.check(&key, Instant::now()) {
+        tracing::info!(key = %key, retry_after_secs, "ingest rate limited");
+        return Err(TelemetryIngestHttpError::RateLimited { retry_after_secs });
+    }
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::input::http::auth::Scope;
+    use crate::core::domains::tenant::TenantId;
+    use axum::body::Body;
+    use axum::http::{StatusCode, header};
+    use axum::{Router, middleware, routing::post};
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills_at_rate() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_second: 2,
+            burst: 3,
+        });
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check("a", start).is_ok());
+        }
+        assert_eq!(limiter.check("a", start), Err(1));
+        assert!(limiter.check("b", start).is_ok());
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check("a", later).is_ok());
+        assert!(limiter.check("a", later).is_err());
+    }
+
+    #[test]
+    fn test_tracked_keys_are_capped_by_evicting_least_recently_used() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            per_second: 1,
+            burst: 1,
+        });
+        let now = Instant::now();
+
+        assert!(limiter.check("first", now).is_ok());
+        for i in 1..MAX_TRACKED_KEYS {
+            assert!(limiter.check(&format!("ip:{i}"), now).is_ok());
+        }
+        // Seen again, so it is no longer the least recently used key.
+        assert!(limiter.check("first", now).is_err());
+        assert!(limiter.check("new", now).is_ok());
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.by_key.len(), MAX_TRACKED_KEYS);
+        assert_eq!(buckets.by_use.len(), MAX_TRACKED_KEYS);
+        assert!(buckets.by_key.contains_key("first"));
+        assert!(!buckets.by_key.contains_key("ip:1"));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_keys_by_bound_server_and_returns_429() {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            per_second: 1,
+            burst: 1,
+        }));
+        let app = |server: u128| {
+            Router::new()
+                .route("/telemetry", post(|| async { StatusCode::ACCEPTED }))
+                .route_layer(middleware::from_fn_with_state(limiter.clone(), rate_limit))
+                .layer(axum::Extension(AuthContext {
+                    subject: Some("api-key:abcd1234".to_string()),
+                    scopes: vec![Scope::TelemetryWrite],
+                    server_id: Some(Uuid::from_u128(server)),
+                    tenant: TenantId::default(),
+                }))
+        };
+        let post = || {
+            Request::builder()
+                .method("POST")
+                .uri("/telemetry")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        assert_eq!(
+            app(1).oneshot(post()).await.unwrap().status(),
+            StatusCode::ACCEPTED
+        );
+        let resp = app(1).oneshot(post()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "1");
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "rate_limited");
+
+        assert_eq!(
+            app(2).oneshot(post()).await.unwrap().status(),
+            StatusCode::ACCEPTED
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! HMAC request signing and replay protection for the ingest routes.
+//!
+//! `X-CRC32` catches bodies corrupted on the way; signing also catches bodies changed on
+//! purpose. When signing keys are configured, every ingest request must carry:
+//!
+//! | Header | Value |
+//! | --- | --- |
+//! | `X-Signature-Key-Id` | Id of the agent's signing key |
+//! | `X-Signature-Timestamp` | Unix time of signing, in seconds |
+//! | `X-Signature-Nonce` | Unique per request (1 to 128 characters) |
+//! | `X-Signature` | Hex HMAC-SHA256, see [`sign`] |
+//!
+//! Requests whose timestamp is further than the allowed skew from the server clock are
+//! rejected, and so are nonces already seen within that window.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use axum::{Router, middleware, routing::post};
+//! use rustpulse::adapters::input::http::request_signing::{self, RequestVerifier, SigningConfig};
+//! use std::collections::HashMap;
+//! use std::sync::Arc;
+//!
+//! let verifier = Arc::new(RequestVerifier::new(SigningConfig {
+//!     keys: HashMap::from([("edge-01".to_string(), b"s3cret".to_vec())]),
+//!     max_skew_secs: 300,
+//!     nonce_cache_size: 100_000,
+//! }));
+//! let _app = Router::<()>::new()
+//!     .route("/telemetry", post(|| async { "" }))
+//!     .route_layer(middleware::from_fn_with_state(verifier, request_signing::verify_signature));
+//! ```
+
+use crate::adapters::input::http::telemetry_handler::{
+    TelemetryIngestHttpError, max_ingest_body_bytes, read_ingest_body,
+};
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Header naming the signing key.
+pub const KEY_ID_HEADER: &str = "x-signature-key-id";
+/// Header carrying the signing time, in Unix seconds.
+pub const TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+/// Header carrying the request's nonce.
+pub const NONCE_HEADER: &str = "x-signature-nonce";
+/// Header carrying the hex HMAC-SHA256 signature.
+pub const SIGNATURE_HEADER: &str = "x-signature";
+/// Default allowed distance between the signing time and the server clock.
+pub const DEFAULT_MAX_SKEW_SECS: u64 = 300;
+/// Default number of nonces remembered.
+pub const DEFAULT_NONCE_CACHE_SIZE: usize = 100_000;
+
+const MAX_NONCE_LEN: usize = 128;
+
+/// Seconds between two warnings about unexpired nonces being forgotten.
+const EVICTION_WARNING_INTERVAL_SECS: i64 = 60;
+
+#[derive(Debug, Clone)]
+/// Keys and replay window of signed ingest.
+pub struct SigningConfig {
+    /// Secret of each key id.
+    pub keys: HashMap<String, Vec<u8>>,
+    /// Seconds a signature stays valid on either side of the server clock.
+    pub max_skew_secs: u64,
+    /// Nonces remembered at most; beyond it the oldest are forgotten.
+    pub nonce_cache_size: usize,
+}
+
+/// Signs a request: hex HMAC-SHA256 with `secret` over
+/// `"{METHOD}\n{path}\n{timestamp}\n{nonce}\n"` followed by the body exactly as sent.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::adapters::input::http::request_signing::sign;
+///
+/// let signature = sign(b"s3cret", "POST", "/telemetry", 1_700_000_000, "n-1", b"{}");
+/// assert_eq!(signature.len(), 64);
+/// assert_ne!(signature, sign(b"s3cret", "POST", "/telemetry", 1_700_000_000, "n-1", b"[]"));
+/// ```
+pub fn sign(
+    secret: &[u8],
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    mac(secret, method, path, timestamp, nonce, body)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn mac(
+    secret: &[u8],
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{method}\n{path}\n{timestamp}\n{nonce}\n").as_bytes());
+    mac.update(body);
+    mac
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+/// Nonces seen recently, each kept until its signature could no longer pass the skew check.
+struct NonceCache {
+    expires_at: HashMap<String, i64>,
+    order: VecDeque<(String, i64)>,
+    /// Unexpired nonces forgotten since the last warning.
+    evicted: u64,
+    /// When the last eviction warning was logged.
+    warned_at: Option<i64>,
+}
+
+impl NonceCache {
+    /// Records `nonce` until `expires_at`; returns `false` when it is already known.
+    fn insert(&mut self, nonce: String, expires_at: i64, now: i64, capacity: usize) -> bool {
+        while self.order.front().is_some_and(|(_, at)| *at < now) {
+            self.pop_oldest();
+        }
+        if self.expires_at.get(&nonce).is_some_and(|&at| at >= now) {
+            return false;
+        }
+        while self.order.len() >= capacity.max(1) {
+            self.pop_oldest();
+            self.evicted += 1;
+            metrics::counter!("rustpulse_nonce_cache_evictions_total").increment(1);
+        }
+        if self.evicted > 0
+            && self
+                .warned_at
+                .is_none_or(|at| now - at >= EVICTION_WARNING_INTERVAL_SECS)
+        {
+            tracing::warn!(
+                evicted = self.evicted,
+                "nonce cache full: forgot unexpired nonces; raise RUSTPULSE_NONCE_CACHE_SIZE"
+            );
+            self.evicted = 0;
+            self.warned_at = Some(now);
+        }
+        self.order.push_back((nonce.clone(), expires_at));
+        self.expires_at.insert(nonce, expires_at);
+        true
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some((nonce, at)) = self.order.pop_front()
+            // A nonce seen again after expiring has a newer entry further back.
+            && self.expires_at.get(&nonce) == Some(&at)
+        {
+            self.expires_at.remove(&nonce);
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Checks request signatures and remembers the nonces of accepted ones.
+pub struct RequestVerifier {
+    config: SigningConfig,
+    nonces: Mutex<NonceCache>,
+}
+
+impl RequestVerifier {
+    /// Creates a verifier with an empty nonce cache.
+    pub fn new(config: SigningConfig) -> Self {
+        Self {
+            config,
+            nonces: Mutex::new(NonceCache::default()),
+        }
+    }
+
+    /// Verifies the signature headers of a request against its body at Unix time `now`.
+    ///
+    /// The nonce is only recorded once the signature is valid, so unsigned traffic cannot
+    /// fill the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use axum::http::HeaderMap;
+    /// use rustpulse::adapters::input::http::request_signing::{
+    ///     RequestVerifier, SigningConfig, sign,
+    /// };
+    /// use std::collections::HashMap;
+    ///
+    /// let verifier = RequestVerifier::new(SigningConfig {
+    ///     keys: HashMap::from([("edge-01".to_string(), b"s3cret".to_vec())]),
+    ///     max_skew_secs: 300,
+    ///     nonce_cache_size: 16,
+    /// });
+    /// let now = 1_700_000_000;
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-signature-key-id", "edge-01".parse().unwrap());
+    /// headers.insert("x-signature-timestamp", now.to_string().parse().unwrap());
+    /// headers.insert("x-signature-nonce", "n-1".parse().unwrap());
+    /// let signature = sign(b"s3cret", "POST", "/telemetry", now, "n-1", b"{}");
+    /// headers.insert("x-signature", signature.parse().unwrap());
+    ///
+    /// assert!(verifier.verify("POST", "/telemetry", &headers, b"{}", now).is_ok());
+    /// // The same request again is a replay.
+    /// assert!(verifier.verify("POST", "/telemetry", &headers, b"{}", now).is_err());
+    /// ```
+    pub fn verify(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), TelemetryIngestHttpError> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let (Some(key_id), Some(timestamp), Some(nonce), Some(signature)) = (
+            header(KEY_ID_HEADER),
+            header(TIMESTAMP_HEADER),
+            header(NONCE_HEADER),
+            header(SIGNATURE_HEADER),
+        ) else {
+            return Err(TelemetryIngestHttpError::MissingSignature);
+        };
+        let secret = self
+            .config
+            .keys
+            .get(key_id)
+            .ok_or(TelemetryIngestHttpError::UnknownSigningKey)?;
+        let timestamp = timestamp
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| TelemetryIngestHttpError::InvalidSignature)?;
+        let max_skew = i64::try_from(self.config.max_skew_secs).unwrap_or(i64::MAX);
+        if now.abs_diff(timestamp) > self.config.max_skew_secs {
+            return Err(TelemetryIngestHttpError::StaleTimestamp);
+        }
+        if nonce.is_empty() || nonce.len() > MAX_NONCE_LEN {
+            return Err(TelemetryIngestHttpError::InvalidSignature);
+        }
+        let signature =
+            decode_hex(signature.trim()).ok_or(TelemetryIngestHttpError::InvalidSignature)?;
+        mac(secret, method, path, timestamp, nonce, body)
+            .verify_slice(&signature)
+            .map_err(|_| TelemetryIngestHttpError::InvalidSignature)?;
+
+        let mut nonces = self.nonces.lock().expect("nonce cache lock poisoned");
+        if nonces.insert(
+            format!("{key_id}\n{nonce}"),
+            timestamp.saturating_add(max_skew),
+            now,
+            self.config.nonce_cache_size,
+        ) {
+            Ok(())
+        } else {
+            Err(TelemetryIngestHttpError::ReplayedNonce)
+        }
+    }
+}
+
+/// Axum middleware that rejects ingest requests without a valid, fresh signature with `401`.
+///
+/// The body is buffered (up to the route's own limit) so the signature covers the bytes
+/// exactly as sent, before any CRC check or decompression.
+pub async fn verify_signature(
+    State(verifier): State<Arc<RequestVerifier>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, TelemetryIngestHttpError> {
+    let (parts, body) = req.into_parts();
+    let body = read_ingest_body(body, max_ingest_body_bytes(parts.uri.path())).await?;
+    let now = chrono::Utc::now().timestamp();
+    if let Err(err) = verifier.verify(
+        parts.method.as_str(),
+        parts.uri.path(),
+        &parts.headers,
+        &body,
+        now,
+    ) {
+        let code = failure_code(&err);
+        tracing::info!(
+            signature_check = "fail",
+            code,
+            "ingest signature check failed"
+        );
+        metrics::counter!("rustpulse_signature_failures_total", "code" => code).increment(1);
+        return Err(err);
+    }
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+/// Error code of a signature failure, used as the metric label.
+fn failure_code(err: &TelemetryIngestHttpError) -> &'static str {
+    match err {
+        TelemetryIngestHttpError::MissingSignature => "missing_signature",
+        TelemetryIngestHttpError::UnknownSigningKey => "unknown_signing_key",
+        TelemetryIngestHttpError::InvalidSignature => "invalid_signature",
+        TelemetryIngestHttpError::StaleTimestamp => "stale_timestamp",
+        TelemetryIngestHttpError::ReplayedNonce => "replayed_nonce",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::{Router, middleware, routing::post};
+    use tower::ServiceExt;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn verifier(nonce_cache_size: usize) -> RequestVerifier {
+        RequestVerifier::new(SigningConfig {
+            keys: HashMap::from([("edge-01".to_string(), b"s3cret".to_vec())]),
+            max_skew_secs: 300,
+            nonce_cache_size,
+        })
+    }
+
+    fn signed(key_id: &str, secret: &[u8], timestamp: i64, nonce: &str, body: &[u8]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(KEY_ID_HEADER, key_id.parse().unwrap());
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(NONCE_HEADER, nonce.parse().unwrap());
+        let signature = sign(secret, "POST", "/telemetry", timestamp, nonce, body);
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers
+    }
+
+    fn code(result: Result<(), TelemetryIngestHttpError>) -> &'static str {
+        result.map_or_else(|err| failure_code(&err), |()| "ok")
+    }
+
+    #[test]
+    fn test_sign_matches_reference_vector() {
+        // The agent signs with this function too; the vector pins the wire format.
+        assert_eq!(
+            sign(b"s3cret", "POST", "/telemetry", NOW, "n-1", br#"{"cpu":1}"#),
+            "b9374a7a076e60232dac81560e07239b4208e092e900e73a5e5f5a145667fd5c"
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_each_failure() {
+        let verifier = verifier(16);
+        let verify = |headers: &HeaderMap, body: &[u8]| {
+            code(verifier.verify("POST", "/telemetry", headers, body, NOW))
+        };
+        let body = br#"{"cpu": 1}"#;
+
+        assert_eq!(verify(&HeaderMap::new(), body), "missing_signature");
+        assert_eq!(
+            verify(&signed("edge-02", b"s3cret", NOW, "a", body), body),
+            "unknown_signing_key"
+        );
+        assert_eq!(
+            verify(&signed("edge-01", b"wrong", NOW, "b", body), body),
+            "invalid_signature"
+        );
+        assert_eq!(
+            verify(
+                &signed("edge-01", b"s3cret", NOW, "c", body),
+                br#"{"cpu": 2}"#
+            ),
+            "invalid_signature"
+        );
+        assert_eq!(
+            verify(&signed("edge-01", b"s3cret", NOW - 301, "d", body), body),
+            "stale_timestamp"
+        );
+        assert_eq!(
+            verify(&signed("edge-01", b"s3cret", NOW + 301, "e", body), body),
+            "stale_timestamp"
+        );
+        assert_eq!(
+            code(verifier.verify(
+                "POST",
+                "/telemetry/batch",
+                &signed("edge-01", b"s3cret", NOW, "f", body),
+                body,
+                NOW
+            )),
+            "invalid_signature"
+        );
+
+        let headers = signed("edge-01", b"s3cret", NOW - 300, "g", body);
+        assert_eq!(verify(&headers, body), "ok");
+        assert_eq!(verify(&headers, body), "replayed_nonce");
+        // A failed attempt does not burn the nonce.
+        assert_eq!(
+            verify(&signed("edge-01", b"s3cret", NOW, "b", body), body),
+            "ok"
+        );
+    }
+
+    #[test]
+    fn test_nonce_cache_expires_and_stays_bounded() {
+        let mut cache = NonceCache::default();
+        assert!(cache.insert("a".to_string(), NOW + 10, NOW, 2));
+        assert!(!cache.insert("a".to_string(), NOW + 10, NOW + 5, 2));
+        assert!(!cache.insert("a".to_string(), NOW + 20, NOW + 10, 2));
+        assert!(cache.insert("a".to_string(), NOW + 20, NOW + 11, 2));
+
+        assert!(cache.insert("b".to_string(), NOW + 30, NOW + 11, 2));
+        assert!(cache.insert("c".to_string(), NOW + 30, NOW + 11, 2));
+        assert_eq!(cache.order.len(), 2);
+        assert_eq!((cache.evicted, cache.warned_at), (0, Some(NOW + 11)));
+        assert!(cache.insert("a".to_string(), NOW + 30, NOW + 11, 2));
+        // Later evictions are only counted until the next warning is due.
+        assert_eq!((cache.evicted, cache.warned_at), (1, Some(NOW + 11)));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_passes_signed_body_through() {
+        let verifier = Arc::new(verifier(16));
+        let app = Router::new()
+            .route("/telemetry", post(|body: String| async move { body }))
+            .route_layer(middleware::from_fn_with_state(verifier, verify_signature));
+        let body = r#"{"cpu": 1}"#;
+        let request = |timestamp: i64, nonce: &str| {
+            let mut req = Request::builder()
+                .method("POST")
+                .uri("/telemetry")
+                .body(Body::from(body))
+                .unwrap();
+            *req.headers_mut() = signed("edge-01", b"s3cret", timestamp, nonce, body.as_bytes());
+            req
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        let resp = app.clone().oneshot(request(now, "n-1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes, body.as_bytes());
+
+        let resp = app.oneshot(request(now - 3_600, "n-2")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "stale_timestamp");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_buffers_at_most_the_route_limit() {
+        let verifier = Arc::new(verifier(16));
+        let app = Router::new()
+            .route("/telemetry", post(|| async { "" }))
+            .route_layer(middleware::from_fn_with_state(verifier, verify_signature));
+        // Within the batch limit, but over the 1 MiB of `POST /telemetry`.
+        let req = Request::builder()
+            .method("POST")
+            .uri("/telemetry")
+            .body(Body::from(vec![b' '; 2 * 1024 * 1024]))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -7,6 +7,7 @@ use axum::{
     response::Response,
 };
 use opentelemetry::trace::TraceContextExt as _;
+use std::time::Instant;
 use tracing::Instrument as _;
 use tracing::field;
 use tracing_opentelemetry::OpenTelemetrySpanExt as _;
@@ -65,8 +66,16 @@ pub async fn trace_middleware(req: Request, next: Next) -> Response {
         }
     }
 
+    let start = Instant::now();
     let mut response = next.run(req).instrument(span.clone()).await;
     span.record("http.status_code", response.status().as_u16());
+    metrics::histogram!(
+        "rustpulse_http_request_duration_seconds",
+        "method" => method,
+        "route" => route,
+        "status" => response.status().as_u16().to_string(),
+    )
+    .record(start.elapsed().as_secs_f64());
 
     if let Ok(value) = HeaderValue::from_str(&request_id) {
         response
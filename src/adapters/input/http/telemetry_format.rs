@@ -0,0 +1,253 @@
+//! Response encodings of `GET /metrics`: compact JSON, NDJSON, CSV and protobuf.
+//!
+//! The format comes from `?format=` when present, otherwise from the `Accept` header. Bodies are
+//! streamed one record per chunk instead of being rendered into a single buffer.
+
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, header};
+use chrono::SecondsFormat;
+use futures_util::future::ready;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use prost::Message;
+use serde_json::Value;
+
+use super::telemetry_proto::{PROTOBUF_MEDIA_TYPE, v1};
+use crate::core::application::telemetry::TelemetryStream;
+use crate::core::domains::telemetry::Telemetry;
+
+/// Response header carrying the cursor of the next page (all formats).
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+const CSV_COLUMNS: [&str; 6] = [
+    "source_id",
+    "server_id",
+    "timestamp",
+    "cpu",
+    "memory",
+    "temperature",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Encoding of a telemetry page.
+pub enum MetricsFormat {
+    /// `{"items":[...],"next_cursor":...}` (`application/json`).
+    #[default]
+    Json,
+    /// One JSON record per line (`application/x-ndjson`).
+    Ndjson,
+    /// RFC 4180 CSV with a header row (`text/csv`).
+    Csv,
+    /// A `rustpulse.telemetry.v1.TelemetryPage` message (`application/x-protobuf`).
+    Protobuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Why no format could be chosen.
+pub enum FormatError {
+    /// `?format=` names an unknown format.
+    UnknownFormat,
+    /// No media type in `Accept` is one this endpoint produces.
+    NotAcceptable,
+}
+
+impl MetricsFormat {
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" | "application/*" | "*/*" => Some(Self::Json),
+            "application/x-ndjson" | "application/ndjson" => Some(Self::Ndjson),
+            "text/csv" | "text/*" => Some(Self::Csv),
+            PROTOBUF_MEDIA_TYPE | "application/protobuf" => Some(Self::Protobuf),
+            _ => None,
+        }
+    }
+
+    /// Picks the format from `?format=` (`json`, `ndjson`, `csv`, `protobuf`), else from `Accept`.
+    ///
+    /// A missing or empty `Accept` means JSON; otherwise the supported media type with the
+    /// highest `q` wins, ties going to the first listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use axum::http::{HeaderMap, HeaderValue, header};
+    /// use rustpulse::adapters::input::http::telemetry_format::MetricsFormat;
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(header::ACCEPT, HeaderValue::from_static("text/csv;q=0.5, application/x-ndjson"));
+    /// assert_eq!(MetricsFormat::negotiate(None, &headers), Ok(MetricsFormat::Ndjson));
+    /// assert_eq!(MetricsFormat::negotiate(Some("csv"), &headers), Ok(MetricsFormat::Csv));
+    /// ```
+    pub fn negotiate(format: Option<&str>, headers: &HeaderMap) -> Result<Self, FormatError> {
+        if let Some(format) = format {
+            return match format.trim().to_ascii_lowercase().as_str() {
+                "json" => Ok(Self::Json),
+                "ndjson" => Ok(Self::Ndjson),
+                "csv" => Ok(Self::Csv),
+                "protobuf" => Ok(Self::Protobuf),
+                _ => Err(FormatError::UnknownFormat),
+            };
+        }
+
+        let accept = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        if accept.trim().is_empty() {
+            return Ok(Self::Json);
+        }
+
+        let mut best: Option<(f32, Self)> = None;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_type = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let q = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if let Some(format) = Self::from_media_type(&media_type)
+                && q > 0.0
+                && best.is_none_or(|(best_q, _)| q > best_q)
+            {
+                best = Some((q, format));
+            }
+        }
+        best.map(|(_, format)| format)
+            .ok_or(FormatError::NotAcceptable)
+    }
+
+    /// `Content-Type` of bodies in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Protobuf => "application/x-protobuf; proto=rustpulse.telemetry.v1.TelemetryPage",
+        }
+    }
+
+    /// Streams `records` in this format.
+    ///
+    /// `next_cursor` is only part of JSON and protobuf bodies (the others rely on
+    /// [`NEXT_CURSOR_HEADER`]). Protobuf bodies are written as one `TelemetryPage` per record
+    /// followed by one holding the cursor, which decode as a single message.
+    /// `extras_columns` only applies to CSV: each key becomes an `extras_<key>` column and is
+    /// removed from the `extras` JSON column. An error from `records` aborts the body, so
+    /// clients see a truncated response rather than a well-formed partial one.
+    pub fn body(
+        self,
+        records: TelemetryStream,
+        next_cursor: Option<String>,
+        extras_columns: Vec<String>,
+    ) -> Body {
+        let chunks: BoxStream<'static, anyhow::Result<Bytes>> = match self {
+            Self::Json => {
+                let mut first = true;
+                let records = records.map(move |telemetry| {
+                    let mut chunk = if std::mem::take(&mut first) {
+                        Vec::new()
+                    } else {
+                        vec![b',']
+                    };
+                    serde_json::to_writer(&mut chunk, &telemetry?)?;
+                    Ok(Bytes::from(chunk))
+                });
+                let footer = serde_json::to_vec(&next_cursor)
+                    .map(|cursor| {
+                        Bytes::from([b"],\"next_cursor\":".as_slice(), &cursor, b"}"].concat())
+                    })
+                    .map_err(anyhow::Error::from);
+                stream::once(ready(Ok(Bytes::from_static(b"{\"items\":["))))
+                    .chain(records)
+                    .chain(stream::once(ready(footer)))
+                    .boxed()
+            }
+            Self::Ndjson => records
+                .map(|telemetry| {
+                    let mut line = serde_json::to_vec(&telemetry?)?;
+                    line.push(b'\n');
+                    Ok(Bytes::from(line))
+                })
+                .boxed(),
+            Self::Csv => {
+                let header = CSV_COLUMNS
+                    .iter()
+                    .map(|column| column.to_string())
+                    .chain(extras_columns.iter().map(|key| format!("extras_{key}")))
+                    .chain(std::iter::once("extras".to_string()));
+                let header = Bytes::from(csv_row(header));
+                stream::once(ready(Ok(header)))
+                    .chain(records.map(move |telemetry| {
+                        Ok(Bytes::from(csv_record(telemetry?, &extras_columns)))
+                    }))
+                    .boxed()
+            }
+            Self::Protobuf => {
+                let records = records.map(|telemetry| {
+                    let page = v1::TelemetryPage {
+                        items: vec![v1::Telemetry::from(&telemetry?)],
+                        next_cursor: None,
+                    };
+                    Ok(Bytes::from(page.encode_to_vec()))
+                });
+                let footer = v1::TelemetryPage {
+                    items: Vec::new(),
+                    next_cursor,
+                };
+                records
+                    .chain(stream::once(ready(Ok(Bytes::from(footer.encode_to_vec())))))
+                    .boxed()
+            }
+        };
+        Body::from_stream(chunks)
+    }
+}
+
+/// Joins fields into one CSV line, quoting those that need it.
+fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut row = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+fn csv_record(telemetry: Telemetry, extras_columns: &[String]) -> String {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    let mut extras = telemetry.extras;
+    let lifted: Vec<String> = extras_columns
+        .iter()
+        .map(
+            |key| match extras.as_object_mut().and_then(|object| object.remove(key)) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => text,
+                Some(other) => other.to_string(),
+            },
+        )
+        .collect();
+
+    let fields = [
+        telemetry.source_id.to_string(),
+        telemetry.server_id.to_string(),
+        telemetry
+            .timestamp
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        optional(telemetry.cpu.map(|v| v.to_string())),
+        optional(telemetry.memory.map(|v| v.to_string())),
+        optional(telemetry.temperature.map(|v| v.to_string())),
+    ];
+    csv_row(
+        fields
+            .into_iter()
+            .chain(lifted)
+            .chain(std::iter::once(extras.to_string())),
+    )
+}
@@ -1,37 +1,62 @@
 //! HTTP handlers for telemetry ingest and query.
 
-use crate::core::application::telemetry::{TelemetryIngestCase, TelemetryQueryCase};
+use crate::adapters::input::http::auth::{AuthContext, CallerTenant};
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, BucketWidth, QuotaExceeded, SortOrder, StorageBackpressure,
+    TelemetryAggregateCase, TelemetryCursor, TelemetryIngestCase, TelemetryQuery,
+    TelemetryQueryCase, TelemetryQueryError,
+};
 use crate::core::domains::telemetry::Telemetry;
 use axum::Json;
+use axum::body::{Body, Bytes};
 use axum::extract::{Query, Request, State};
-use axum::http::{HeaderMap, StatusCode};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::routing::{get, post};
 use axum::{Router, middleware, response::IntoResponse};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
 use std::collections::HashMap;
+use std::io::Read;
 use std::sync::Arc;
 use tracing::instrument;
+use uuid::Uuid;
 
 use super::request_tracing;
+use super::telemetry_format::{FormatError, MetricsFormat, NEXT_CURSOR_HEADER};
+use super::telemetry_proto::{self, PROTOBUF_MEDIA_TYPE};
+use prost::Message;
 
 #[instrument(level = "info", skip(service))]
-/// Router for querying metrics via `GET /metrics`.
+/// Router for querying metrics via `GET /metrics` and `GET /metrics/export`.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// # async fn demo() -> anyhow::Result<()> {
 /// use rustpulse::adapters::input::http::telemetry_handler;
-/// use rustpulse::core::application::telemetry::TelemetryQueryCase;
+/// use rustpulse::core::application::telemetry::{TelemetryPage, TelemetryQuery, TelemetryQueryCase};
 /// use rustpulse::core::domains::telemetry::Telemetry;
+/// use rustpulse::core::domains::tenant::TenantId;
 /// use std::sync::Arc;
 ///
 /// struct DummyQuery;
 ///
 /// #[async_trait::async_trait]
 /// impl TelemetryQueryCase for DummyQuery {
-///     async fn fetch_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+///     async fn fetch_all(
+///         &self,
+///         _tenant: &TenantId,
+///         _node_id: Option<String>,
+///     ) -> anyhow::Result<Vec<Telemetry>> {
 ///         Ok(Vec::new())
 ///     }
+///     async fn fetch_page(
+///         &self,
+///         _tenant: &TenantId,
+///         _query: TelemetryQuery,
+///     ) -> anyhow::Result<TelemetryPage> {
+///         Ok(TelemetryPage::default())
+///     }
 /// }
 ///
 /// let service: Arc<dyn TelemetryQueryCase> = Arc::new(DummyQuery);
@@ -42,14 +67,82 @@ use super::request_tracing;
 pub fn routes(service: Arc<dyn TelemetryQueryCase>) -> Router {
     Router::new()
         .route("/metrics", get(fetch_telemetry_handler))
+        .route("/metrics/export", get(export_telemetry_handler))
+        .with_state(service)
+        .route_layer(middleware::from_fn(request_tracing::trace_middleware))
+}
+
+#[instrument(level = "info", skip(service))]
+/// Router for bucketed aggregates via `GET /metrics/aggregate`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use rustpulse::adapters::input::http::telemetry_handler;
+/// use rustpulse::core::application::telemetry::{
+///     AggregateBucket, AggregateQuery, TelemetryAggregateCase,
+/// };
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyAggregate;
+///
+/// #[async_trait::async_trait]
+/// impl TelemetryAggregateCase for DummyAggregate {
+///     async fn aggregate(
+///         &self,
+///         _tenant: &TenantId,
+///         _query: AggregateQuery,
+///     ) -> anyhow::Result<Vec<AggregateBucket>> {
+///         Ok(Vec::new())
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryAggregateCase> = Arc::new(DummyAggregate);
+/// let _router = telemetry_handler::aggregate_routes(service);
+/// # Ok(())
+/// # }
+/// ```
+pub fn aggregate_routes(service: Arc<dyn TelemetryAggregateCase>) -> Router {
+    Router::new()
+        .route("/metrics/aggregate", get(aggregate_telemetry_handler))
         .with_state(service)
         .route_layer(middleware::from_fn(request_tracing::trace_middleware))
 }
 
 const MAX_TELEMETRY_BODY_BYTES: usize = 1024 * 1024;
+const MAX_TELEMETRY_BATCH_BODY_BYTES: usize = 8 * 1024 * 1024;
+const MAX_TELEMETRY_BATCH_ITEMS: usize = 5_000;
+
+/// Body size limit of the ingest route at `path`.
+pub(crate) fn max_ingest_body_bytes(path: &str) -> usize {
+    match path {
+        "/telemetry/batch" => MAX_TELEMETRY_BATCH_BODY_BYTES,
+        _ => MAX_TELEMETRY_BODY_BYTES,
+    }
+}
+
+/// Buffers an ingest body of at most `max_bytes`.
+///
+/// Only a body over the limit is `BodyTooLarge`; one that fails to arrive is `InvalidJson`.
+pub(crate) async fn read_ingest_body(
+    body: Body,
+    max_bytes: usize,
+) -> Result<Bytes, TelemetryIngestHttpError> {
+    axum::body::to_bytes(body, max_bytes).await.map_err(|e| {
+        let too_large = std::iter::successors(std::error::Error::source(&e), |e| e.source())
+            .any(|e| e.is::<http_body_util::LengthLimitError>());
+        if too_large {
+            TelemetryIngestHttpError::BodyTooLarge
+        } else {
+            TelemetryIngestHttpError::InvalidJson
+        }
+    })
+}
 
 #[instrument(level = "info", skip(service))]
-/// Router for ingesting telemetry via `POST /telemetry`.
+/// Router for ingesting telemetry via `POST /telemetry` and `POST /telemetry/batch`.
 ///
 /// # Examples
 ///
@@ -58,13 +151,21 @@ const MAX_TELEMETRY_BODY_BYTES: usize = 1024 * 1024;
 /// use rustpulse::adapters::input::http::telemetry_handler;
 /// use rustpulse::core::application::telemetry::TelemetryIngestCase;
 /// use rustpulse::core::domains::telemetry::Telemetry;
+/// use rustpulse::core::domains::tenant::TenantId;
 /// use std::sync::Arc;
 ///
 /// struct DummyIngest;
 ///
 /// #[async_trait::async_trait]
 /// impl TelemetryIngestCase for DummyIngest {
-///     async fn ingest(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
+///     async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+///         Ok(())
+///     }
+///     async fn ingest_batch(
+///         &self,
+///         _tenant: &TenantId,
+///         _batch: Vec<Telemetry>,
+///     ) -> anyhow::Result<()> {
 ///         Ok(())
 ///     }
 /// }
@@ -77,6 +178,7 @@ const MAX_TELEMETRY_BODY_BYTES: usize = 1024 * 1024;
 pub fn ingest_routes(service: Arc<dyn TelemetryIngestCase + Send + Sync>) -> Router {
     Router::new()
         .route("/telemetry", post(ingest_telemetry_handler))
+        .route("/telemetry/batch", post(ingest_telemetry_batch_handler))
         .with_state(service)
         .route_layer(middleware::from_fn(request_tracing::trace_middleware))
 }
@@ -88,20 +190,66 @@ pub enum TelemetryIngestHttpError {
     InvalidCrc,
     /// The CRC was valid but does not match the request body.
     CrcMismatch,
+    /// Signed ingest is enabled and a signature header is missing.
+    MissingSignature,
+    /// `X-Signature-Key-Id` names no configured signing key.
+    UnknownSigningKey,
+    /// The signature is malformed or does not match the request.
+    InvalidSignature,
+    /// `X-Signature-Timestamp` is outside the allowed clock skew.
+    StaleTimestamp,
+    /// `X-Signature-Nonce` was already used within the skew window.
+    ReplayedNonce,
     /// The request body is not valid telemetry JSON (or could not be read).
     InvalidJson,
+    /// The `application/x-protobuf` body is not a valid `rustpulse.telemetry.v1` message.
+    InvalidProtobuf,
+    /// `Content-Encoding` is neither `gzip` nor `zstd`.
+    UnsupportedEncoding,
+    /// The body could not be decompressed as its `Content-Encoding` says.
+    InvalidEncoding,
+    /// The body, compressed or decompressed, exceeds the endpoint's size limit.
+    BodyTooLarge,
+    /// The batch contained no items.
+    EmptyBatch,
+    /// The batch contained more items than the server accepts in one request.
+    BatchTooLarge,
+    /// The caller's API key is bound to a different `server_id`.
+    ServerIdMismatch,
+    /// The caller sent requests faster than its rate limit allows.
+    RateLimited {
+        /// Seconds until the next request will be accepted.
+        retry_after_secs: u64,
+    },
+    /// The tenant has ingested its daily record quota.
+    QuotaExceeded {
+        /// Seconds until the quota resets.
+        retry_after_secs: u64,
+    },
+    /// Storage is saturated (e.g. the write-ahead log is full).
+    Backpressure {
+        /// Suggested delay before retrying.
+        retry_after_secs: u64,
+    },
     /// The ingest use case returned an error.
     IngestFailed,
 }
 
 #[derive(serde::Serialize)]
-struct ErrorResponse {
-    code: &'static str,
-    message: String,
+/// JSON error body shared by the HTTP adapters: `{"code": "...", "message": "..."}`.
+pub(crate) struct ErrorResponse {
+    pub(crate) code: &'static str,
+    pub(crate) message: String,
 }
 
 impl IntoResponse for TelemetryIngestHttpError {
     fn into_response(self) -> axum::response::Response {
+        let retry_after = match self {
+            Self::RateLimited { retry_after_secs }
+            | Self::QuotaExceeded { retry_after_secs }
+            | Self::Backpressure { retry_after_secs } => Some(retry_after_secs),
+            _ => None,
+        };
         let (status, code, message) = match self {
             Self::InvalidCrc => (
                 StatusCode::BAD_REQUEST,
@@ -113,11 +261,88 @@ impl IntoResponse for TelemetryIngestHttpError {
                 "crc_mismatch",
                 "CRC does not match request body".to_string(),
             ),
+            Self::MissingSignature => (
+                StatusCode::UNAUTHORIZED,
+                "missing_signature",
+                "Signed ingest requires X-Signature-Key-Id, X-Signature-Timestamp, \
+                 X-Signature-Nonce and X-Signature"
+                    .to_string(),
+            ),
+            Self::UnknownSigningKey => (
+                StatusCode::UNAUTHORIZED,
+                "unknown_signing_key",
+                "X-Signature-Key-Id does not name a signing key".to_string(),
+            ),
+            Self::InvalidSignature => (
+                StatusCode::UNAUTHORIZED,
+                "invalid_signature",
+                "Signature is malformed or does not match the request".to_string(),
+            ),
+            Self::StaleTimestamp => (
+                StatusCode::UNAUTHORIZED,
+                "stale_timestamp",
+                "X-Signature-Timestamp is outside the allowed clock skew".to_string(),
+            ),
+            Self::ReplayedNonce => (
+                StatusCode::UNAUTHORIZED,
+                "replayed_nonce",
+                "X-Signature-Nonce was already used".to_string(),
+            ),
             Self::InvalidJson => (
                 StatusCode::BAD_REQUEST,
                 "invalid_json",
                 "Request body must be valid telemetry JSON".to_string(),
             ),
+            Self::InvalidProtobuf => (
+                StatusCode::BAD_REQUEST,
+                "invalid_protobuf",
+                "Request body must be a valid rustpulse.telemetry.v1 protobuf message".to_string(),
+            ),
+            Self::UnsupportedEncoding => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_encoding",
+                "Content-Encoding must be gzip, zstd or identity".to_string(),
+            ),
+            Self::InvalidEncoding => (
+                StatusCode::BAD_REQUEST,
+                "invalid_encoding",
+                "Request body could not be decompressed".to_string(),
+            ),
+            Self::BodyTooLarge => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "body_too_large",
+                "Request body is too large".to_string(),
+            ),
+            Self::EmptyBatch => (
+                StatusCode::BAD_REQUEST,
+                "empty_batch",
+                "Batch must contain at least one telemetry item".to_string(),
+            ),
+            Self::BatchTooLarge => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "batch_too_large",
+                format!("Batch must not exceed {MAX_TELEMETRY_BATCH_ITEMS} items"),
+            ),
+            Self::ServerIdMismatch => (
+                StatusCode::FORBIDDEN,
+                "server_id_mismatch",
+                "Telemetry server_id does not match the API key binding".to_string(),
+            ),
+            Self::RateLimited { .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                "Too many ingest requests; retry later".to_string(),
+            ),
+            Self::QuotaExceeded { .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "quota_exceeded",
+                "Daily telemetry quota exhausted for this tenant".to_string(),
+            ),
+            Self::Backpressure { .. } => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "backpressure",
+                "Ingest buffer is full; retry later".to_string(),
+            ),
             Self::IngestFailed => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "internal",
@@ -125,15 +350,39 @@ impl IntoResponse for TelemetryIngestHttpError {
             ),
         };
 
-        (status, Json(ErrorResponse { code, message })).into_response()
+        let mut response = (status, Json(ErrorResponse { code, message })).into_response();
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+impl From<anyhow::Error> for TelemetryIngestHttpError {
+    fn from(err: anyhow::Error) -> Self {
+        if let Some(exceeded) = err.downcast_ref::<QuotaExceeded>() {
+            return Self::QuotaExceeded {
+                retry_after_secs: exceeded.retry_after_secs,
+            };
+        }
+        match err.downcast_ref::<StorageBackpressure>() {
+            Some(backpressure) => Self::Backpressure {
+                retry_after_secs: backpressure.retry_after_secs,
+            },
+            None => Self::IngestFailed,
+        }
     }
 }
 
 #[instrument(name = "ingest telemetry", level = "info", skip(service, req))]
 /// Handles `POST /telemetry`.
 ///
-/// Accepts JSON telemetry payloads and optionally validates the request body
-/// against the `X-CRC32` header.
+/// Accepts JSON telemetry payloads, or a `rustpulse.telemetry.v1.Telemetry` message with
+/// `Content-Type: application/x-protobuf`, and optionally validates the raw request body
+/// against the `X-CRC32` header. Bodies may be compressed with `Content-Encoding: gzip` or
+/// `zstd`; the CRC then covers the compressed bytes.
 ///
 /// # Examples
 ///
@@ -142,12 +391,20 @@ impl IntoResponse for TelemetryIngestHttpError {
 /// use rustpulse::adapters::input::http::telemetry_handler;
 /// use rustpulse::core::application::telemetry::TelemetryIngestCase;
 /// use rustpulse::core::domains::telemetry::Telemetry;
+/// use rustpulse::core::domains::tenant::TenantId;
 /// use std::sync::Arc;
 ///
 /// struct DummyIngest;
 /// #[async_trait::async_trait]
 /// impl TelemetryIngestCase for DummyIngest {
-///     async fn ingest(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
+///     async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+///         Ok(())
+///     }
+///     async fn ingest_batch(
+///         &self,
+///         _tenant: &TenantId,
+///         _batch: Vec<Telemetry>,
+///     ) -> anyhow::Result<()> {
 ///         Ok(())
 ///     }
 /// }
@@ -159,31 +416,327 @@ impl IntoResponse for TelemetryIngestHttpError {
 /// ```
 pub async fn ingest_telemetry_handler(
     State(service): State<Arc<dyn TelemetryIngestCase + Send + Sync>>,
+    CallerTenant(tenant): CallerTenant,
     req: Request,
 ) -> Result<StatusCode, TelemetryIngestHttpError> {
+    let encoding = BodyEncoding::of(req.headers());
+    let bound_server = bound_server_id(&req);
+    let body = read_checked_body(req, MAX_TELEMETRY_BODY_BYTES).await?;
+
+    let telemetry: Telemetry = match encoding {
+        BodyEncoding::Protobuf => telemetry_proto::v1::Telemetry::decode(body)
+            .ok()
+            .and_then(|message| Telemetry::try_from(message).ok())
+            .ok_or(TelemetryIngestHttpError::InvalidProtobuf)?,
+        _ => serde_json::from_slice(&body).map_err(|_| TelemetryIngestHttpError::InvalidJson)?,
+    };
+
+    if bound_server.is_some_and(|id| id != telemetry.server_id) {
+        tracing::info!("telemetry rejected: server_id does not match api key");
+        return Err(TelemetryIngestHttpError::ServerIdMismatch);
+    }
+
+    service.ingest(&tenant, telemetry).await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// `server_id` the authenticated API key is bound to, if any.
+fn bound_server_id(req: &Request) -> Option<Uuid> {
+    req.extensions()
+        .get::<AuthContext>()
+        .and_then(|context| context.server_id)
+}
+
+/// Reads the request body, validates it against the optional `X-CRC32` header and decompresses
+/// it according to `Content-Encoding`.
+///
+/// The CRC covers the body exactly as transmitted, so a compressed body is checked before it is
+/// inflated. `max_bytes` bounds both the transmitted and the decompressed size.
+async fn read_checked_body(
+    req: Request,
+    max_bytes: usize,
+) -> Result<Bytes, TelemetryIngestHttpError> {
     let provided_crc = parse_crc32_header(req.headers())?;
+    let content_encoding = ContentEncoding::of(req.headers())?;
 
-    let body = axum::body::to_bytes(req.into_body(), MAX_TELEMETRY_BODY_BYTES)
-        .await
-        .map_err(|_| TelemetryIngestHttpError::InvalidJson)?;
+    let body = read_ingest_body(req.into_body(), max_bytes).await?;
 
     if let Some(expected) = provided_crc {
         let actual = crc32_ieee(&body);
         if actual != expected {
             tracing::info!(crc_check = "fail", "telemetry CRC check failed");
+            metrics::counter!("rustpulse_crc_check_failures_total").increment(1);
             return Err(TelemetryIngestHttpError::CrcMismatch);
         }
     }
 
-    let telemetry: Telemetry =
-        serde_json::from_slice(&body).map_err(|_| TelemetryIngestHttpError::InvalidJson)?;
+    content_encoding.decode(body, max_bytes)
+}
 
-    service
-        .ingest(telemetry)
-        .await
-        .map_err(|_| TelemetryIngestHttpError::IngestFailed)?;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Compression of an ingest request body, from its `Content-Encoding`.
+enum ContentEncoding {
+    Identity,
+    Gzip,
+    Zstd,
+}
 
-    Ok(StatusCode::ACCEPTED)
+impl ContentEncoding {
+    fn of(headers: &HeaderMap) -> Result<Self, TelemetryIngestHttpError> {
+        let Some(value) = headers.get(header::CONTENT_ENCODING) else {
+            return Ok(Self::Identity);
+        };
+        let value = value
+            .to_str()
+            .map_err(|_| TelemetryIngestHttpError::UnsupportedEncoding)?;
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => Ok(Self::Identity),
+            "gzip" | "x-gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err(TelemetryIngestHttpError::UnsupportedEncoding),
+        }
+    }
+
+    /// Inflates `body`, reading at most one byte past `max_bytes` so a small, highly compressed
+    /// body cannot expand without bound.
+    ///
+    /// Every gzip member is read, not just the first. Zstd frames may not use a window larger
+    /// than `max_bytes`, so a frame cannot make the decoder allocate more than the body limit.
+    fn decode(self, body: Bytes, max_bytes: usize) -> Result<Bytes, TelemetryIngestHttpError> {
+        let limit = max_bytes as u64 + 1;
+        let mut decoded = Vec::new();
+        let read = match self {
+            Self::Identity => return Ok(body),
+            Self::Gzip => flate2::read::MultiGzDecoder::new(body.as_ref())
+                .take(limit)
+                .read_to_end(&mut decoded),
+            Self::Zstd => {
+                zstd::stream::read::Decoder::new(body.as_ref()).and_then(|mut decoder| {
+                    decoder.window_log_max(zstd_window_log(max_bytes))?;
+                    decoder.take(limit).read_to_end(&mut decoded)
+                })
+            }
+        };
+        read.map_err(|_| TelemetryIngestHttpError::InvalidEncoding)?;
+        if decoded.len() > max_bytes {
+            return Err(TelemetryIngestHttpError::BodyTooLarge);
+        }
+        Ok(Bytes::from(decoded))
+    }
+}
+
+/// Smallest zstd window log covering `max_bytes`, within the range zstd accepts.
+fn zstd_window_log(max_bytes: usize) -> u32 {
+    let log = usize::BITS - max_bytes.saturating_sub(1).leading_zeros();
+    log.clamp(10, 31)
+}
+
+#[derive(Debug, serde::Serialize)]
+/// Outcome of one item in a batch ingest request.
+pub struct BatchItemResult {
+    /// Zero-based position of the item in the submitted batch.
+    pub index: usize,
+    /// `accepted` or `rejected`.
+    pub status: &'static str,
+    /// Machine-readable rejection reason.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    /// Human-readable rejection detail.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+/// Per-item report returned by `POST /telemetry/batch`.
+pub struct BatchIngestReport {
+    /// Number of items written.
+    pub accepted: usize,
+    /// Number of items rejected before reaching storage.
+    pub rejected: usize,
+    /// One entry per submitted item, in submission order.
+    pub items: Vec<BatchItemResult>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Encoding of an ingest request body, from its `Content-Type`.
+enum BodyEncoding {
+    Json,
+    Ndjson,
+    Protobuf,
+}
+
+impl BodyEncoding {
+    fn of(headers: &HeaderMap) -> Self {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .trim_start()
+            .to_ascii_lowercase();
+        if content_type.starts_with("application/x-ndjson") {
+            Self::Ndjson
+        } else if content_type.starts_with(PROTOBUF_MEDIA_TYPE)
+            || content_type.starts_with("application/protobuf")
+        {
+            Self::Protobuf
+        } else {
+            Self::Json
+        }
+    }
+}
+
+/// Splits a batch body into per-item parse results (JSON array, NDJSON or protobuf).
+fn parse_batch_items(
+    body: &[u8],
+    encoding: BodyEncoding,
+) -> Result<Vec<Result<Telemetry, String>>, TelemetryIngestHttpError> {
+    if encoding == BodyEncoding::Protobuf {
+        let batch = telemetry_proto::v1::TelemetryBatch::decode(body)
+            .map_err(|_| TelemetryIngestHttpError::InvalidProtobuf)?;
+        return Ok(batch
+            .items
+            .into_iter()
+            .map(|message| Telemetry::try_from(message).map_err(|e| e.to_string()))
+            .collect());
+    }
+    if encoding == BodyEncoding::Ndjson {
+        let text = std::str::from_utf8(body).map_err(|_| TelemetryIngestHttpError::InvalidJson)?;
+        return Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<Telemetry>(line).map_err(|e| e.to_string()))
+            .collect());
+    }
+
+    let values: Vec<serde_json::Value> =
+        serde_json::from_slice(body).map_err(|_| TelemetryIngestHttpError::InvalidJson)?;
+    Ok(values
+        .into_iter()
+        .map(|v| serde_json::from_value::<Telemetry>(v).map_err(|e| e.to_string()))
+        .collect())
+}
+
+#[instrument(
+    name = "ingest telemetry batch",
+    level = "info",
+    skip(service, req),
+    fields(batch_size = tracing::field::Empty, rejected = tracing::field::Empty)
+)]
+/// Handles `POST /telemetry/batch`.
+///
+/// Accepts either a JSON array of telemetry objects, one telemetry object per line with
+/// `Content-Type: application/x-ndjson`, or a `rustpulse.telemetry.v1.TelemetryBatch` with
+/// `Content-Type: application/x-protobuf`.
+/// The whole body is validated against the optional `X-CRC32` header (over the compressed bytes
+/// when `Content-Encoding` is `gzip` or `zstd`); items
+/// that fail to parse are reported individually while the valid ones are
+/// written in a single storage call.
+///
+/// Responds with `202 Accepted` when at least one item was written and
+/// `422 Unprocessable Entity` when every item was rejected, both carrying a
+/// [`BatchIngestReport`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use rustpulse::adapters::input::http::telemetry_handler;
+/// use rustpulse::core::application::telemetry::TelemetryIngestCase;
+/// use rustpulse::core::domains::telemetry::Telemetry;
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyIngest;
+/// #[async_trait::async_trait]
+/// impl TelemetryIngestCase for DummyIngest {
+///     async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+///         Ok(())
+///     }
+///     async fn ingest_batch(
+///         &self,
+///         _tenant: &TenantId,
+///         _batch: Vec<Telemetry>,
+///     ) -> anyhow::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryIngestCase + Send + Sync> = Arc::new(DummyIngest);
+/// let _router = telemetry_handler::ingest_routes(service);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn ingest_telemetry_batch_handler(
+    State(service): State<Arc<dyn TelemetryIngestCase + Send + Sync>>,
+    CallerTenant(tenant): CallerTenant,
+    req: Request,
+) -> Result<(StatusCode, Json<BatchIngestReport>), TelemetryIngestHttpError> {
+    let encoding = BodyEncoding::of(req.headers());
+    let bound_server = bound_server_id(&req);
+    let body = read_checked_body(req, MAX_TELEMETRY_BATCH_BODY_BYTES).await?;
+
+    let parsed = parse_batch_items(&body, encoding)?;
+    if parsed.is_empty() {
+        return Err(TelemetryIngestHttpError::EmptyBatch);
+    }
+    if parsed.len() > MAX_TELEMETRY_BATCH_ITEMS {
+        return Err(TelemetryIngestHttpError::BatchTooLarge);
+    }
+
+    let mut valid = Vec::with_capacity(parsed.len());
+    let mut items = Vec::with_capacity(parsed.len());
+    for (index, item) in parsed.into_iter().enumerate() {
+        match item {
+            Ok(telemetry) if bound_server.is_some_and(|id| id != telemetry.server_id) => items
+                .push(BatchItemResult {
+                    index,
+                    status: "rejected",
+                    code: Some("server_id_mismatch"),
+                    message: Some("server_id does not match the API key binding".to_string()),
+                }),
+            Ok(telemetry) => {
+                valid.push(telemetry);
+                items.push(BatchItemResult {
+                    index,
+                    status: "accepted",
+                    code: None,
+                    message: None,
+                });
+            }
+            Err(message) => items.push(BatchItemResult {
+                index,
+                status: "rejected",
+                code: Some("invalid_item"),
+                message: Some(message),
+            }),
+        }
+    }
+
+    let accepted = valid.len();
+    let rejected = items.len() - accepted;
+    let span = tracing::Span::current();
+    span.record("batch_size", items.len());
+    span.record("rejected", rejected);
+
+    if accepted > 0 {
+        service.ingest_batch(&tenant, valid).await?;
+    }
+
+    let status = if accepted > 0 {
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+
+    Ok((
+        status,
+        Json(BatchIngestReport {
+            accepted,
+            rejected,
+            items,
+        }),
+    ))
 }
 
 fn parse_crc32_header(headers: &HeaderMap) -> Result<Option<u32>, TelemetryIngestHttpError> {
@@ -206,7 +759,16 @@ fn parse_crc32_header(headers: &HeaderMap) -> Result<Option<u32>, TelemetryInges
         .map_err(|_| TelemetryIngestHttpError::InvalidCrc)
 }
 
-fn crc32_ieee(bytes: &[u8]) -> u32 {
+/// CRC-32/IEEE of `bytes`, as checked against the `X-CRC32` header.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::adapters::input::http::telemetry_handler::crc32_ieee;
+///
+/// assert_eq!(crc32_ieee(b"123456789"), 0xCBF4_3926);
+/// ```
+pub fn crc32_ieee(bytes: &[u8]) -> u32 {
     let mut crc: u32 = 0xFFFF_FFFF;
     for &b in bytes {
         crc ^= b as u32;
@@ -218,28 +780,153 @@ fn crc32_ieee(bytes: &[u8]) -> u32 {
     !crc
 }
 
+#[derive(Debug)]
+/// Errors returned by the HTTP query endpoint.
+pub enum TelemetryQueryHttpError {
+    /// A query parameter could not be parsed.
+    InvalidParameter {
+        /// Name of the offending parameter.
+        name: &'static str,
+    },
+    /// The `cursor` parameter was not issued by this server.
+    InvalidCursor,
+    /// `Accept` lists no format the endpoint can produce.
+    NotAcceptable,
+    /// The query use case returned an error.
+    QueryFailed,
+}
+
+impl IntoResponse for TelemetryQueryHttpError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, code, message) = match self {
+            Self::InvalidParameter { name } => (
+                StatusCode::BAD_REQUEST,
+                "invalid_parameter",
+                format!("Invalid value for query parameter `{name}`"),
+            ),
+            Self::InvalidCursor => (
+                StatusCode::BAD_REQUEST,
+                "invalid_cursor",
+                "Cursor is malformed or was not issued by this server".to_string(),
+            ),
+            Self::NotAcceptable => (
+                StatusCode::NOT_ACCEPTABLE,
+                "not_acceptable",
+                "Supported formats: application/json, application/x-ndjson, text/csv, application/x-protobuf"
+                    .to_string(),
+            ),
+            Self::QueryFailed => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "Failed to fetch metrics".to_string(),
+            ),
+        };
+
+        (status, Json(ErrorResponse { code, message })).into_response()
+    }
+}
+
+fn parse_timestamp(
+    params: &HashMap<String, String>,
+    name: &'static str,
+) -> Result<Option<DateTime<Utc>>, TelemetryQueryHttpError> {
+    params
+        .get(name)
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(raw)
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|_| TelemetryQueryHttpError::InvalidParameter { name })
+        })
+        .transpose()
+}
+
+fn parse_metrics_query(
+    params: &HashMap<String, String>,
+) -> Result<TelemetryQuery, TelemetryQueryHttpError> {
+    let from = parse_timestamp(params, "from")?;
+    let to = parse_timestamp(params, "to")?;
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(TelemetryQueryHttpError::InvalidParameter { name: "to" });
+    }
+
+    let limit = match params.get("limit") {
+        None => TelemetryQuery::DEFAULT_LIMIT,
+        Some(raw) => raw
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=TelemetryQuery::MAX_LIMIT).contains(n))
+            .ok_or(TelemetryQueryHttpError::InvalidParameter { name: "limit" })?,
+    };
+
+    let order = params
+        .get("order")
+        .map(|raw| raw.parse::<SortOrder>())
+        .transpose()
+        .map_err(|_| TelemetryQueryHttpError::InvalidParameter { name: "order" })?
+        .unwrap_or_default();
+
+    let cursor = params
+        .get("cursor")
+        .map(|raw| TelemetryCursor::decode(raw))
+        .transpose()
+        .map_err(|_| TelemetryQueryHttpError::InvalidCursor)?;
+
+    Ok(TelemetryQuery {
+        source_id: params.get("source_id").cloned(),
+        from,
+        to,
+        limit: Some(limit),
+        cursor,
+        order,
+    })
+}
+
 #[instrument(name = "fetch telemetry", skip(service), fields(
+    tenant = %tenant,
     source_id = tracing::field::Empty
 ))]
 /// Handles `GET /metrics`.
 ///
-/// Supports an optional `source_id` query parameter to filter results.
+/// Supported query parameters:
+/// - `source_id`: only return telemetry for this source
+/// - `from` / `to`: RFC 3339 timestamps bounding the half-open range `[from, to)`
+/// - `limit`: page size (default 100, max 1000)
+/// - `order`: `asc` (default) or `desc`
+/// - `cursor`: the `next_cursor` returned by the previous page
+/// - `format`: `json`, `ndjson`, `csv` or `protobuf`; overrides the `Accept` header
+/// - `extras`: comma-separated `extras` keys written as their own CSV columns
+///
+/// The next page's cursor is also returned in the `x-next-cursor` header.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// # async fn demo() -> anyhow::Result<()> {
 /// use rustpulse::adapters::input::http::telemetry_handler;
-/// use rustpulse::core::application::telemetry::TelemetryQueryCase;
+/// use rustpulse::core::application::telemetry::{TelemetryPage, TelemetryQuery, TelemetryQueryCase};
 /// use rustpulse::core::domains::telemetry::Telemetry;
+/// use rustpulse::core::domains::tenant::TenantId;
 /// use std::sync::Arc;
 ///
 /// struct DummyQuery;
 /// #[async_trait::async_trait]
 /// impl TelemetryQueryCase for DummyQuery {
-///     async fn fetch_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+///     async fn fetch_all(
+///         &self,
+///         _tenant: &TenantId,
+///         _node_id: Option<String>,
+///     ) -> anyhow::Result<Vec<Telemetry>> {
 ///         Ok(Vec::new())
 ///     }
+///     async fn fetch_page(
+///         &self,
+///         _tenant: &TenantId,
+///         _query: TelemetryQuery,
+///     ) -> anyhow::Result<TelemetryPage> {
+///         Ok(TelemetryPage::default())
+///     }
 /// }
 ///
 /// let service: Arc<dyn TelemetryQueryCase> = Arc::new(DummyQuery);
@@ -249,104 +936,324 @@ fn crc32_ieee(bytes: &[u8]) -> u32 {
 /// ```
 pub async fn fetch_telemetry_handler(
     State(service): State<Arc<dyn TelemetryQueryCase>>,
+    CallerTenant(tenant): CallerTenant,
+    headers: HeaderMap,
     Query(params): Query<HashMap<String, String>>,
-) -> impl IntoResponse {
+) -> Result<axum::response::Response, TelemetryQueryHttpError> {
     let span = tracing::Span::current();
 
     if let Some(source_id) = params.get("source_id") {
         span.record("source_id", source_id.as_str());
     }
 
-    let source_id = params.get("source_id").cloned();
+    let query = parse_metrics_query(&params)?;
+    let (format, extras_columns) = parse_format(&params, &headers)?;
 
-    match service.fetch_all(source_id).await {
-        Ok(metrics) => {
+    match service.fetch_page(&tenant, query).await {
+        Ok(page) => {
             tracing::info!(
-                metrics_count = metrics.len(),
+                metrics_count = page.items.len(),
+                has_more = page.next_cursor.is_some(),
+                ?format,
                 "fetched metrics successfully."
             );
 
-            //only test purposes for now. Pretty Json causes high payload
-            match serde_json::to_string_pretty(&metrics) {
-                Ok(json) => (StatusCode::OK, json).into_response(),
-                Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            let next_cursor = page.next_cursor.map(|c| c.encode());
+            let mut response = (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, format.content_type())],
+                format.body(
+                    Box::pin(futures_util::stream::iter(page.items.into_iter().map(Ok))),
+                    next_cursor.clone(),
+                    extras_columns,
+                ),
+            )
+                .into_response();
+            if let Some(cursor) = next_cursor.and_then(|c| HeaderValue::from_str(&c).ok()) {
+                response.headers_mut().insert(NEXT_CURSOR_HEADER, cursor);
             }
+            Ok(response)
         }
         Err(_) => {
             tracing::error!("Failed to fetch metrics");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            Err(TelemetryQueryHttpError::QueryFailed)
         }
     }
 }
 
-#[cfg(test)]
-mod ingest_crc_tests {
-    use crate::core::application::telemetry::TelemetryIngestCase;
-    use crate::core::domains::telemetry::Telemetry;
+/// Reads the response format (`format` and `Accept`) and the CSV `extras` columns.
+fn parse_format(
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+) -> Result<(MetricsFormat, Vec<String>), TelemetryQueryHttpError> {
+    let format = MetricsFormat::negotiate(params.get("format").map(String::as_str), headers)
+        .map_err(|e| match e {
+            FormatError::UnknownFormat => {
+                TelemetryQueryHttpError::InvalidParameter { name: "format" }
+            }
+            FormatError::NotAcceptable => TelemetryQueryHttpError::NotAcceptable,
+        })?;
+    let extras_columns = params
+        .get("extras")
+        .map(|keys| {
+            keys.split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok((format, extras_columns))
+}
 
-    use async_trait::async_trait;
-    use axum::body::Body;
-    use axum::http::{Request, StatusCode};
-    use serde_json::Value;
-    use std::collections::BTreeMap;
-    use std::fmt;
-    use std::sync::atomic::{AtomicUsize, Ordering};
-    use std::sync::{Arc, Mutex};
-    use tower::ServiceExt;
-    use tracing::Subscriber;
-    use tracing::field::{Field, Visit};
-    use tracing_subscriber::Layer;
-    use tracing_subscriber::layer::Context;
-    use tracing_subscriber::prelude::*;
-    use tracing_subscriber::registry::LookupSpan;
+/// Same as [`parse_metrics_query`], except that `limit` is optional and unbounded.
+fn parse_export_query(
+    params: &HashMap<String, String>,
+) -> Result<TelemetryQuery, TelemetryQueryHttpError> {
+    let limit = params
+        .get("limit")
+        .map(|raw| {
+            raw.parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or(TelemetryQueryHttpError::InvalidParameter { name: "limit" })
+        })
+        .transpose()?;
+    let mut params = params.clone();
+    params.remove("limit");
+    Ok(TelemetryQuery {
+        limit,
+        ..parse_metrics_query(&params)?
+    })
+}
 
-    struct FakeIngest {
-        calls: Arc<AtomicUsize>,
-    }
+#[instrument(name = "export telemetry", skip(service), fields(tenant = %tenant))]
+/// Handles `GET /metrics/export`: streams every record matching the query in one response.
+///
+/// Takes the parameters of [`fetch_telemetry_handler`], except that `limit` is optional and has
+/// no maximum. Records are read from storage as the response is written, so memory use does not
+/// grow with the size of the range.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use rustpulse::adapters::input::http::telemetry_handler;
+/// use rustpulse::core::application::telemetry::TelemetryQueryCase;
+/// use std::sync::Arc;
+///
+/// # fn service() -> Arc<dyn TelemetryQueryCase> { unimplemented!() }
+/// // Served next to `GET /metrics`, e.g. `GET /metrics/export?from=2026-02-18T00:00:00Z&format=ndjson`.
+/// let _router = telemetry_handler::routes(service());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn export_telemetry_handler(
+    State(service): State<Arc<dyn TelemetryQueryCase>>,
+    CallerTenant(tenant): CallerTenant,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<axum::response::Response, TelemetryQueryHttpError> {
+    let query = parse_export_query(&params)?;
+    let (format, extras_columns) = parse_format(&params, &headers)?;
 
-    #[async_trait]
-    impl TelemetryIngestCase for FakeIngest {
-        async fn ingest(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
-            self.calls.fetch_add(1, Ordering::SeqCst);
-            Ok(())
+    match service.fetch_stream(&tenant, query).await {
+        Ok(records) => {
+            tracing::info!(?format, "streaming metrics export.");
+            let records = records.inspect_err(|e| {
+                tracing::error!(error = %e, "metrics export aborted");
+            });
+            Ok((
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, format.content_type())],
+                format.body(Box::pin(records), None, extras_columns),
+            )
+                .into_response())
         }
-    }
-
-    fn crc32_ieee(bytes: &[u8]) -> u32 {
-        let mut crc: u32 = 0xFFFF_FFFF;
-        for &b in bytes {
-            crc ^= b as u32;
-            for _ in 0..8 {
-                let mask = 0u32.wrapping_sub(crc & 1);
-                crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        Err(e) => match e.downcast_ref::<TelemetryQueryError>() {
+            Some(TelemetryQueryError::UnsupportedOrder(_)) => {
+                Err(TelemetryQueryHttpError::InvalidParameter { name: "order" })
             }
-        }
-        !crc
-    }
-
-    fn telemetry_body() -> &'static str {
-        r#"{"source_id":"00000000-0000-0000-0000-000000000001","server_id":"00000000-0000-0000-0000-000000000002","timestamp":"2026-02-18T00:00:00Z","cpu":1.0,"memory":null,"temperature":null,"extras":{}}"#
+            _ => {
+                tracing::error!("Failed to export metrics");
+                Err(TelemetryQueryHttpError::QueryFailed)
+            }
+        },
     }
+}
 
-    #[derive(Clone, Default)]
-    struct CapturedEvents(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+#[derive(serde::Serialize)]
+struct AggregateResponse {
+    bucket: BucketWidth,
+    buckets: Vec<AggregateBucket>,
+}
 
-    #[derive(Clone, Default)]
-    struct CaptureEventLayer {
-        captured: CapturedEvents,
+fn parse_aggregate_query(
+    params: &HashMap<String, String>,
+) -> Result<AggregateQuery, TelemetryQueryHttpError> {
+    let from = parse_timestamp(params, "from")?;
+    let to = parse_timestamp(params, "to")?;
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(TelemetryQueryHttpError::InvalidParameter { name: "to" });
     }
 
-    struct EventFieldVisitor<'a> {
-        fields: &'a mut BTreeMap<String, String>,
-    }
+    let bucket = params
+        .get("bucket")
+        .map(|raw| raw.parse::<BucketWidth>())
+        .transpose()
+        .map_err(|_| TelemetryQueryHttpError::InvalidParameter { name: "bucket" })?
+        .unwrap_or_default();
 
-    impl<'a> Visit for EventFieldVisitor<'a> {
-        fn record_str(&mut self, field: &Field, value: &str) {
-            self.fields
-                .insert(field.name().to_string(), value.to_string());
-        }
+    Ok(AggregateQuery {
+        source_id: params.get("source_id").cloned(),
+        from,
+        to,
+        bucket,
+    })
+}
 
-        fn record_u64(&mut self, field: &Field, value: u64) {
+#[instrument(name = "aggregate telemetry", skip(service), fields(
+    tenant = %tenant,
+    source_id = tracing::field::Empty
+))]
+/// Handles `GET /metrics/aggregate`.
+///
+/// Returns count/min/max/avg/p50/p95/p99 of `cpu`, `memory` and `temperature`
+/// per `source_id` and time bucket.
+///
+/// Supported query parameters:
+/// - `source_id`: only aggregate telemetry for this source
+/// - `from` / `to`: RFC 3339 timestamps bounding the half-open range `[from, to)`
+/// - `bucket`: `1m` (default), `5m` or `1h`
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use rustpulse::adapters::input::http::telemetry_handler;
+/// use rustpulse::core::application::telemetry::{
+///     AggregateBucket, AggregateQuery, TelemetryAggregateCase,
+/// };
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyAggregate;
+/// #[async_trait::async_trait]
+/// impl TelemetryAggregateCase for DummyAggregate {
+///     async fn aggregate(
+///         &self,
+///         _tenant: &TenantId,
+///         _query: AggregateQuery,
+///     ) -> anyhow::Result<Vec<AggregateBucket>> {
+///         Ok(Vec::new())
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryAggregateCase> = Arc::new(DummyAggregate);
+/// let _router = telemetry_handler::aggregate_routes(service);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn aggregate_telemetry_handler(
+    State(service): State<Arc<dyn TelemetryAggregateCase>>,
+    CallerTenant(tenant): CallerTenant,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<axum::response::Response, TelemetryQueryHttpError> {
+    let span = tracing::Span::current();
+
+    if let Some(source_id) = params.get("source_id") {
+        span.record("source_id", source_id.as_str());
+    }
+
+    let query = parse_aggregate_query(&params)?;
+    let bucket = query.bucket;
+
+    match service.aggregate(&tenant, query).await {
+        Ok(buckets) => {
+            tracing::info!(
+                bucket_count = buckets.len(),
+                "aggregated metrics successfully."
+            );
+            Ok(Json(AggregateResponse { bucket, buckets }).into_response())
+        }
+        Err(_) => {
+            tracing::error!("Failed to aggregate metrics");
+            Err(TelemetryQueryHttpError::QueryFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod ingest_crc_tests {
+    use super::crc32_ieee;
+    use crate::core::application::telemetry::{
+        QuotaExceeded, StorageBackpressure, TelemetryIngestCase,
+    };
+    use crate::core::domains::telemetry::Telemetry;
+    use crate::core::domains::tenant::TenantId;
+
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+    use std::fmt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing::Subscriber;
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::registry::LookupSpan;
+
+    struct FakeIngest {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl TelemetryIngestCase for FakeIngest {
+        async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn ingest_batch(
+            &self,
+            _tenant: &TenantId,
+            batch: Vec<Telemetry>,
+        ) -> anyhow::Result<()> {
+            self.calls.fetch_add(batch.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn telemetry_body() -> &'static str {
+        r#"{"source_id":"00000000-0000-0000-0000-000000000001","server_id":"00000000-0000-0000-0000-000000000002","timestamp":"2026-02-18T00:00:00Z","cpu":1.0,"memory":null,"temperature":null,"extras":{}}"#
+    }
+
+    #[derive(Clone, Default)]
+    struct CapturedEvents(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+
+    #[derive(Clone, Default)]
+    struct CaptureEventLayer {
+        captured: CapturedEvents,
+    }
+
+    struct EventFieldVisitor<'a> {
+        fields: &'a mut BTreeMap<String, String>,
+    }
+
+    impl<'a> Visit for EventFieldVisitor<'a> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
             self.fields
                 .insert(field.name().to_string(), value.to_string());
         }
@@ -472,4 +1379,880 @@ mod ingest_crc_tests {
 
         assert!(any_event_has_field(&captured, "crc_check", "fail"));
     }
+
+    async fn post_batch(
+        calls: Arc<AtomicUsize>,
+        content_type: &str,
+        body: impl Into<Body>,
+        crc: Option<String>,
+    ) -> (StatusCode, Value) {
+        let service: Arc<dyn TelemetryIngestCase + Send + Sync> = Arc::new(FakeIngest { calls });
+        let app = super::ingest_routes(service);
+
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/telemetry/batch")
+            .header("content-type", content_type);
+        if let Some(crc) = crc {
+            builder = builder.header("x-crc32", crc);
+        }
+        let resp = app
+            .oneshot(builder.body(body.into()).unwrap())
+            .await
+            .unwrap();
+
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_protobuf_validates_crc_over_raw_bytes() {
+        use crate::adapters::input::http::telemetry_proto::v1;
+        use prost::Message;
+
+        let telemetry: Telemetry = serde_json::from_str(telemetry_body()).unwrap();
+        let bytes = v1::Telemetry::from(&telemetry).encode_to_vec();
+        let post = |body: Vec<u8>, crc: u32| {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let service: Arc<dyn TelemetryIngestCase + Send + Sync> = Arc::new(FakeIngest {
+                calls: calls.clone(),
+            });
+            let req = Request::builder()
+                .method("POST")
+                .uri("/telemetry")
+                .header("content-type", "application/x-protobuf")
+                .header("x-crc32", format!("{crc:08x}"))
+                .body(Body::from(body))
+                .unwrap();
+            async move {
+                let status = super::ingest_routes(service)
+                    .oneshot(req)
+                    .await
+                    .unwrap()
+                    .status();
+                (status, calls.load(Ordering::SeqCst))
+            }
+        };
+
+        assert_eq!(
+            post(bytes.clone(), crc32_ieee(&bytes)).await,
+            (StatusCode::ACCEPTED, 1)
+        );
+        assert_eq!(
+            post(bytes.clone(), crc32_ieee(&bytes) ^ 1).await,
+            (StatusCode::BAD_REQUEST, 0)
+        );
+        let garbage = vec![0xff, 0xff, 0xff];
+        assert_eq!(
+            post(garbage.clone(), crc32_ieee(&garbage)).await,
+            (StatusCode::BAD_REQUEST, 0)
+        );
+    }
+
+    async fn post_encoded(
+        uri: &str,
+        encoding: &str,
+        body: Vec<u8>,
+        crc: Option<u32>,
+    ) -> (StatusCode, Value, usize) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service: Arc<dyn TelemetryIngestCase + Send + Sync> = Arc::new(FakeIngest {
+            calls: calls.clone(),
+        });
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("content-encoding", encoding);
+        if let Some(crc) = crc {
+            builder = builder.header("x-crc32", format!("{crc:08x}"));
+        }
+        let resp = super::ingest_routes(service)
+            .oneshot(builder.body(Body::from(body)).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, value, calls.load(Ordering::SeqCst))
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ingest_decompresses_gzip_and_zstd_with_crc_over_encoded_bytes() {
+        let plain = telemetry_body().as_bytes();
+        let batch = format!("[{},{}]", telemetry_body(), telemetry_body());
+
+        for (encoding, body) in [
+            ("gzip", gzip(plain)),
+            ("zstd", zstd::bulk::compress(plain, 0).unwrap()),
+        ] {
+            let crc = crc32_ieee(&body);
+            let (status, _, calls) = post_encoded("/telemetry", encoding, body, Some(crc)).await;
+            assert_eq!((status, calls), (StatusCode::ACCEPTED, 1), "{encoding}");
+        }
+
+        let body = zstd::bulk::compress(batch.as_bytes(), 3).unwrap();
+        let crc = crc32_ieee(&body);
+        let (status, report, calls) =
+            post_encoded("/telemetry/batch", "zstd", body, Some(crc)).await;
+        assert_eq!((status, calls), (StatusCode::ACCEPTED, 2));
+        assert_eq!(report["accepted"], 2);
+
+        // Concatenated gzip members decode as one body.
+        let items = telemetry_body().as_bytes();
+        let mut body = gzip(b"[");
+        body.extend(gzip(items));
+        body.extend(gzip(b","));
+        body.extend(gzip(items));
+        body.extend(gzip(b"]"));
+        let crc = crc32_ieee(&body);
+        let (status, report, calls) =
+            post_encoded("/telemetry/batch", "gzip", body, Some(crc)).await;
+        assert_eq!((status, calls), (StatusCode::ACCEPTED, 2));
+        assert_eq!(report["accepted"], 2);
+
+        // A CRC over the decompressed bytes does not match what was sent.
+        let (status, error, calls) =
+            post_encoded("/telemetry", "gzip", gzip(plain), Some(crc32_ieee(plain))).await;
+        assert_eq!((status, calls), (StatusCode::BAD_REQUEST, 0));
+        assert_eq!(error["code"], "crc_mismatch");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_rejects_unknown_corrupt_and_oversized_encodings() {
+        use std::io::Write;
+
+        let plain = telemetry_body().as_bytes().to_vec();
+
+        let (status, error, _) = post_encoded("/telemetry", "br", plain.clone(), None).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(error["code"], "unsupported_encoding");
+
+        let (status, error, _) = post_encoded("/telemetry", "gzip", plain, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error["code"], "invalid_encoding");
+
+        // A few KB that inflate past the 1 MiB limit of `POST /telemetry`.
+        let bomb = gzip(&vec![b' '; super::MAX_TELEMETRY_BODY_BYTES + 1]);
+        assert!(bomb.len() < 16 * 1024);
+        let (status, error, calls) = post_encoded("/telemetry", "gzip", bomb, None).await;
+        assert_eq!((status, calls), (StatusCode::PAYLOAD_TOO_LARGE, 0));
+        assert_eq!(error["code"], "body_too_large");
+
+        // A zstd frame whose window is larger than the body limit is refused up front.
+        let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 3).unwrap();
+        encoder.window_log(27).unwrap();
+        encoder.write_all(telemetry_body().as_bytes()).unwrap();
+        let wide = encoder.finish().unwrap();
+        let (status, error, calls) = post_encoded("/telemetry", "zstd", wide, None).await;
+        assert_eq!((status, calls), (StatusCode::BAD_REQUEST, 0));
+        assert_eq!(error["code"], "invalid_encoding");
+    }
+
+    #[test]
+    fn test_zstd_window_log_covers_the_body_limit() {
+        assert_eq!(super::zstd_window_log(super::MAX_TELEMETRY_BODY_BYTES), 20);
+        assert_eq!(super::zstd_window_log(1024 * 1024 + 1), 21);
+        assert_eq!(super::zstd_window_log(0), 10);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_batch_protobuf_reports_invalid_items() {
+        use crate::adapters::input::http::telemetry_proto::v1;
+        use prost::Message;
+
+        let telemetry: Telemetry = serde_json::from_str(telemetry_body()).unwrap();
+        let invalid = v1::Telemetry {
+            source_id: vec![1],
+            ..(&telemetry).into()
+        };
+        let batch = v1::TelemetryBatch {
+            items: vec![(&telemetry).into(), invalid],
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let (status, report) = post_batch(
+            calls.clone(),
+            "application/x-protobuf",
+            batch.encode_to_vec(),
+            None,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(report["items"][1]["code"], "invalid_item");
+        assert_eq!(
+            report["items"][1]["message"],
+            "`source_id` must be a 16-byte UUID"
+        );
+
+        let (status, report) = post_batch(calls, "application/x-protobuf", vec![0xff], None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(report["code"], "invalid_protobuf");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_batch_json_array_reports_per_item_and_writes_valid_items() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let body = format!(
+            "[{},{{\"source_id\":\"nope\"}},{}]",
+            telemetry_body(),
+            telemetry_body()
+        );
+
+        let (status, report) = post_batch(calls.clone(), "application/json", body, None).await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(report.get("accepted").and_then(Value::as_u64), Some(2));
+        assert_eq!(report.get("rejected").and_then(Value::as_u64), Some(1));
+        let items = report.get("items").and_then(Value::as_array).unwrap();
+        assert_eq!(
+            items[1].get("code").and_then(Value::as_str),
+            Some("invalid_item")
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_batch_ndjson_validates_crc_over_whole_body() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let body = format!("{}\n{}\n", telemetry_body(), telemetry_body());
+        let crc = format!("{:08x}", crc32_ieee(body.as_bytes()));
+
+        let (status, report) = post_batch(
+            calls.clone(),
+            "application/x-ndjson",
+            body.clone(),
+            Some(crc),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(report.get("accepted").and_then(Value::as_u64), Some(2));
+
+        let (status, err) = post_batch(
+            calls.clone(),
+            "application/x-ndjson",
+            body,
+            Some("00000000".to_string()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            err.get("code").and_then(Value::as_str),
+            Some("crc_mismatch")
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_batch_with_only_invalid_items_returns_422_without_writing() {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let (status, report) = post_batch(
+            calls.clone(),
+            "application/json",
+            "[{}, 42]".to_string(),
+            None,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(report.get("rejected").and_then(Value::as_u64), Some(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_rejects_server_id_not_bound_to_api_key() {
+        use crate::adapters::input::http::auth::{AuthContext, Scope};
+        use uuid::Uuid;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service: Arc<dyn TelemetryIngestCase + Send + Sync> = Arc::new(FakeIngest {
+            calls: calls.clone(),
+        });
+        // telemetry_body() reports server ...0002; the key is bound to ...0003.
+        let app = super::ingest_routes(service).layer(axum::Extension(AuthContext {
+            subject: None,
+            scopes: vec![Scope::TelemetryWrite],
+            server_id: Some(Uuid::from_u128(3)),
+            tenant: TenantId::default(),
+        }));
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/telemetry")
+            .header("content-type", "application/json")
+            .body(Body::from(telemetry_body()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/telemetry/batch")
+            .header("content-type", "application/x-ndjson")
+            .body(Body::from(format!("{}\n", telemetry_body())))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(report["items"][0]["code"], "server_id_mismatch");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    struct ExhaustedQuotaIngest;
+
+    #[async_trait]
+    impl TelemetryIngestCase for ExhaustedQuotaIngest {
+        async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+            Err(QuotaExceeded {
+                limit: 10,
+                retry_after_secs: 42,
+            }
+            .into())
+        }
+
+        async fn ingest_batch(
+            &self,
+            _tenant: &TenantId,
+            _batch: Vec<Telemetry>,
+        ) -> anyhow::Result<()> {
+            Err(QuotaExceeded {
+                limit: 10,
+                retry_after_secs: 42,
+            }
+            .into())
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_over_quota_returns_429_with_retry_after() {
+        let app = super::ingest_routes(Arc::new(ExhaustedQuotaIngest));
+
+        for (uri, body) in [
+            ("/telemetry", telemetry_body().to_string()),
+            ("/telemetry/batch", format!("[{}]", telemetry_body())),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+
+            assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert_eq!(resp.headers()[axum::http::header::RETRY_AFTER], "42");
+            let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(body["code"], "quota_exceeded");
+        }
+    }
+
+    struct FullBufferIngest;
+
+    #[async_trait]
+    impl TelemetryIngestCase for FullBufferIngest {
+        async fn ingest(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
+            Err(StorageBackpressure {
+                retry_after_secs: 1,
+            }
+            .into())
+        }
+
+        async fn ingest_batch(
+            &self,
+            _tenant: &TenantId,
+            _batch: Vec<Telemetry>,
+        ) -> anyhow::Result<()> {
+            Err(StorageBackpressure {
+                retry_after_secs: 1,
+            }
+            .into())
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_with_full_buffer_returns_503_with_retry_after() {
+        let app = super::ingest_routes(Arc::new(FullBufferIngest));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/telemetry")
+            .header("content-type", "application/json")
+            .body(Body::from(telemetry_body().to_string()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[axum::http::header::RETRY_AFTER], "1");
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "backpressure");
+    }
+}
+
+#[cfg(test)]
+mod metrics_query_tests {
+    use crate::core::application::telemetry::{
+        SortOrder, TelemetryCursor, TelemetryPage, TelemetryQuery, TelemetryQueryCase,
+        TelemetryQueryError, TelemetryStream,
+    };
+    use crate::core::domains::telemetry::Telemetry;
+    use crate::core::domains::tenant::TenantId;
+
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::{DateTime, Utc};
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingQuery {
+        seen: Mutex<Vec<TelemetryQuery>>,
+        tenants: Mutex<Vec<TenantId>>,
+        items: Vec<Telemetry>,
+        next_cursor: Option<TelemetryCursor>,
+        /// Streams like JSONL storage, which only supports ascending order.
+        ascending_only: bool,
+    }
+
+    #[async_trait]
+    impl TelemetryQueryCase for RecordingQuery {
+        async fn fetch_all(
+            &self,
+            _tenant: &TenantId,
+            _node_id: Option<String>,
+        ) -> anyhow::Result<Vec<Telemetry>> {
+            Ok(Vec::new())
+        }
+
+        async fn fetch_page(
+            &self,
+            tenant: &TenantId,
+            query: TelemetryQuery,
+        ) -> anyhow::Result<TelemetryPage> {
+            self.tenants
+                .lock()
+                .expect("lock poisoned")
+                .push(tenant.clone());
+            self.seen.lock().expect("lock poisoned").push(query);
+            Ok(TelemetryPage {
+                items: self.items.clone(),
+                next_cursor: self.next_cursor,
+            })
+        }
+
+        async fn fetch_stream(
+            &self,
+            tenant: &TenantId,
+            query: TelemetryQuery,
+        ) -> anyhow::Result<TelemetryStream> {
+            if self.ascending_only && query.order == SortOrder::Desc {
+                return Err(TelemetryQueryError::UnsupportedOrder(query.order).into());
+            }
+            let page = self.fetch_page(tenant, query).await?;
+            Ok(Box::pin(futures_util::stream::iter(
+                page.items.into_iter().map(Ok),
+            )))
+        }
+    }
+
+    async fn get(service: Arc<RecordingQuery>, uri: &str) -> (StatusCode, Value) {
+        let app = super::routes(service);
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    async fn get_raw(
+        service: Arc<RecordingQuery>,
+        uri: &str,
+        accept: Option<&str>,
+    ) -> (StatusCode, axum::http::HeaderMap, String) {
+        let app = super::routes(service);
+        let mut req = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            req = req.header("accept", accept);
+        }
+        let resp = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+        let (parts, body) = resp.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (
+            parts.status,
+            parts.headers,
+            String::from_utf8(bytes.to_vec()).unwrap(),
+        )
+    }
+
+    fn telemetry(cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            timestamp: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+            cpu: Some(cpu),
+            memory: None,
+            temperature: None,
+            extras: serde_json::json!({ "site": "lab, \"east\"", "rpm": 1200 }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_negotiates_ndjson_and_csv_with_flattened_extras() {
+        let cursor = TelemetryCursor {
+            timestamp: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+            source_id: Uuid::nil(),
+            id: 1,
+        };
+        let service = Arc::new(RecordingQuery {
+            items: vec![telemetry(1.5), telemetry(2.0)],
+            next_cursor: Some(cursor),
+            ..Default::default()
+        });
+
+        let (status, headers, body) = get_raw(
+            service.clone(),
+            "/metrics",
+            Some("text/csv;q=0.5, application/x-ndjson"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "application/x-ndjson");
+        assert_eq!(headers["x-next-cursor"], cursor.encode().as_str());
+        let lines: Vec<Value> = body
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["cpu"], 2.0);
+
+        let (status, headers, body) = get_raw(
+            service,
+            "/metrics?format=csv&extras=site,missing",
+            Some("application/json"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "text/csv; charset=utf-8");
+        let rows: Vec<&str> = body.split("\r\n").collect();
+        assert_eq!(
+            rows[0],
+            "source_id,server_id,timestamp,cpu,memory,temperature,extras_site,extras_missing,extras"
+        );
+        assert_eq!(
+            rows[1],
+            format!(
+                "{nil},{nil},2023-11-14T22:13:20Z,1.5,,,\"lab, \"\"east\"\"\",,\"{{\"\"rpm\"\":1200}}\"",
+                nil = Uuid::nil()
+            )
+        );
+        assert_eq!(rows.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_export_streams_without_default_limit() {
+        let service = Arc::new(RecordingQuery {
+            items: vec![telemetry(1.0), telemetry(2.0), telemetry(3.0)],
+            ..Default::default()
+        });
+
+        let (status, headers, body) = get_raw(
+            service.clone(),
+            "/metrics/export?format=ndjson&from=2023-11-14T00:00:00Z",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "application/x-ndjson");
+        assert_eq!(body.lines().count(), 3);
+
+        let (status, _, body) = get_raw(service.clone(), "/metrics/export?limit=5000", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["items"].as_array().map(Vec::len), Some(3));
+
+        let seen = service.seen.lock().unwrap();
+        assert_eq!(seen[0].limit, None);
+        assert!(seen[0].from.is_some());
+        assert_eq!(seen[1].limit, Some(5000));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_export_rejects_order_the_storage_cannot_stream() {
+        let service = Arc::new(RecordingQuery {
+            items: vec![telemetry(1.0)],
+            ascending_only: true,
+            ..Default::default()
+        });
+
+        let (status, body) = get(service.clone(), "/metrics/export?order=desc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_parameter");
+
+        let (status, _) = get(service, "/metrics/export?order=asc").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_responds_in_protobuf() {
+        use crate::adapters::input::http::telemetry_proto::v1;
+        use prost::Message;
+
+        let cursor = TelemetryCursor {
+            timestamp: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+            source_id: Uuid::nil(),
+            id: 1,
+        };
+        let service = Arc::new(RecordingQuery {
+            items: vec![telemetry(1.5), telemetry(2.0)],
+            next_cursor: Some(cursor),
+            ..Default::default()
+        });
+
+        let app = super::routes(service);
+        let req = Request::builder()
+            .uri("/metrics")
+            .header("accept", "application/x-protobuf")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(
+            resp.headers()["content-type"]
+                .to_str()
+                .unwrap()
+                .starts_with("application/x-protobuf")
+        );
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page = v1::TelemetryPage::decode(bytes).unwrap();
+
+        assert_eq!(page.next_cursor, Some(cursor.encode()));
+        let items: Vec<Telemetry> = page
+            .items
+            .into_iter()
+            .map(|message| Telemetry::try_from(message).unwrap())
+            .collect();
+        assert_eq!(
+            serde_json::to_value(&items).unwrap(),
+            serde_json::to_value(vec![telemetry(1.5), telemetry(2.0)]).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_rejects_unknown_format_and_unacceptable_accept() {
+        let service = Arc::new(RecordingQuery::default());
+
+        let (status, headers, body) = get_raw(service.clone(), "/metrics", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(body, r#"{"items":[],"next_cursor":null}"#);
+
+        let (status, _, body) = get_raw(service.clone(), "/metrics?format=xml", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("invalid_parameter"));
+
+        let (status, _, body) = get_raw(service, "/metrics", Some("application/xml")).await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+        assert!(body.contains("not_acceptable"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_parses_range_limit_order_and_cursor() {
+        let cursor = TelemetryCursor {
+            timestamp: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+            source_id: Uuid::nil(),
+            id: 1,
+        };
+        let service = Arc::new(RecordingQuery {
+            next_cursor: Some(cursor),
+            ..Default::default()
+        });
+
+        let uri = format!(
+            "/metrics?from=2026-02-18T00:00:00Z&to=2026-02-19T00:00:00Z&limit=10&order=desc&cursor={}",
+            cursor.encode()
+        );
+        let (status, body) = get(service.clone(), &uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body.get("next_cursor").and_then(Value::as_str),
+            Some(cursor.encode().as_str())
+        );
+
+        let seen = service.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].limit, Some(10));
+        assert_eq!(seen[0].order, SortOrder::Desc);
+        assert_eq!(seen[0].cursor, Some(cursor));
+        assert!(seen[0].from.is_some() && seen[0].to.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_applies_default_limit_and_rejects_bad_params() {
+        let service = Arc::new(RecordingQuery::default());
+
+        let (status, body) = get(service.clone(), "/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.get("next_cursor").is_some_and(Value::is_null));
+        assert_eq!(
+            service.seen.lock().unwrap()[0].limit,
+            Some(TelemetryQuery::DEFAULT_LIMIT)
+        );
+
+        let (status, body) = get(service.clone(), "/metrics?limit=0").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body.get("code").and_then(Value::as_str),
+            Some("invalid_parameter")
+        );
+
+        let (status, body) = get(service, "/metrics?cursor=garbage").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body.get("code").and_then(Value::as_str),
+            Some("invalid_cursor")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_queries_the_tenant_of_the_caller() {
+        use crate::adapters::input::http::auth::{AuthContext, Scope};
+
+        let service = Arc::new(RecordingQuery::default());
+        let acme = TenantId::parse("acme").unwrap();
+
+        let (status, _) = get(service.clone(), "/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let app = super::routes(service.clone()).layer(axum::Extension(AuthContext {
+            subject: Some("reader".to_string()),
+            scopes: vec![Scope::TelemetryRead],
+            server_id: None,
+            tenant: acme.clone(),
+        }));
+        let req = Request::builder()
+            .uri("/metrics")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+        assert_eq!(
+            *service.tenants.lock().unwrap(),
+            vec![TenantId::default(), acme]
+        );
+    }
+}
+
+#[cfg(test)]
+mod metrics_aggregate_tests {
+    use crate::core::application::telemetry::{
+        AggregateBucket, AggregateQuery, BucketWidth, TelemetryAggregateCase,
+    };
+    use crate::core::domains::tenant::TenantId;
+
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct RecordingAggregate {
+        seen: Mutex<Vec<AggregateQuery>>,
+    }
+
+    #[async_trait]
+    impl TelemetryAggregateCase for RecordingAggregate {
+        async fn aggregate(
+            &self,
+            _tenant: &TenantId,
+            query: AggregateQuery,
+        ) -> anyhow::Result<Vec<AggregateBucket>> {
+            self.seen.lock().expect("lock poisoned").push(query);
+            Ok(Vec::new())
+        }
+    }
+
+    async fn get(service: Arc<RecordingAggregate>, uri: &str) -> (StatusCode, Value) {
+        let app = super::aggregate_routes(service);
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_defaults_to_one_minute_buckets() {
+        let service = Arc::new(RecordingAggregate::default());
+
+        let (status, body) = get(service.clone(), "/metrics/aggregate").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["bucket"], "1m");
+        assert_eq!(body["buckets"], Value::Array(Vec::new()));
+        let seen = service.seen.lock().unwrap();
+        assert_eq!(seen[0].bucket, BucketWidth::OneMinute);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_parses_bucket_and_range() {
+        let service = Arc::new(RecordingAggregate::default());
+
+        let (status, body) = get(
+            service.clone(),
+            "/metrics/aggregate?bucket=5m&from=2026-02-18T00:00:00Z&to=2026-02-19T00:00:00Z",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["bucket"], "5m");
+        let seen = service.seen.lock().unwrap();
+        assert_eq!(seen[0].bucket, BucketWidth::FiveMinutes);
+        assert!(seen[0].from.is_some() && seen[0].to.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_rejects_unknown_bucket() {
+        let service = Arc::new(RecordingAggregate::default());
+
+        let (status, body) = get(service.clone(), "/metrics/aggregate?bucket=10m").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_parameter");
+        assert!(service.seen.lock().unwrap().is_empty());
+    }
 }
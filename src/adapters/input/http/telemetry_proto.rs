@@ -0,0 +1,287 @@
+//! Protobuf wire format of telemetry (`application/x-protobuf`).
+//!
+//! The messages mirror `proto/rustpulse/telemetry/v1/telemetry.proto`. They are written with
+//! `prost` derives rather than generated, so building needs no `protoc`; any change to the
+//! schema must be made in both places.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use prost::Message;
+//! use rustpulse::adapters::input::http::telemetry_proto::v1;
+//! use rustpulse::core::domains::telemetry::Telemetry;
+//! use chrono::Utc;
+//! use uuid::Uuid;
+//!
+//! let telemetry = Telemetry {
+//!     source_id: Uuid::new_v4(),
+//!     server_id: Uuid::new_v4(),
+//!     timestamp: Utc::now(),
+//!     cpu: Some(0.5),
+//!     memory: None,
+//!     temperature: None,
+//!     extras: serde_json::json!({"region":"eu"}),
+//! };
+//!
+//! let bytes = v1::Telemetry::from(&telemetry).encode_to_vec();
+//! let decoded = Telemetry::try_from(v1::Telemetry::decode(bytes.as_slice()).unwrap()).unwrap();
+//! assert_eq!(decoded.timestamp, telemetry.timestamp);
+//! ```
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::domains::telemetry::Telemetry;
+
+/// Media type of protobuf bodies.
+pub const PROTOBUF_MEDIA_TYPE: &str = "application/x-protobuf";
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+/// A decoded message that does not describe valid telemetry.
+pub enum ProtoTelemetryError {
+    /// `source_id` or `server_id` is not 16 bytes long.
+    #[error("`{field}` must be a 16-byte UUID")]
+    InvalidUuid {
+        /// Name of the offending field.
+        field: &'static str,
+    },
+
+    /// The timestamp is out of range or `timestamp_nanos` exceeds one second.
+    #[error("timestamp is out of range")]
+    InvalidTimestamp,
+
+    /// `extras_json` is not valid JSON.
+    #[error("`extras_json` is not valid JSON")]
+    InvalidExtras,
+
+    /// A reading is NaN or infinite, which JSON bodies cannot express either.
+    #[error("`{field}` must be a finite number")]
+    InvalidValue {
+        /// Name of the offending field.
+        field: &'static str,
+    },
+}
+
+/// Messages of the `rustpulse.telemetry.v1` package.
+pub mod v1 {
+    /// One datapoint; body of `POST /telemetry`.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Telemetry {
+        /// 16-byte big-endian UUID of the source.
+        #[prost(bytes = "vec", tag = "1")]
+        pub source_id: Vec<u8>,
+        /// 16-byte big-endian UUID of the reporting server.
+        #[prost(bytes = "vec", tag = "2")]
+        pub server_id: Vec<u8>,
+        /// Seconds since the Unix epoch (UTC).
+        #[prost(int64, tag = "3")]
+        pub timestamp_seconds: i64,
+        /// Nanoseconds within the second.
+        #[prost(uint32, tag = "4")]
+        pub timestamp_nanos: u32,
+        /// CPU usage.
+        #[prost(double, optional, tag = "5")]
+        pub cpu: Option<f64>,
+        /// Memory usage.
+        #[prost(double, optional, tag = "6")]
+        pub memory: Option<f64>,
+        /// Temperature reading.
+        #[prost(float, optional, tag = "7")]
+        pub temperature: Option<f32>,
+        /// Domain-specific attributes as JSON text; empty means `{}`.
+        #[prost(string, tag = "8")]
+        pub extras_json: String,
+    }
+
+    /// Body of `POST /telemetry/batch`.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TelemetryBatch {
+        /// Datapoints of the batch, in submission order.
+        #[prost(message, repeated, tag = "1")]
+        pub items: Vec<Telemetry>,
+    }
+
+    /// Page of `GET /metrics` results.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TelemetryPage {
+        /// Datapoints of the page.
+        #[prost(message, repeated, tag = "1")]
+        pub items: Vec<Telemetry>,
+        /// Cursor of the next page; absent on the last page.
+        #[prost(string, optional, tag = "2")]
+        pub next_cursor: Option<String>,
+    }
+}
+
+impl From<&Telemetry> for v1::Telemetry {
+    fn from(telemetry: &Telemetry) -> Self {
+        Self {
+            source_id: telemetry.source_id.as_bytes().to_vec(),
+            server_id: telemetry.server_id.as_bytes().to_vec(),
+            timestamp_seconds: telemetry.timestamp.timestamp(),
+            timestamp_nanos: telemetry.timestamp.timestamp_subsec_nanos(),
+            cpu: telemetry.cpu,
+            memory: telemetry.memory,
+            temperature: telemetry.temperature,
+            extras_json: telemetry.extras.to_string(),
+        }
+    }
+}
+
+impl TryFrom<v1::Telemetry> for Telemetry {
+    type Error = ProtoTelemetryError;
+
+    fn try_from(message: v1::Telemetry) -> Result<Self, Self::Error> {
+        let uuid = |bytes: &[u8], field| {
+            Uuid::from_slice(bytes).map_err(|_| ProtoTelemetryError::InvalidUuid { field })
+        };
+        let finite = |value: Option<f64>, field| match value {
+            Some(v) if !v.is_finite() => Err(ProtoTelemetryError::InvalidValue { field }),
+            _ => Ok(value),
+        };
+        finite(message.temperature.map(f64::from), "temperature")?;
+        let timestamp: DateTime<Utc> =
+            DateTime::from_timestamp(message.timestamp_seconds, message.timestamp_nanos)
+                .ok_or(ProtoTelemetryError::InvalidTimestamp)?;
+        let extras = if message.extras_json.is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&message.extras_json)
+                .map_err(|_| ProtoTelemetryError::InvalidExtras)?
+        };
+
+        Ok(Self {
+            source_id: uuid(&message.source_id, "source_id")?,
+            server_id: uuid(&message.server_id, "server_id")?,
+            timestamp,
+            cpu: finite(message.cpu, "cpu")?,
+            memory: finite(message.memory, "memory")?,
+            temperature: message.temperature,
+            extras,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use serde_json::Value;
+
+    const FIXTURE: &str = r#"{
+        "source_id": "6f1c2a44-0d7e-4c1b-9a51-3e2f8b0c9d12",
+        "server_id": "00000000-0000-0000-0000-000000000001",
+        "timestamp": "2026-02-18T10:15:30.123456789Z",
+        "cpu": 42.5,
+        "memory": null,
+        "temperature": 21.25,
+        "extras": {"rpm": 1200, "site": "lab", "tags": ["a", "b"], "nested": {"ok": true}}
+    }"#;
+
+    #[test]
+    fn test_protobuf_round_trip_matches_serde_json_representation() {
+        let from_json: Telemetry = serde_json::from_str(FIXTURE).unwrap();
+
+        let bytes = v1::Telemetry::from(&from_json).encode_to_vec();
+        let from_proto =
+            Telemetry::try_from(v1::Telemetry::decode(bytes.as_slice()).unwrap()).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&from_proto).unwrap(),
+            serde_json::from_str::<Value>(FIXTURE).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&from_proto).unwrap(),
+            serde_json::to_value(&from_json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_batch_and_page_round_trip_and_defaults() {
+        let telemetry: Telemetry = serde_json::from_str(FIXTURE).unwrap();
+        let batch = v1::TelemetryBatch {
+            items: vec![(&telemetry).into(), (&telemetry).into()],
+        };
+        let decoded = v1::TelemetryBatch::decode(batch.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, batch);
+
+        // Pages are streamed as one message per item followed by the cursor; they concatenate.
+        let mut bytes = v1::TelemetryPage {
+            items: vec![(&telemetry).into()],
+            next_cursor: None,
+        }
+        .encode_to_vec();
+        v1::TelemetryPage {
+            items: Vec::new(),
+            next_cursor: Some("abc".to_string()),
+        }
+        .encode(&mut bytes)
+        .unwrap();
+        let page = v1::TelemetryPage::decode(bytes.as_slice()).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.next_cursor.as_deref(), Some("abc"));
+
+        let minimal = v1::Telemetry {
+            source_id: Uuid::nil().as_bytes().to_vec(),
+            server_id: Uuid::nil().as_bytes().to_vec(),
+            ..Default::default()
+        };
+        let telemetry = Telemetry::try_from(minimal.clone()).unwrap();
+        assert_eq!(telemetry.extras, serde_json::json!({}));
+        assert_eq!(telemetry.cpu, None);
+
+        let short_id = v1::Telemetry {
+            source_id: vec![1, 2, 3],
+            ..minimal
+        };
+        assert_eq!(
+            Telemetry::try_from(short_id).unwrap_err(),
+            ProtoTelemetryError::InvalidUuid { field: "source_id" }
+        );
+    }
+
+    #[test]
+    fn test_non_finite_readings_are_rejected() {
+        let minimal = v1::Telemetry {
+            source_id: Uuid::nil().as_bytes().to_vec(),
+            server_id: Uuid::nil().as_bytes().to_vec(),
+            ..Default::default()
+        };
+        let cases = [
+            (
+                v1::Telemetry {
+                    cpu: Some(f64::NAN),
+                    ..minimal.clone()
+                },
+                "cpu",
+            ),
+            (
+                v1::Telemetry {
+                    memory: Some(f64::INFINITY),
+                    ..minimal.clone()
+                },
+                "memory",
+            ),
+            (
+                v1::Telemetry {
+                    temperature: Some(f32::NEG_INFINITY),
+                    ..minimal.clone()
+                },
+                "temperature",
+            ),
+        ];
+        for (message, field) in cases {
+            assert_eq!(
+                Telemetry::try_from(message).unwrap_err(),
+                ProtoTelemetryError::InvalidValue { field }
+            );
+        }
+
+        let finite = v1::Telemetry {
+            cpu: Some(f64::MAX),
+            temperature: Some(-40.0),
+            ..minimal
+        };
+        assert!(Telemetry::try_from(finite).is_ok());
+    }
+}
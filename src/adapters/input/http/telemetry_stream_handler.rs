@@ -0,0 +1,328 @@
+//! HTTP handlers for the live telemetry tail (Server-Sent Events and WebSocket).
+
+use crate::adapters::input::http::auth::CallerTenant;
+use crate::adapters::input::http::telemetry_handler::TelemetryQueryHttpError;
+use crate::core::application::telemetry::{
+    LiveTailEvent, LiveTailFilter, LiveTailSubscription, TelemetryLiveTailCase,
+};
+use axum::Router;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use futures_util::Stream;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::request_tracing;
+
+#[instrument(level = "info", skip(service))]
+/// Router for the live tail via `GET /metrics/stream` (SSE) and `GET /metrics/ws` (WebSocket).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::telemetry_stream_handler;
+/// use rustpulse::core::application::telemetry::{
+///     LiveTailFilter, LiveTailSubscription, TelemetryLiveTailCase,
+/// };
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyLiveTail;
+///
+/// impl TelemetryLiveTailCase for DummyLiveTail {
+///     fn subscribe(&self, _tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+///         let (_tx, rx) = tokio::sync::broadcast::channel(1);
+///         LiveTailSubscription::new(rx, filter)
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryLiveTailCase> = Arc::new(DummyLiveTail);
+/// let _router = telemetry_stream_handler::routes(service);
+/// ```
+pub fn routes(service: Arc<dyn TelemetryLiveTailCase>) -> Router {
+    Router::new()
+        .route("/metrics/stream", get(stream_telemetry_handler))
+        .route("/metrics/ws", get(ws_telemetry_handler))
+        .with_state(service)
+        .route_layer(middleware::from_fn(request_tracing::trace_middleware))
+}
+
+fn parse_uuid(
+    params: &HashMap<String, String>,
+    name: &'static str,
+) -> Result<Option<Uuid>, TelemetryQueryHttpError> {
+    params
+        .get(name)
+        .map(|raw| {
+            Uuid::parse_str(raw).map_err(|_| TelemetryQueryHttpError::InvalidParameter { name })
+        })
+        .transpose()
+}
+
+fn parse_filter(
+    params: &HashMap<String, String>,
+) -> Result<LiveTailFilter, TelemetryQueryHttpError> {
+    Ok(LiveTailFilter {
+        source_id: parse_uuid(params, "source_id")?,
+        server_id: parse_uuid(params, "server_id")?,
+    })
+}
+
+fn sse_event(event: LiveTailEvent) -> Event {
+    match event {
+        LiveTailEvent::Telemetry(telemetry) => Event::default()
+            .event("telemetry")
+            .json_data(&telemetry)
+            .unwrap_or_else(|_| Event::default().event("error")),
+        LiveTailEvent::Lagged { skipped } => Event::default()
+            .event("lagged")
+            .data(serde_json::json!({ "skipped": skipped }).to_string()),
+    }
+}
+
+fn ws_message(event: LiveTailEvent) -> Message {
+    let body = match event {
+        LiveTailEvent::Telemetry(telemetry) => {
+            serde_json::json!({ "type": "telemetry", "data": telemetry })
+        }
+        LiveTailEvent::Lagged { skipped } => {
+            serde_json::json!({ "type": "lagged", "skipped": skipped })
+        }
+    };
+    Message::Text(body.to_string().into())
+}
+
+fn event_stream(
+    subscription: LiveTailSubscription,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures_util::stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.recv().await?;
+        Some((Ok(sse_event(event)), subscription))
+    })
+}
+
+#[instrument(name = "stream telemetry", skip(service))]
+/// Handles `GET /metrics/stream`.
+///
+/// Pushes every record accepted for the caller's tenant as an SSE `telemetry` event. A client that falls
+/// behind receives a `lagged` event carrying `{"skipped": n}`.
+///
+/// Supported query parameters:
+/// - `source_id`: only stream telemetry from this source
+/// - `server_id`: only stream telemetry reported by this server
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::telemetry_stream_handler;
+/// use rustpulse::core::application::telemetry::{
+///     LiveTailFilter, LiveTailSubscription, TelemetryLiveTailCase,
+/// };
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyLiveTail;
+/// impl TelemetryLiveTailCase for DummyLiveTail {
+///     fn subscribe(&self, _tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+///         let (_tx, rx) = tokio::sync::broadcast::channel(1);
+///         LiveTailSubscription::new(rx, filter)
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryLiveTailCase> = Arc::new(DummyLiveTail);
+/// let _router = telemetry_stream_handler::routes(service);
+/// ```
+pub async fn stream_telemetry_handler(
+    State(service): State<Arc<dyn TelemetryLiveTailCase>>,
+    CallerTenant(tenant): CallerTenant,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<axum::response::Response, TelemetryQueryHttpError> {
+    let filter = parse_filter(&params)?;
+    let subscription = service.subscribe(&tenant, filter);
+    tracing::info!("live tail subscriber connected (sse)");
+
+    Ok(Sse::new(event_stream(subscription))
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+#[instrument(name = "stream telemetry ws", skip(service, upgrade))]
+/// Handles `GET /metrics/ws`.
+///
+/// Same filters and semantics as [`stream_telemetry_handler`]; each message is a
+/// JSON text frame, either `{"type":"telemetry","data":{...}}` or
+/// `{"type":"lagged","skipped":n}`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rustpulse::adapters::input::http::telemetry_stream_handler;
+/// use rustpulse::core::application::telemetry::{
+///     LiveTailFilter, LiveTailSubscription, TelemetryLiveTailCase,
+/// };
+/// use rustpulse::core::domains::tenant::TenantId;
+/// use std::sync::Arc;
+///
+/// struct DummyLiveTail;
+/// impl TelemetryLiveTailCase for DummyLiveTail {
+///     fn subscribe(&self, _tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+///         let (_tx, rx) = tokio::sync::broadcast::channel(1);
+///         LiveTailSubscription::new(rx, filter)
+///     }
+/// }
+///
+/// let service: Arc<dyn TelemetryLiveTailCase> = Arc::new(DummyLiveTail);
+/// let _router = telemetry_stream_handler::routes(service);
+/// ```
+pub async fn ws_telemetry_handler(
+    State(service): State<Arc<dyn TelemetryLiveTailCase>>,
+    CallerTenant(tenant): CallerTenant,
+    Query(params): Query<HashMap<String, String>>,
+    upgrade: WebSocketUpgrade,
+) -> Result<axum::response::Response, TelemetryQueryHttpError> {
+    let filter = parse_filter(&params)?;
+    let subscription = service.subscribe(&tenant, filter);
+
+    Ok(upgrade.on_upgrade(move |socket| forward_to_socket(socket, subscription)))
+}
+
+async fn forward_to_socket(mut socket: WebSocket, mut subscription: LiveTailSubscription) {
+    tracing::info!("live tail subscriber connected (ws)");
+
+    loop {
+        tokio::select! {
+            event = subscription.recv() => {
+                let Some(event) = event else { break };
+                if socket.send(ws_message(event)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => {
+                // Inbound frames are ignored; stop when the client goes away.
+                match incoming {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    tracing::info!("live tail subscriber disconnected (ws)");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::application::telemetry::{
+        LiveTailFilter, LiveTailSubscription, TelemetryLiveTailCase,
+    };
+    use crate::core::domains::telemetry::Telemetry;
+    use crate::core::domains::tenant::TenantId;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use chrono::Utc;
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    struct FakeLiveTail {
+        tx: broadcast::Sender<Telemetry>,
+    }
+
+    impl TelemetryLiveTailCase for FakeLiveTail {
+        fn subscribe(&self, _tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+            LiveTailSubscription::new(self.tx.subscribe(), filter)
+        }
+    }
+
+    fn telemetry(source_id: Uuid) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: Utc::now(),
+            cpu: Some(1.0),
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_pushes_matching_records_only() {
+        let (tx, _) = broadcast::channel(8);
+        let service = Arc::new(FakeLiveTail { tx: tx.clone() });
+        let wanted = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let app = super::routes(service);
+        let req = Request::builder()
+            .uri(format!("/metrics/stream?source_id={wanted}"))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+
+        tx.send(telemetry(other)).unwrap();
+        tx.send(telemetry(wanted)).unwrap();
+
+        let mut body = resp.into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+
+        assert!(frame.starts_with("event: telemetry\n"), "{frame}");
+        assert!(frame.contains(&wanted.to_string()));
+        assert!(!frame.contains(&other.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_reports_lag() {
+        let (tx, _) = broadcast::channel(1);
+        let service = Arc::new(FakeLiveTail { tx: tx.clone() });
+
+        let app = super::routes(service);
+        let req = Request::builder()
+            .uri("/metrics/stream")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        for _ in 0..3 {
+            tx.send(telemetry(Uuid::nil())).unwrap();
+        }
+
+        let mut body = resp.into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+
+        assert_eq!(frame, "event: lagged\ndata: {\"skipped\":2}\n\n");
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_invalid_filter() {
+        let (tx, _) = broadcast::channel(1);
+        let app = super::routes(Arc::new(FakeLiveTail { tx }));
+        let req = Request::builder()
+            .uri("/metrics/stream?server_id=nope")
+            .body(Body::empty())
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,6 +1,14 @@
 //! Adapter implementations for outbound dependencies (storage, databases, etc.).
 
 pub mod fault_injecting_repo;
+pub mod in_memory_api_key_repo;
+pub mod in_memory_telemetry_repo;
 pub mod jsonl_repo;
+#[cfg(feature = "parquet")]
+pub mod parquet_archive;
+pub mod postgres_api_key_repo;
 pub mod postgres_db;
+pub mod postgres_partitions;
 pub mod postgres_telemetry_repo;
+pub mod sqlite_telemetry_repo;
+pub mod wal_repo;
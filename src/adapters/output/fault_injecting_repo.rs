@@ -1,7 +1,11 @@
 //! Telemetry repository wrapper that can inject faults for testing and demos.
 
-use crate::core::application::telemetry::TelemetryRepository;
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, TelemetryPage, TelemetryQuery, TelemetryRepository,
+    TelemetryStream,
+};
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
 use std::sync::Mutex;
 
 #[derive(Debug, Clone)]
@@ -137,16 +141,19 @@ enum InjectionDecision {
     Pass,
 }
 
-#[async_trait::async_trait]
-impl<R> TelemetryRepository for FaultInjectingTelemetryRepo<R>
-where
-    R: TelemetryRepository + Send + Sync,
-{
-    async fn save(&self, mut telemetry: Telemetry) -> anyhow::Result<()> {
-        if !self.config.enabled {
-            return self.inner.save(telemetry).await;
+impl InjectionDecision {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Corrupt => "corrupt",
+            Self::Pass => "pass",
         }
+    }
+}
 
+impl<R> FaultInjectingTelemetryRepo<R> {
+    /// Rolls for one write; returns `None` when it should be dropped.
+    fn inject(&self, mut telemetry: Telemetry) -> anyhow::Result<Option<Telemetry>> {
         let roll = self.next_f64()?;
         let decision = if roll < self.config.drop_rate {
             InjectionDecision::Drop
@@ -155,6 +162,11 @@ where
         } else {
             InjectionDecision::Pass
         };
+        metrics::counter!(
+            "rustpulse_fault_injection_decisions_total",
+            "decision" => decision.as_str(),
+        )
+        .increment(1);
 
         match decision {
             InjectionDecision::Drop => {
@@ -165,7 +177,7 @@ where
                     corrupt_rate = self.config.corrupt_rate,
                     "fault injection decision"
                 );
-                Ok(())
+                Ok(None)
             }
             InjectionDecision::Corrupt => {
                 tracing::event!(
@@ -181,20 +193,84 @@ where
                     "original": telemetry.extras
                 });
 
-                self.inner.save(telemetry).await
+                Ok(Some(telemetry))
+            }
+            InjectionDecision::Pass => Ok(Some(telemetry)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R> TelemetryRepository for FaultInjectingTelemetryRepo<R>
+where
+    R: TelemetryRepository + Send + Sync,
+{
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            return self.inner.save(tenant, telemetry).await;
+        }
+
+        match self.inject(telemetry)? {
+            Some(telemetry) => self.inner.save(tenant, telemetry).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            return self.inner.save_batch(tenant, batch).await;
+        }
+
+        let mut survivors = Vec::with_capacity(batch.len());
+        for telemetry in batch {
+            if let Some(telemetry) = self.inject(telemetry)? {
+                survivors.push(telemetry);
             }
-            InjectionDecision::Pass => self.inner.save(telemetry).await,
         }
+        self.inner.save_batch(tenant, survivors).await
     }
 
-    async fn query_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
-        self.inner.query_all(node_id).await
+    fn atomic_batches(&self) -> bool {
+        self.inner.atomic_batches()
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        self.inner.query_all(tenant, node_id).await
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        self.inner.query_page(tenant, query).await
+    }
+
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        self.inner.query_stream(tenant, query).await
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        self.inner.aggregate(tenant, query).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::output::in_memory_telemetry_repo::InMemoryTelemetryRepo;
     use chrono::{TimeZone as _, Utc};
     use serde_json::json;
     use std::collections::BTreeMap;
@@ -207,40 +283,8 @@ mod tests {
     use tracing_subscriber::{Layer, registry::LookupSpan};
     use uuid::Uuid;
 
-    #[derive(Default)]
-    struct CapturingRepo {
-        saved: StdMutex<Vec<Telemetry>>,
-    }
-
-    #[async_trait::async_trait]
-    impl TelemetryRepository for CapturingRepo {
-        async fn save(&self, telemetry: Telemetry) -> anyhow::Result<()> {
-            let mut locked = self
-                .saved
-                .lock()
-                .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
-            locked.push(telemetry);
-            Ok(())
-        }
-
-        async fn query_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
-            let locked = self
-                .saved
-                .lock()
-                .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
-            Ok(locked.clone())
-        }
-    }
-
-    #[async_trait::async_trait]
-    impl TelemetryRepository for Arc<CapturingRepo> {
-        async fn save(&self, telemetry: Telemetry) -> anyhow::Result<()> {
-            (**self).save(telemetry).await
-        }
-
-        async fn query_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
-            (**self).query_all(node_id).await
-        }
+    async fn saved(repo: &InMemoryTelemetryRepo) -> Vec<Telemetry> {
+        repo.query_all(&TenantId::default(), None).await.unwrap()
     }
 
     fn sample_telemetry() -> Telemetry {
@@ -321,14 +365,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_fault_injection_disabled_delegates_unchanged() {
-        let inner = Arc::new(CapturingRepo::default());
+        let inner = InMemoryTelemetryRepo::default();
         let cfg = FaultInjectionConfig::try_new(false, 1.0, 0.0, 123).unwrap();
         let repo = FaultInjectingTelemetryRepo::new(inner.clone(), cfg);
 
         let t = sample_telemetry();
-        repo.save(t.clone()).await.unwrap();
+        repo.save(&TenantId::default(), t.clone()).await.unwrap();
 
-        let saved = inner.saved.lock().unwrap();
+        let saved = saved(&inner).await;
         assert_eq!(saved.len(), 1);
         assert_eq!(saved[0].source_id, t.source_id);
         assert_eq!(saved[0].server_id, t.server_id);
@@ -344,13 +388,15 @@ mod tests {
         });
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let inner = Arc::new(CapturingRepo::default());
+        let inner = InMemoryTelemetryRepo::default();
         let cfg = FaultInjectionConfig::try_new(true, 1.0, 0.0, 123).unwrap();
         let repo = FaultInjectingTelemetryRepo::new(inner.clone(), cfg);
 
-        repo.save(sample_telemetry()).await.unwrap();
+        repo.save(&TenantId::default(), sample_telemetry())
+            .await
+            .unwrap();
 
-        let saved = inner.saved.lock().unwrap();
+        let saved = saved(&inner).await;
         assert_eq!(saved.len(), 0);
         assert!(any_event_has_field(&captured, "decision", "drop"));
     }
@@ -363,16 +409,54 @@ mod tests {
         });
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let inner = Arc::new(CapturingRepo::default());
+        let inner = InMemoryTelemetryRepo::default();
         let cfg = FaultInjectionConfig::try_new(true, 0.0, 1.0, 123).unwrap();
         let repo = FaultInjectingTelemetryRepo::new(inner.clone(), cfg);
 
         let t = sample_telemetry();
-        repo.save(t.clone()).await.unwrap();
+        repo.save(&TenantId::default(), t.clone()).await.unwrap();
 
-        let saved = inner.saved.lock().unwrap();
+        let saved = saved(&inner).await;
         assert_eq!(saved.len(), 1);
         assert_ne!(saved[0].extras, t.extras);
         assert!(any_event_has_field(&captured, "decision", "corrupt"));
     }
+
+    #[tokio::test]
+    async fn test_fault_injection_save_batch_rolls_per_item() {
+        let inner = InMemoryTelemetryRepo::default();
+        let cfg = FaultInjectionConfig::try_new(true, 1.0, 0.0, 123).unwrap();
+        let repo = FaultInjectingTelemetryRepo::new(inner.clone(), cfg);
+
+        repo.save_batch(
+            &TenantId::default(),
+            vec![sample_telemetry(), sample_telemetry()],
+        )
+        .await
+        .unwrap();
+
+        assert!(saved(&inner).await.is_empty());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_fault_injection_counts_every_decision() {
+        let recorder = crate::infra::metrics::build_recorder();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let cfg = FaultInjectionConfig::try_new(true, 1.0, 0.0, 123).unwrap();
+        let repo = FaultInjectingTelemetryRepo::new(InMemoryTelemetryRepo::default(), cfg);
+
+        repo.save_batch(
+            &TenantId::default(),
+            vec![sample_telemetry(), sample_telemetry()],
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            recorder
+                .handle()
+                .render()
+                .contains(r#"rustpulse_fault_injection_decisions_total{decision="drop"} 2"#)
+        );
+    }
 }
@@ -0,0 +1,72 @@
+//! In-process API key repository for storage modes without a database.
+//!
+//! Keys live only as long as the process; restarting the server forgets them.
+
+use chrono::{DateTime, Utc};
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::core::application::api_keys::ApiKeyRepository;
+use crate::core::domains::api_key::{ApiKey, StoredApiKey};
+
+#[derive(Default)]
+/// Keeps API keys in memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+///
+/// let _repo = InMemoryApiKeyRepo::default();
+/// ```
+pub struct InMemoryApiKeyRepo {
+    keys: RwLock<Vec<StoredApiKey>>,
+}
+
+#[async_trait::async_trait]
+impl ApiKeyRepository for InMemoryApiKeyRepo {
+    async fn insert(&self, key: StoredApiKey) -> anyhow::Result<()> {
+        let mut keys = self
+            .keys
+            .write()
+            .map_err(|_| anyhow::anyhow!("api key store poisoned"))?;
+        if keys.iter().any(|k| k.key.prefix == key.key.prefix) {
+            anyhow::bail!("duplicate api key prefix");
+        }
+        keys.push(key);
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+        let keys = self
+            .keys
+            .read()
+            .map_err(|_| anyhow::anyhow!("api key store poisoned"))?;
+        Ok(keys.iter().rev().map(|k| k.key.clone()).collect())
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<StoredApiKey>> {
+        let keys = self
+            .keys
+            .read()
+            .map_err(|_| anyhow::anyhow!("api key store poisoned"))?;
+        Ok(keys.iter().find(|k| k.key.prefix == prefix).cloned())
+    }
+
+    async fn revoke(&self, id: Uuid, at: DateTime<Utc>) -> anyhow::Result<bool> {
+        let mut keys = self
+            .keys
+            .write()
+            .map_err(|_| anyhow::anyhow!("api key store poisoned"))?;
+        match keys
+            .iter_mut()
+            .find(|k| k.key.id == id && k.key.is_active())
+        {
+            Some(stored) => {
+                stored.key.revoked_at = Some(at);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
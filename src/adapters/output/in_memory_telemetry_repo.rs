@@ -0,0 +1,473 @@
+//! Bounded in-process telemetry repository, for tests and ephemeral deployments.
+//!
+//! Records live in a ring buffer shared by every tenant: once `capacity` records are held,
+//! each new one evicts the oldest stored. A per-`(tenant, source)` index keeps source-filtered
+//! queries proportional to that source's records rather than to the whole buffer.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # async fn demo() -> anyhow::Result<()> {
+//! use rustpulse::adapters::output::in_memory_telemetry_repo::InMemoryTelemetryRepo;
+//! use rustpulse::core::application::telemetry::TelemetryRepository;
+//! use rustpulse::core::domains::telemetry::Telemetry;
+//! use rustpulse::core::domains::tenant::TenantId;
+//!
+//! let repo = InMemoryTelemetryRepo::new(2);
+//! let tenant = TenantId::default();
+//! for cpu in [1.0, 2.0, 3.0] {
+//!     let telemetry = Telemetry {
+//!         source_id: uuid::Uuid::nil(),
+//!         server_id: uuid::Uuid::nil(),
+//!         timestamp: chrono::Utc::now(),
+//!         cpu: Some(cpu),
+//!         memory: None,
+//!         temperature: None,
+//!         extras: serde_json::json!({}),
+//!     };
+//!     repo.save(&tenant, telemetry).await?;
+//! }
+//!
+//! assert_eq!(repo.query_all(&tenant, None).await?.len(), 2);
+//! assert_eq!(repo.stats().evicted, 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use crate::core::application::health::DependencyProbe;
+use crate::core::application::retention::{ExpiringStore, RetentionPolicy};
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, BucketAggregator, TelemetryPage, TelemetryQuery,
+    TelemetryRepository,
+};
+use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+
+/// Records held when no capacity is configured.
+pub const DEFAULT_CAPACITY: usize = 100_000;
+
+#[derive(thiserror::Error, Debug)]
+/// Errors produced by the in-memory telemetry repository.
+pub enum InMemoryRepoError {
+    /// The provided `source_id` filter is not a valid UUID.
+    #[error("invalid source id filter")]
+    InvalidSourceId {
+        /// Underlying UUID parse error.
+        source: uuid::Error,
+    },
+
+    /// A writer panicked while holding the buffer lock.
+    #[error("in-memory telemetry store poisoned")]
+    Poisoned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Occupancy and eviction counters of an [`InMemoryTelemetryRepo`].
+pub struct RingStats {
+    /// Records currently held.
+    pub len: usize,
+    /// Most records held at once.
+    pub capacity: usize,
+    /// Records dropped to make room for newer ones since startup.
+    pub evicted: u64,
+    /// Records deleted by retention since startup.
+    pub expired: u64,
+}
+
+struct Entry {
+    seq: u64,
+    tenant: TenantId,
+    telemetry: Telemetry,
+}
+
+struct Ring {
+    capacity: usize,
+    next_seq: u64,
+    /// Oldest first; `seq` is strictly increasing.
+    entries: VecDeque<Entry>,
+    /// Sequence numbers of each `(tenant, source)`'s entries, oldest first.
+    by_source: HashMap<(TenantId, Uuid), VecDeque<u64>>,
+    evicted: u64,
+    expired: u64,
+}
+
+impl Ring {
+    fn push(&mut self, tenant: &TenantId, telemetry: Telemetry) {
+        if self.entries.len() == self.capacity {
+            self.evict_oldest();
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_source
+            .entry((tenant.clone(), telemetry.source_id))
+            .or_default()
+            .push_back(seq);
+        self.entries.push_back(Entry {
+            seq,
+            tenant: tenant.clone(),
+            telemetry,
+        });
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some(entry) = self.entries.pop_front() else {
+            return;
+        };
+        let key = (entry.tenant, entry.telemetry.source_id);
+        if let Some(seqs) = self.by_source.get_mut(&key) {
+            seqs.pop_front();
+            if seqs.is_empty() {
+                self.by_source.remove(&key);
+            }
+        }
+        self.evicted += 1;
+        metrics::counter!("rustpulse_memory_evicted_records_total").increment(1);
+    }
+
+    fn get(&self, seq: u64) -> Option<&Entry> {
+        let index = self.entries.binary_search_by_key(&seq, |e| e.seq).ok()?;
+        self.entries.get(index)
+    }
+
+    /// `tenant`'s entries, of `source` only when set, in insertion order.
+    fn matching_entries(&self, tenant: &TenantId, source: Option<Uuid>) -> Vec<&Entry> {
+        match source {
+            Some(source_id) => self
+                .by_source
+                .get(&(tenant.clone(), source_id))
+                .into_iter()
+                .flatten()
+                .filter_map(|seq| self.get(*seq))
+                .collect(),
+            None => self
+                .entries
+                .iter()
+                .filter(|e| &e.tenant == tenant)
+                .collect(),
+        }
+    }
+
+    /// `tenant`'s telemetry, of `source` only when set, in insertion order.
+    fn matching(&self, tenant: &TenantId, source: Option<Uuid>) -> Vec<&Telemetry> {
+        self.matching_entries(tenant, source)
+            .into_iter()
+            .map(|e| &e.telemetry)
+            .collect()
+    }
+
+    fn rebuild_index(&mut self) {
+        self.by_source.clear();
+        for entry in &self.entries {
+            self.by_source
+                .entry((entry.tenant.clone(), entry.telemetry.source_id))
+                .or_default()
+                .push_back(entry.seq);
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Keeps the most recent telemetry in memory, up to a fixed number of records.
+///
+/// Clones share the same buffer, so a test can keep a handle to a repository it gave away.
+pub struct InMemoryTelemetryRepo {
+    ring: Arc<RwLock<Ring>>,
+}
+
+impl Default for InMemoryTelemetryRepo {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl InMemoryTelemetryRepo {
+    /// Creates an empty repository holding at most `capacity` records (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            ring: Arc::new(RwLock::new(Ring {
+                capacity,
+                next_seq: 0,
+                entries: VecDeque::with_capacity(capacity.min(DEFAULT_CAPACITY)),
+                by_source: HashMap::new(),
+                evicted: 0,
+                expired: 0,
+            })),
+        }
+    }
+
+    /// Returns the current occupancy and eviction counters.
+    pub fn stats(&self) -> RingStats {
+        let ring = self.ring.read().unwrap_or_else(|e| e.into_inner());
+        RingStats {
+            len: ring.entries.len(),
+            capacity: ring.capacity,
+            evicted: ring.evicted,
+            expired: ring.expired,
+        }
+    }
+
+    fn read(&self) -> Result<std::sync::RwLockReadGuard<'_, Ring>, InMemoryRepoError> {
+        self.ring.read().map_err(|_| InMemoryRepoError::Poisoned)
+    }
+
+    fn write(&self) -> Result<std::sync::RwLockWriteGuard<'_, Ring>, InMemoryRepoError> {
+        self.ring.write().map_err(|_| InMemoryRepoError::Poisoned)
+    }
+}
+
+fn source_filter(raw: Option<&str>) -> Result<Option<Uuid>, InMemoryRepoError> {
+    raw.map(Uuid::parse_str)
+        .transpose()
+        .map_err(|source| InMemoryRepoError::InvalidSourceId { source })
+}
+
+#[async_trait::async_trait]
+impl TelemetryRepository for InMemoryTelemetryRepo {
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        self.write()?.push(tenant, telemetry);
+        Ok(())
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        let mut ring = self.write()?;
+        for telemetry in batch {
+            ring.push(tenant, telemetry);
+        }
+        Ok(())
+    }
+
+    fn atomic_batches(&self) -> bool {
+        true
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        let source = source_filter(node_id.as_deref())?;
+        let mut out: Vec<Telemetry> = self
+            .read()?
+            .matching(tenant, source)
+            .into_iter()
+            .cloned()
+            .collect();
+        // Stable, so records sharing a timestamp keep their arrival order.
+        out.sort_by_key(|t| t.timestamp);
+        Ok(out)
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let source = source_filter(query.source_id.as_deref())?;
+        // The sequence number is the storage position: it survives eviction of older entries.
+        let items = self
+            .read()?
+            .matching_entries(tenant, source)
+            .into_iter()
+            .filter(|e| query.matches(&e.telemetry, source))
+            .map(|e| (e.seq as i64, e.telemetry.clone()))
+            .collect();
+        Ok(query.finish(items))
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let source = source_filter(query.source_id.as_deref())?;
+        let mut aggregator = BucketAggregator::new(query.bucket);
+        for telemetry in self.read()?.matching(tenant, source) {
+            if query.matches(telemetry, source) {
+                aggregator.push(telemetry);
+            }
+        }
+        Ok(aggregator.finish())
+    }
+}
+
+#[async_trait::async_trait]
+impl ExpiringStore for InMemoryTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn expire(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let mut ring = self.write()?;
+        let before = ring.entries.len();
+        ring.entries.retain(|e| {
+            policy
+                .cutoff(&e.tenant, e.telemetry.source_id, now)
+                .is_none_or(|cutoff| e.telemetry.timestamp >= cutoff)
+        });
+        let expired = (before - ring.entries.len()) as u64;
+        if expired > 0 {
+            ring.rebuild_index();
+            ring.expired += expired;
+        }
+        Ok(expired)
+    }
+}
+
+#[async_trait::async_trait]
+impl DependencyProbe for InMemoryTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    /// Fails only once a writer panicked and poisoned the buffer.
+    async fn probe(&self) -> anyhow::Result<()> {
+        self.read().map(drop)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn telemetry_at(source_id: Uuid, timestamp: DateTime<Utc>, cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp,
+            cpu: Some(cpu),
+            memory: None,
+            temperature: None,
+            extras: serde_json::json!({}),
+        }
+    }
+
+    fn cpus(items: &[Telemetry]) -> Vec<Option<f64>> {
+        items.iter().map(|t| t.cpu).collect()
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_full_buffer_evicts_oldest_and_keeps_source_index_consistent() {
+        let recorder = crate::infra::metrics::build_recorder();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let repo = InMemoryTelemetryRepo::new(3);
+        let tenant = TenantId::default();
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let base = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+
+        repo.save_batch(
+            &tenant,
+            vec![
+                telemetry_at(a, base, 1.0),
+                telemetry_at(b, base, 2.0),
+                telemetry_at(a, base + TimeDelta::seconds(1), 3.0),
+            ],
+        )
+        .await
+        .unwrap();
+        repo.save(&tenant, telemetry_at(b, base + TimeDelta::seconds(2), 4.0))
+            .await
+            .unwrap();
+
+        let only_a = repo.query_all(&tenant, Some(a.to_string())).await.unwrap();
+        let only_b = repo.query_all(&tenant, Some(b.to_string())).await.unwrap();
+        assert_eq!(cpus(&only_a), vec![Some(3.0)]);
+        assert_eq!(cpus(&only_b), vec![Some(2.0), Some(4.0)]);
+        assert_eq!(
+            repo.stats(),
+            RingStats {
+                len: 3,
+                capacity: 3,
+                evicted: 1,
+                expired: 0
+            }
+        );
+        assert!(
+            recorder
+                .handle()
+                .render()
+                .contains("rustpulse_memory_evicted_records_total 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queries_are_scoped_to_tenant_and_paginate_like_other_backends() {
+        let repo = InMemoryTelemetryRepo::default();
+        let acme = TenantId::parse("acme").unwrap();
+        let source_id = Uuid::new_v4();
+        let base = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+        // Stored out of timestamp order on purpose.
+        for i in [3, 0, 4, 1, 2] {
+            repo.save(
+                &acme,
+                telemetry_at(source_id, base + TimeDelta::seconds(i), i as f64),
+            )
+            .await
+            .unwrap();
+        }
+        repo.save(&TenantId::default(), telemetry_at(source_id, base, 9.0))
+            .await
+            .unwrap();
+
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            from: Some(base + TimeDelta::seconds(1)),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first = repo.query_page(&acme, query.clone()).await.unwrap();
+        let second = repo
+            .query_page(
+                &acme,
+                TelemetryQuery {
+                    cursor: first.next_cursor,
+                    ..query
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(cpus(&first.items), vec![Some(1.0), Some(2.0)]);
+        assert_eq!(cpus(&second.items), vec![Some(3.0), Some(4.0)]);
+        assert!(second.next_cursor.is_none());
+        assert_eq!(repo.query_all(&acme, None).await.unwrap().len(), 5);
+        assert!(
+            repo.query_all(&acme, Some("nope".to_string()))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expire_drops_records_past_their_retention() {
+        let repo = InMemoryTelemetryRepo::default();
+        let acme = TenantId::parse("acme").unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+        let old = now - TimeDelta::days(10);
+        repo.save(&TenantId::default(), telemetry_at(Uuid::nil(), old, 1.0))
+            .await
+            .unwrap();
+        repo.save(&acme, telemetry_at(Uuid::nil(), old, 2.0))
+            .await
+            .unwrap();
+
+        let policy = RetentionPolicy::new(Some(TimeDelta::days(7)))
+            .with_tenant(acme.clone(), TimeDelta::days(30));
+        assert_eq!(repo.expire(&policy, now).await.unwrap(), 1);
+        assert!(
+            repo.query_all(&TenantId::default(), Some(Uuid::nil().to_string()))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(repo.query_all(&acme, None).await.unwrap().len(), 1);
+        assert_eq!(repo.stats().expired, 1);
+    }
+}
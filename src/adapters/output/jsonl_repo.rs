@@ -1,5 +1,15 @@
 //! JSONL-backed telemetry repository.
 //!
+//! Each tenant's telemetry is appended to numbered segments in
+//! `<path>.segments/<tenant>/`. A segment is sealed once it reaches the
+//! [`RotationPolicy`] size or the hour changes, and gets a sidecar
+//! `<seq>.index.json` with its record count, timestamp range and source ids, so
+//! range and source queries only open the segments that can match. Reads take no
+//! lock; they stop at a final line still being written.
+//!
+//! Files from before segmentation are still read first: the configured path for
+//! the default tenant and `<stem>.<tenant>.<ext>` for any other tenant.
+//!
 //! # Examples
 //!
 //! ```rust,no_run
@@ -7,13 +17,14 @@
 //! use rustpulse::adapters::output::jsonl_repo::JsonlTelemetryRepo;
 //! use rustpulse::core::application::telemetry::TelemetryRepository as _;
 //! use rustpulse::core::domains::telemetry::Telemetry;
+//! use rustpulse::core::domains::tenant::TenantId;
 //! use chrono::Utc;
 //! use uuid::Uuid;
 //!
 //! let path = std::env::temp_dir().join("telemetry.jsonl");
 //! let repo = JsonlTelemetryRepo::new(path);
 //!
-//! repo.save(Telemetry {
+//! repo.save(&TenantId::default(), Telemetry {
 //!     source_id: Uuid::new_v4(),
 //!     server_id: Uuid::new_v4(),
 //!     timestamp: Utc::now(),
@@ -28,21 +39,117 @@
 //! ```
 
 // adapter/jsonl/telemetry_repo.rs
-use crate::core::application::telemetry::TelemetryRepository;
+use crate::core::application::health::DependencyProbe;
+use crate::core::application::retention::{ExpiringStore, RetentionPolicy};
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, BucketAggregator, SortOrder, TelemetryPage, TelemetryQuery,
+    TelemetryQueryError, TelemetryRepository, TelemetryStream,
+};
 use crate::core::domains::telemetry::Telemetry;
-use std::fs::OpenOptions;
+use crate::core::domains::tenant::TenantId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-/// Stores and retrieves telemetry from a newline-delimited JSON file.
+const SEGMENT_EXTENSION: &str = "jsonl";
+const INDEX_SUFFIX: &str = ".index.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// When a tenant's active segment is sealed and a new one started.
+pub struct RotationPolicy {
+    /// Seal the segment once it holds this many bytes.
+    pub max_bytes: u64,
+    /// Also seal it when the wall-clock hour (UTC) changes.
+    pub hourly: bool,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024 * 1024,
+            hourly: true,
+        }
+    }
+}
+
+impl RotationPolicy {
+    fn should_rotate(&self, segment: &ActiveSegment, incoming: u64, hour: i64) -> bool {
+        segment.bytes > 0
+            && (segment.bytes + incoming > self.max_bytes || (self.hourly && segment.hour != hour))
+    }
+}
+
+/// Sidecar summary of a sealed segment, checked before the segment is opened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SegmentIndex {
+    records: u64,
+    min_timestamp: Option<DateTime<Utc>>,
+    max_timestamp: Option<DateTime<Utc>>,
+    source_ids: BTreeSet<Uuid>,
+}
+
+impl SegmentIndex {
+    fn push(&mut self, telemetry: &Telemetry) {
+        self.records += 1;
+        self.min_timestamp = Some(
+            self.min_timestamp
+                .map_or(telemetry.timestamp, |min| min.min(telemetry.timestamp)),
+        );
+        self.max_timestamp = Some(
+            self.max_timestamp
+                .map_or(telemetry.timestamp, |max| max.max(telemetry.timestamp)),
+        );
+        self.source_ids.insert(telemetry.source_id);
+    }
+
+    fn may_contain(&self, filter: &SegmentFilter) -> bool {
+        self.records > 0
+            && filter.source.is_none_or(|id| self.source_ids.contains(&id))
+            && filter
+                .from
+                .is_none_or(|from| self.max_timestamp.is_some_and(|max| max >= from))
+            && filter
+                .to
+                .is_none_or(|to| self.min_timestamp.is_some_and(|min| min < to))
+    }
+}
+
+/// Source and time range a read is restricted to.
+#[derive(Debug, Default)]
+struct SegmentFilter {
+    source: Option<Uuid>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+struct ActiveSegment {
+    seq: u64,
+    // Shared with the blocking task writing to it.
+    file: Arc<File>,
+    bytes: u64,
+    /// Hours since the Unix epoch when the segment was opened.
+    hour: i64,
+    index: SegmentIndex,
+}
+
+/// Stores and retrieves telemetry from rotated newline-delimited JSON segments, one set per tenant.
 pub struct JsonlTelemetryRepo<P: AsRef<std::path::Path>> {
-    /// Path to the JSONL file.
+    /// Path of the default tenant's legacy JSONL file; segments live next to it.
     pub path: P,
-    /// In-process lock used to serialize file access.
-    pub lock: Mutex<()>,
+    rotation: RotationPolicy,
+    /// Active segment per tenant; only writers and expiry take a tenant's lock.
+    active: std::sync::Mutex<HashMap<TenantId, TenantSegment>>,
 }
 
+/// A tenant's active segment, if one is open.
+type TenantSegment = Arc<Mutex<Option<ActiveSegment>>>;
+
 impl<P: AsRef<std::path::Path>> JsonlTelemetryRepo<P> {
     /// Creates a repository backed by the provided file path.
     ///
@@ -57,7 +164,361 @@ impl<P: AsRef<std::path::Path>> JsonlTelemetryRepo<P> {
     pub fn new(path: P) -> Self {
         Self {
             path,
-            lock: Mutex::new(()),
+            rotation: RotationPolicy::default(),
+            active: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the default rotation policy (64 MiB or hourly).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::output::jsonl_repo::{JsonlTelemetryRepo, RotationPolicy};
+    ///
+    /// let _repo = JsonlTelemetryRepo::new("metrics.jsonl").with_rotation(RotationPolicy {
+    ///     max_bytes: 8 * 1024 * 1024,
+    ///     hourly: false,
+    /// });
+    /// ```
+    pub fn with_rotation(mut self, rotation: RotationPolicy) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Returns the legacy single-file store of `tenant`, still read but no longer written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::output::jsonl_repo::JsonlTelemetryRepo;
+    /// use rustpulse::core::domains::tenant::TenantId;
+    /// use std::path::Path;
+    ///
+    /// let repo = JsonlTelemetryRepo::new("data/metrics.jsonl");
+    /// let acme = TenantId::parse("acme").unwrap();
+    /// assert_eq!(repo.tenant_path(&acme), Path::new("data/metrics.acme.jsonl"));
+    /// assert_eq!(repo.tenant_path(&TenantId::default()), Path::new("data/metrics.jsonl"));
+    /// ```
+    pub fn tenant_path(&self, tenant: &TenantId) -> PathBuf {
+        let path = self.path.as_ref();
+        if tenant.is_default() {
+            return path.to_path_buf();
+        }
+
+        // Tenant ids never contain '.', so these names cannot collide with the default file.
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match path.extension() {
+            Some(ext) => format!("{stem}.{tenant}.{}", ext.to_string_lossy()),
+            None => format!("{stem}.{tenant}"),
+        };
+        path.with_file_name(name)
+    }
+
+    /// Returns the directory holding `tenant`'s segments and their indexes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::output::jsonl_repo::JsonlTelemetryRepo;
+    /// use rustpulse::core::domains::tenant::TenantId;
+    /// use std::path::Path;
+    ///
+    /// let repo = JsonlTelemetryRepo::new("data/metrics.jsonl");
+    /// let acme = TenantId::parse("acme").unwrap();
+    /// assert_eq!(repo.segment_dir(&acme), Path::new("data/metrics.jsonl.segments/acme"));
+    /// ```
+    pub fn segment_dir(&self, tenant: &TenantId) -> PathBuf {
+        self.segments_root().join(tenant.as_str())
+    }
+
+    fn segments_root(&self) -> PathBuf {
+        let mut root = self.path.as_ref().as_os_str().to_owned();
+        root.push(".segments");
+        PathBuf::from(root)
+    }
+
+    fn tenant_segment(&self, tenant: &TenantId) -> TenantSegment {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        active.entry(tenant.clone()).or_default().clone()
+    }
+
+    async fn append(&self, tenant: &TenantId, batch: &[Telemetry]) -> anyhow::Result<()> {
+        // Serialize up front so a bad item never leaves a half-written batch behind.
+        let mut lines = String::new();
+        for telemetry in batch {
+            lines.push_str(&serde_json::to_string(telemetry)?);
+            lines.push('\n');
+        }
+        let len = lines.len() as u64;
+        let hour = Utc::now().timestamp().div_euclid(3600);
+        let dir = self.segment_dir(tenant);
+
+        let tenant_segment = self.tenant_segment(tenant);
+        // Held across the write, which keeps a tenant's appends in order and lets a failed
+        // one be cut off; other tenants and readers never wait on it.
+        let mut slot = tenant_segment.lock().await;
+        // Out of the slot until the write is accounted for: if this future is dropped
+        // mid-write, the next append starts a new segment and indexes this one.
+        let mut segment = match slot.take() {
+            Some(segment) if !self.rotation.should_rotate(&segment, len, hour) => segment,
+            sealed => {
+                if let Some(sealed) = sealed {
+                    seal(dir.clone(), sealed).await?;
+                }
+                let open_dir = dir.clone();
+                tokio::task::spawn_blocking(move || open_segment(&open_dir, hour)).await??
+            }
+        };
+
+        let file = segment.file.clone();
+        let offset = segment.bytes;
+        let written = tokio::task::spawn_blocking(move || {
+            // Part of the batch may be on disk: cut it off so a retry cannot duplicate it.
+            (&*file)
+                .write_all(lines.as_bytes())
+                .map_err(|e| (e, file.set_len(offset)))
+        })
+        .await?;
+
+        match written {
+            Ok(()) => {
+                segment.bytes += len;
+                for telemetry in batch {
+                    segment.index.push(telemetry);
+                }
+                *slot = Some(segment);
+                Ok(())
+            }
+            Err((e, rolled_back)) => {
+                // Seal the segment so nothing is appended after it.
+                seal(dir, segment).await?;
+                if let Err(truncate) = rolled_back {
+                    // Not an io::Error, so the batch is not retried on top of its partial copy.
+                    anyhow::bail!("append failed ({e}) and could not be rolled back: {truncate}");
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Folds every record of `tenant` in the files that may pass `filter` into `acc`, with
+    /// its [`record_id`], on a blocking thread.
+    async fn scan<A: Send + 'static>(
+        &self,
+        tenant: &TenantId,
+        filter: SegmentFilter,
+        mut acc: A,
+        mut visit: impl FnMut(&mut A, i64, Telemetry) + Send + 'static,
+    ) -> anyhow::Result<A> {
+        let legacy = self.tenant_path(tenant);
+        let dir = self.segment_dir(tenant);
+        tokio::task::spawn_blocking(move || {
+            for (file, path) in files_for_read(legacy, &dir, &filter)? {
+                let mut line = 0;
+                read_records(&path, &mut |t| {
+                    visit(&mut acc, record_id(file, line), t);
+                    line += 1;
+                })?;
+            }
+            Ok(acc)
+        })
+        .await?
+    }
+}
+
+/// Files of a tenant that may hold records passing `filter`, oldest first, with the file
+/// number their [`record_id`]s use: `0` for the `legacy` file from before segmentation and
+/// `seq + 1` for segment `seq` of `dir`, so no two files share one.
+fn files_for_read(
+    legacy: PathBuf,
+    dir: &Path,
+    filter: &SegmentFilter,
+) -> std::io::Result<Vec<(u64, PathBuf)>> {
+    let mut files = vec![(0, legacy)];
+    for seq in list_segments(dir)? {
+        // The active segment (and any left unindexed by a crash) is always scanned.
+        if read_index(dir, seq).is_none_or(|index| index.may_contain(filter)) {
+            files.push((seq + 1, segment_path(dir, seq)));
+        }
+    }
+    Ok(files)
+}
+
+/// Storage position of the `line`-th record of file number `file` (see
+/// [`files_for_read`]), used as the cursor tie-breaker.
+///
+/// Segments are append-only and only ever deleted whole, so a record keeps its position.
+fn record_id(file: u64, line: u64) -> i64 {
+    ((file << 32) | (line & 0xFFFF_FFFF)) as i64
+}
+
+fn segment_path(dir: &Path, seq: u64) -> PathBuf {
+    dir.join(format!("{seq:020}.{SEGMENT_EXTENSION}"))
+}
+
+fn index_path(dir: &Path, seq: u64) -> PathBuf {
+    dir.join(format!("{seq:020}{INDEX_SUFFIX}"))
+}
+
+/// Tenants with a segment directory under `root`; none when it does not exist yet.
+fn list_tenant_dirs(root: &Path) -> std::io::Result<Vec<(TenantId, PathBuf)>> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut tenants = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if let Some(tenant) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| TenantId::parse(name).ok())
+        {
+            tenants.push((tenant, entry.path()));
+        }
+    }
+    Ok(tenants)
+}
+
+/// Sequence numbers of the segments in `dir`, oldest first; none when it does not exist yet.
+fn list_segments(dir: &Path) -> std::io::Result<Vec<u64>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut seqs = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let seq = name
+            .to_str()
+            .and_then(|n| n.strip_suffix(SEGMENT_EXTENSION))
+            .and_then(|n| n.strip_suffix('.'))
+            .and_then(|n| n.parse::<u64>().ok());
+        seqs.extend(seq);
+    }
+    seqs.sort_unstable();
+    Ok(seqs)
+}
+
+/// Loads a segment's index; `None` when it is missing or unreadable, so the segment gets scanned.
+fn read_index(dir: &Path, seq: u64) -> Option<SegmentIndex> {
+    let bytes = fs::read(index_path(dir, seq)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Writes the index of a segment that is no longer appended to, on a blocking thread.
+async fn seal(dir: PathBuf, segment: ActiveSegment) -> anyhow::Result<()> {
+    tokio::task::spawn_blocking(move || write_index(&dir, segment.seq, &segment.index)).await??;
+    Ok(())
+}
+
+/// Writes the index next to its segment, atomically so readers never see half of it.
+fn write_index(dir: &Path, seq: u64, index: &SegmentIndex) -> std::io::Result<()> {
+    let path = index_path(dir, seq);
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec(index)?)?;
+    fs::rename(tmp, path)
+}
+
+/// Starts a new segment after the existing ones, indexing any a previous process left unsealed.
+fn open_segment(dir: &Path, hour: i64) -> anyhow::Result<ActiveSegment> {
+    fs::create_dir_all(dir)?;
+    let seqs = list_segments(dir)?;
+    for &seq in &seqs {
+        if read_index(dir, seq).is_none() {
+            let mut index = SegmentIndex::default();
+            read_records(&segment_path(dir, seq), &mut |t| index.push(&t))?;
+            write_index(dir, seq, &index)?;
+        }
+    }
+
+    let seq = seqs.last().map_or(0, |last| last + 1);
+    let file = Arc::new(
+        OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(segment_path(dir, seq))?,
+    );
+    Ok(ActiveSegment {
+        seq,
+        file,
+        bytes: 0,
+        hour,
+        index: SegmentIndex::default(),
+    })
+}
+
+/// Calls `visit` for each record in `path`; a missing file has none.
+///
+/// Stops at a final line without a newline: it is still being appended (or was torn by a crash).
+fn read_records(path: &Path, visit: &mut impl FnMut(Telemetry)) -> anyhow::Result<()> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+            return Ok(());
+        }
+        visit(serde_json::from_str(&line)?);
+    }
+}
+
+/// Reads the records of a list of files one line at a time, keeping those a query matches.
+struct RecordStream {
+    files: std::vec::IntoIter<(u64, PathBuf)>,
+    reader: Option<tokio::io::BufReader<tokio::fs::File>>,
+    /// File number and next line number of `reader`.
+    position: (u64, u64),
+    line: String,
+    query: TelemetryQuery,
+    source: Option<Uuid>,
+    remaining: Option<usize>,
+}
+
+impl RecordStream {
+    /// Next matching record, with the same end-of-file rule as [`read_records`].
+    async fn next_record(mut self) -> anyhow::Result<Option<(Telemetry, Self)>> {
+        use tokio::io::AsyncBufReadExt;
+
+        loop {
+            if self.remaining == Some(0) {
+                return Ok(None);
+            }
+            let Some(reader) = &mut self.reader else {
+                let Some((file, path)) = self.files.next() else {
+                    return Ok(None);
+                };
+                self.position = (file, 0);
+                match tokio::fs::File::open(&path).await {
+                    Ok(file) => self.reader = Some(tokio::io::BufReader::new(file)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e.into()),
+                }
+                continue;
+            };
+
+            self.line.clear();
+            if reader.read_line(&mut self.line).await? == 0 || !self.line.ends_with('\n') {
+                self.reader = None;
+                continue;
+            }
+            let telemetry: Telemetry = serde_json::from_str(&self.line)?;
+            let (file, line) = self.position;
+            self.position.1 += 1;
+            if self.query.matches(&telemetry, self.source)
+                && self.query.after_cursor(record_id(file, line), &telemetry)
+            {
+                self.remaining = self.remaining.map(|n| n - 1);
+                return Ok(Some((telemetry, self)));
+            }
         }
     }
 }
@@ -67,39 +528,212 @@ impl<P> TelemetryRepository for JsonlTelemetryRepo<P>
 where
     P: AsRef<std::path::Path> + Send + Sync,
 {
-    async fn save(&self, telemetry: Telemetry) -> anyhow::Result<()> {
-        let _guard = self.lock.lock().await;
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.path)?;
-        let line = serde_json::to_string(&telemetry)?;
-        writeln!(file, "{line}")?;
-        Ok(())
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        self.append(tenant, std::slice::from_ref(&telemetry)).await
     }
 
-    async fn query_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
-        let file = OpenOptions::new().read(true).open(&self.path)?;
-        let reader = BufReader::new(file);
-        let iter = reader.lines().map(|line| -> anyhow::Result<Telemetry> {
-            let line = line?;
-            Ok(serde_json::from_str(&line)?)
-        });
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        self.append(tenant, &batch).await
+    }
 
-        let result: anyhow::Result<Vec<Telemetry>> = match node_id {
-            Some(id_str) => {
-                let id = Uuid::parse_str(&id_str)?;
-                iter.filter_map(|res| match res {
-                    Ok(t) if t.source_id == id => Some(Ok(t)),
-                    Ok(_) => None,
-                    Err(e) => Some(Err(e)),
-                })
-                .collect()
+    fn atomic_batches(&self) -> bool {
+        true
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        let source = node_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let filter = SegmentFilter {
+            source,
+            ..Default::default()
+        };
+
+        self.scan(tenant, filter, Vec::new(), move |items, _, t| {
+            if source.is_none_or(|id| t.source_id == id) {
+                items.push(t);
             }
-            None => iter.collect(),
+        })
+        .await
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let source = query.source_filter()?;
+        let filter = SegmentFilter {
+            source,
+            from: query.from,
+            to: query.to,
         };
 
-        result
+        // Filter while reading so rows outside the range are never held in memory.
+        let matcher = query.clone();
+        let items = self
+            .scan(tenant, filter, Vec::new(), move |items, id, t| {
+                if matcher.matches(&t, source) {
+                    items.push((id, t));
+                }
+            })
+            .await?;
+
+        Ok(query.finish(items))
+    }
+
+    /// Streams matching records line by line, in the order they were appended.
+    ///
+    /// Descending order is rejected with [`TelemetryQueryError::UnsupportedOrder`]: reversing
+    /// the segments would need the whole result in memory.
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        if query.order == SortOrder::Desc {
+            return Err(TelemetryQueryError::UnsupportedOrder(query.order).into());
+        }
+        let source = query.source_filter()?;
+        let filter = SegmentFilter {
+            source,
+            from: query.from,
+            to: query.to,
+        };
+        let legacy = self.tenant_path(tenant);
+        let dir = self.segment_dir(tenant);
+        let files =
+            tokio::task::spawn_blocking(move || files_for_read(legacy, &dir, &filter)).await??;
+        let records = RecordStream {
+            files: files.into_iter(),
+            reader: None,
+            position: (0, 0),
+            line: String::new(),
+            remaining: query.limit,
+            query,
+            source,
+        };
+        Ok(Box::pin(futures_util::stream::try_unfold(
+            records,
+            RecordStream::next_record,
+        )))
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let source = query.source_filter()?;
+        let filter = SegmentFilter {
+            source,
+            from: query.from,
+            to: query.to,
+        };
+
+        // One pass over the relevant segments; each bucket keeps a bounded sample for percentiles.
+        let aggregator = BucketAggregator::new(query.bucket);
+        let aggregator = self
+            .scan(tenant, filter, aggregator, move |aggregator, _, t| {
+                if query.matches(&t, source) {
+                    aggregator.push(&t);
+                }
+            })
+            .await?;
+
+        Ok(aggregator.finish())
+    }
+}
+
+#[async_trait::async_trait]
+impl<P> ExpiringStore for JsonlTelemetryRepo<P>
+where
+    P: AsRef<std::path::Path> + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+
+    /// Deletes sealed segments whose every source has expired past the segment's newest record.
+    ///
+    /// Active segments and pre-segmentation files are never deleted.
+    async fn expire(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let root = self.segments_root();
+        let tenants = tokio::task::spawn_blocking(move || list_tenant_dirs(&root)).await??;
+
+        let mut expired = 0;
+        for (tenant, dir) in tenants {
+            let tenant_segment = self.tenant_segment(&tenant);
+            // Held so a writer never re-indexes a segment while it is being deleted.
+            let _writer = tenant_segment.lock().await;
+
+            let list_dir = dir.clone();
+            let sealed = tokio::task::spawn_blocking(move || {
+                let seqs = list_segments(&list_dir)?;
+                std::io::Result::Ok(
+                    seqs.into_iter()
+                        .filter_map(|seq| read_index(&list_dir, seq).map(|index| (seq, index)))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .await??;
+
+            let (seqs, records): (Vec<u64>, Vec<u64>) = sealed
+                .into_iter()
+                .filter(|(_, index)| {
+                    index.max_timestamp.is_none_or(|newest| {
+                        index.source_ids.iter().all(|source| {
+                            policy
+                                .cutoff(&tenant, *source, now)
+                                .is_some_and(|cutoff| newest < cutoff)
+                        })
+                    })
+                })
+                .map(|(seq, index)| (seq, index.records))
+                .unzip();
+            if seqs.is_empty() {
+                continue;
+            }
+
+            tokio::task::spawn_blocking(move || {
+                for seq in seqs {
+                    fs::remove_file(segment_path(&dir, seq))?;
+                    fs::remove_file(index_path(&dir, seq))?;
+                }
+                std::io::Result::Ok(())
+            })
+            .await??;
+            expired += records.iter().sum::<u64>();
+        }
+
+        tracing::info!(row_count = expired, "repo.telemetry.expire");
+        Ok(expired)
+    }
+}
+
+#[async_trait::async_trait]
+impl<P> DependencyProbe for JsonlTelemetryRepo<P>
+where
+    P: AsRef<std::path::Path> + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+
+    /// Checks a file can be created in the default tenant's segment directory.
+    async fn probe(&self) -> anyhow::Result<()> {
+        let dir = self.segment_dir(&TenantId::default());
+        let probe = dir.join(".probe");
+        tokio::task::spawn_blocking(move || {
+            fs::create_dir_all(&dir)
+                .and_then(|()| File::create(&probe))
+                .and_then(|_| fs::remove_file(&probe))
+        })
+        .await?
+        .map_err(|e| anyhow::anyhow!("segment directory is not writable: {}", e.kind()))?;
+        Ok(())
     }
 }
 
@@ -108,9 +742,421 @@ where
 mod tests {
     use super::*;
 
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("rustpulse-{}.jsonl", Uuid::new_v4()))
+    }
+
+    fn remove_store(path: &Path) {
+        let mut segments = path.as_os_str().to_owned();
+        segments.push(".segments");
+        std::fs::remove_dir_all(segments).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    fn telemetry_at(source_id: Uuid, timestamp: DateTime<Utc>, cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp,
+            cpu: Some(cpu),
+            memory: None,
+            temperature: None,
+            extras: serde_json::json!({}),
+        }
+    }
+
     #[test]
     fn test_load_metrics() {
         let repo: JsonlTelemetryRepo<String> = JsonlTelemetryRepo::new("mock-path.jsonl".into());
-        let _data = repo.query_all(None);
+        let tenant = TenantId::default();
+        let _data = repo.query_all(&tenant, None);
+    }
+
+    #[tokio::test]
+    async fn test_query_page_filters_by_range_and_returns_next_cursor() {
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone());
+        let source_id = Uuid::new_v4();
+        let base = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+
+        for i in 0..5 {
+            repo.save(
+                &TenantId::default(),
+                telemetry_at(source_id, base + chrono::Duration::seconds(i), i as f64),
+            )
+            .await
+            .unwrap();
+        }
+
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            from: Some(base + chrono::Duration::seconds(1)),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let page = repo.query_page(&TenantId::default(), query).await.unwrap();
+        remove_store(&path);
+
+        let cpus: Vec<_> = page.items.iter().map(|t| t.cpu).collect();
+        assert_eq!(cpus, vec![Some(1.0), Some(2.0)]);
+        assert_eq!(
+            page.next_cursor.map(|c| c.timestamp),
+            Some(base + chrono::Duration::seconds(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_stream_reads_every_segment_with_filters_and_limit() {
+        use futures_util::TryStreamExt;
+
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone()).with_rotation(RotationPolicy {
+            max_bytes: 1,
+            hourly: false,
+        });
+        let source_id = Uuid::new_v4();
+        let base = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        for i in 0..6 {
+            let source = if i == 3 { Uuid::new_v4() } else { source_id };
+            repo.save(
+                &TenantId::default(),
+                telemetry_at(source, base + chrono::Duration::seconds(i), i as f64),
+            )
+            .await
+            .unwrap();
+        }
+
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            from: Some(base + chrono::Duration::seconds(1)),
+            limit: Some(3),
+            ..Default::default()
+        };
+        let stream = repo
+            .query_stream(&TenantId::default(), query)
+            .await
+            .unwrap();
+        let items: Vec<Telemetry> = stream.try_collect().await.unwrap();
+        remove_store(&path);
+
+        let cpus: Vec<_> = items.iter().map(|t| t.cpu).collect();
+        assert_eq!(cpus, vec![Some(1.0), Some(2.0), Some(4.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_pages_cover_legacy_file_and_first_segment_without_overlap() {
+        use futures_util::TryStreamExt;
+
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone());
+        let tenant = TenantId::default();
+        let source_id = Uuid::new_v4();
+        let at = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        // Line N of the legacy file and line N of segment 0 share timestamp and source.
+        let legacy: String = (0..3)
+            .map(|i| serde_json::to_string(&telemetry_at(source_id, at, i as f64)).unwrap() + "\n")
+            .collect();
+        fs::write(&path, legacy).unwrap();
+        for i in 3..6 {
+            repo.save(&tenant, telemetry_at(source_id, at, i as f64))
+                .await
+                .unwrap();
+        }
+        assert_eq!(list_segments(&repo.segment_dir(&tenant)).unwrap(), vec![0]);
+
+        let mut cpus = Vec::new();
+        let mut query = TelemetryQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        loop {
+            let page = repo.query_page(&tenant, query.clone()).await.unwrap();
+            cpus.extend(page.items.iter().filter_map(|t| t.cpu));
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+
+        // A stream resumed from a cursor into the legacy file picks up segment 0 in full.
+        let first = repo
+            .query_page(
+                &tenant,
+                TelemetryQuery {
+                    limit: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let rest: Vec<Telemetry> = repo
+            .query_stream(
+                &tenant,
+                TelemetryQuery {
+                    cursor: first.next_cursor,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        remove_store(&path);
+
+        assert_eq!(cpus, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            rest.iter().filter_map(|t| t.cpu).collect::<Vec<_>>(),
+            vec![2.0, 3.0, 4.0, 5.0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_stream_rejects_descending_order() {
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone());
+        let query = TelemetryQuery {
+            order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let err = repo
+            .query_stream(&TenantId::default(), query)
+            .await
+            .err()
+            .expect("descending stream");
+        remove_store(&path);
+
+        assert!(matches!(
+            err.downcast_ref::<TelemetryQueryError>(),
+            Some(TelemetryQueryError::UnsupportedOrder(SortOrder::Desc))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tenants_are_stored_in_separate_segments_and_never_mixed() {
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone());
+        let acme = TenantId::parse("acme").unwrap();
+        let now = Utc::now();
+
+        repo.save(&TenantId::default(), telemetry_at(Uuid::nil(), now, 1.0))
+            .await
+            .unwrap();
+        repo.save_batch(
+            &acme,
+            vec![
+                telemetry_at(Uuid::nil(), now, 2.0),
+                telemetry_at(Uuid::nil(), now, 3.0),
+            ],
+        )
+        .await
+        .unwrap();
+        let other = TenantId::parse("globex").unwrap();
+        let default_rows = repo.query_all(&TenantId::default(), None).await.unwrap();
+        let acme_rows = repo.query_all(&acme, None).await.unwrap();
+        let other_rows = repo.query_all(&other, None).await.unwrap();
+        let acme_segments = list_segments(&repo.segment_dir(&acme)).unwrap();
+        remove_store(&path);
+
+        assert_eq!(default_rows.len(), 1);
+        assert_eq!(
+            acme_rows.iter().map(|t| t.cpu).collect::<Vec<_>>(),
+            vec![Some(2.0), Some(3.0)]
+        );
+        assert!(other_rows.is_empty());
+        assert_eq!(acme_segments, vec![0]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writers_keep_every_batch_whole_and_in_its_tenant() {
+        let path = temp_path();
+        let repo = Arc::new(JsonlTelemetryRepo::new(path.clone()));
+        let acme = TenantId::parse("acme").unwrap();
+        let now = Utc::now();
+
+        let writers = (0..16).map(|i| {
+            let repo = repo.clone();
+            let tenant = if i % 2 == 0 {
+                TenantId::default()
+            } else {
+                acme.clone()
+            };
+            tokio::spawn(async move {
+                let batch = (0..25)
+                    .map(|_| telemetry_at(Uuid::nil(), now, f64::from(i)))
+                    .collect();
+                repo.save_batch(&tenant, batch).await
+            })
+        });
+        for writer in writers.collect::<Vec<_>>() {
+            writer.await.unwrap().unwrap();
+        }
+        let default_rows = repo.query_all(&TenantId::default(), None).await.unwrap();
+        let acme_rows = repo.query_all(&acme, None).await.unwrap();
+        remove_store(&path);
+
+        for (rows, parity) in [(default_rows, 0.0), (acme_rows, 1.0)] {
+            assert_eq!(rows.len(), 8 * 25);
+            // Each batch is contiguous: its 25 records are never interleaved with another's.
+            for batch in rows.chunks(25) {
+                let cpu = batch[0].cpu.unwrap();
+                assert_eq!(cpu % 2.0, parity);
+                assert!(batch.iter().all(|t| t.cpu == Some(cpu)));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sealed_segments_are_indexed_and_skipped_when_out_of_range() {
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone()).with_rotation(RotationPolicy {
+            max_bytes: 1,
+            hourly: false,
+        });
+        let tenant = TenantId::default();
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let base = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let at = |minutes| base + chrono::Duration::minutes(minutes);
+
+        // One record per segment: a@0, b@10, a@20 (active, not indexed yet).
+        repo.save(&tenant, telemetry_at(a, at(0), 0.0))
+            .await
+            .unwrap();
+        repo.save(&tenant, telemetry_at(b, at(10), 1.0))
+            .await
+            .unwrap();
+        repo.save(&tenant, telemetry_at(a, at(20), 2.0))
+            .await
+            .unwrap();
+
+        let dir = repo.segment_dir(&tenant);
+        assert_eq!(list_segments(&dir).unwrap(), vec![0, 1, 2]);
+        let index = read_index(&dir, 1).unwrap();
+        assert_eq!(index.records, 1);
+        assert_eq!(index.min_timestamp, Some(at(10)));
+        assert!(index.source_ids.contains(&b));
+        assert!(read_index(&dir, 2).is_none());
+
+        let by_source = SegmentFilter {
+            source: Some(b),
+            ..Default::default()
+        };
+        let by_range = SegmentFilter {
+            from: Some(at(15)),
+            ..Default::default()
+        };
+        let segments = |filter| {
+            files_for_read(repo.tenant_path(&tenant), &dir, &filter)
+                .unwrap()
+                .into_iter()
+                .skip(1) // the legacy file
+                .map(|(_, path)| path)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            segments(by_source),
+            vec![segment_path(&dir, 1), segment_path(&dir, 2)]
+        );
+        assert_eq!(segments(by_range), vec![segment_path(&dir, 2)]);
+
+        let page = repo
+            .query_page(
+                &tenant,
+                TelemetryQuery {
+                    source_id: Some(a.to_string()),
+                    to: Some(at(15)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        remove_store(&path);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].cpu, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_reopened_store_reads_legacy_file_and_indexes_unsealed_segment() {
+        let path = temp_path();
+        let tenant = TenantId::default();
+        let now = Utc::now();
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n",
+                serde_json::to_string(&telemetry_at(Uuid::nil(), now, 1.0)).unwrap()
+            ),
+        )
+        .unwrap();
+
+        JsonlTelemetryRepo::new(path.clone())
+            .save(&tenant, telemetry_at(Uuid::nil(), now, 2.0))
+            .await
+            .unwrap();
+
+        let repo = JsonlTelemetryRepo::new(path.clone());
+        repo.save(&tenant, telemetry_at(Uuid::nil(), now, 3.0))
+            .await
+            .unwrap();
+        // A torn final line, as left by a crash mid-append, is ignored.
+        let mut active = OpenOptions::new()
+            .append(true)
+            .open(segment_path(&repo.segment_dir(&tenant), 1))
+            .unwrap();
+        active.write_all(b"{\"source_id\":").unwrap();
+
+        let rows = repo.query_all(&tenant, None).await.unwrap();
+        let first_index = read_index(&repo.segment_dir(&tenant), 0);
+        remove_store(&path);
+
+        assert_eq!(
+            rows.iter().map(|t| t.cpu).collect::<Vec<_>>(),
+            vec![Some(1.0), Some(2.0), Some(3.0)]
+        );
+        assert_eq!(first_index.map(|index| index.records), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_expire_deletes_only_fully_expired_sealed_segments() {
+        let path = temp_path();
+        let repo = JsonlTelemetryRepo::new(path.clone()).with_rotation(RotationPolicy {
+            max_bytes: 1,
+            hourly: false,
+        });
+        let tenant = TenantId::default();
+        let (kept_longer, other) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let now = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let days_ago = |days| now - chrono::Duration::days(days);
+
+        // Segments: 0 = other@40d, 1 = kept_longer@40d, 2 = other@1d, 3 = other@50d (active).
+        for (source, days) in [(other, 40), (kept_longer, 40), (other, 1), (other, 50)] {
+            repo.save(&tenant, telemetry_at(source, days_ago(days), days as f64))
+                .await
+                .unwrap();
+        }
+
+        let policy = RetentionPolicy::new(Some(chrono::Duration::days(30)))
+            .with_source(kept_longer, chrono::Duration::days(90));
+        let expired = repo.expire(&policy, now).await.unwrap();
+        let segments = list_segments(&repo.segment_dir(&tenant)).unwrap();
+        remove_store(&path);
+
+        assert_eq!(expired, 1);
+        assert_eq!(segments, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_unwritable_segment_directory() {
+        let path = temp_path();
+        assert!(JsonlTelemetryRepo::new(path.clone()).probe().await.is_ok());
+        remove_store(&path);
+
+        // A regular file where the segment directory should be cannot hold segments.
+        let mut segments = path.as_os_str().to_owned();
+        segments.push(".segments");
+        std::fs::write(&segments, b"").unwrap();
+        assert!(JsonlTelemetryRepo::new(path.clone()).probe().await.is_err());
+
+        std::fs::remove_file(segments).unwrap();
     }
 }
@@ -0,0 +1,933 @@
+//! Columnar Parquet output for offline analytics (requires the `parquet` feature).
+//!
+//! - [`ParquetTelemetryWriter`] encodes telemetry into one Parquet file, row group by row group.
+//! - [`export_range`] writes a time range read from any [`TelemetryRepository`].
+//! - [`ParquetArchiveRepo`] wraps a repository and rolls every stored record into hourly files
+//!   laid out as `<dir>/tenant=<id>/date=<YYYY-MM-DD>/hour=<HH>/part-<n>.parquet`.
+//!
+//! `extras` is written as a JSON string column; an [`ExtrasLayout::Flatten`] layout also lifts
+//! known keys into typed `extras_<key>` columns, leaving the other keys in the JSON column.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn demo(repo: &(dyn rustpulse::core::application::telemetry::TelemetryRepository + Send + Sync)) -> anyhow::Result<()> {
+//! use rustpulse::adapters::output::parquet_archive::{ExtrasLayout, export_range};
+//! use rustpulse::core::application::telemetry::TelemetryQuery;
+//! use rustpulse::core::domains::tenant::TenantId;
+//!
+//! let out = std::fs::File::create("telemetry.parquet")?;
+//! let layout: ExtrasLayout = "rpm:number,site:text".parse()?;
+//! let rows = export_range(repo, &TenantId::default(), TelemetryQuery::default(), out, &layout).await?;
+//! println!("exported {rows} records");
+//! # Ok(())
+//! # }
+//! ```
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, RecordBatch, StringArray,
+    TimestampMicrosecondArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, TelemetryPage, TelemetryQuery, TelemetryRepository,
+    TelemetryStream,
+};
+use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+
+/// Records fetched per page, and written per row group, by [`export_range`].
+pub const EXPORT_PAGE_SIZE: usize = 10_000;
+
+#[derive(thiserror::Error, Debug)]
+/// Errors produced while writing Parquet files.
+pub enum ParquetArchiveError {
+    /// Building the Arrow record batch failed.
+    #[error("arrow error")]
+    Arrow {
+        /// Underlying Arrow error.
+        source: ArrowError,
+    },
+
+    /// Encoding the Parquet file failed.
+    #[error("parquet error")]
+    Parquet {
+        /// Underlying Parquet error.
+        source: ParquetError,
+    },
+
+    /// Creating or renaming an archive file failed.
+    #[error("archive I/O error")]
+    Io {
+        /// Underlying OS error.
+        source: io::Error,
+    },
+
+    /// The extras layout specification could not be parsed.
+    #[error("invalid extras layout `{0}`: expected `json` or `<key>:<number|text|bool>,...`")]
+    InvalidLayout(String),
+}
+
+impl From<ArrowError> for ParquetArchiveError {
+    fn from(source: ArrowError) -> Self {
+        Self::Arrow { source }
+    }
+}
+
+impl From<ParquetError> for ParquetArchiveError {
+    fn from(source: ParquetError) -> Self {
+        Self::Parquet { source }
+    }
+}
+
+impl From<io::Error> for ParquetArchiveError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Column type of a flattened `extras` key.
+pub enum ExtrasKind {
+    /// `DOUBLE`; the key stays in the JSON column when its value is not a number.
+    Number,
+    /// `STRING`; non-string values are written as JSON text.
+    Text,
+    /// `BOOLEAN`; the key stays in the JSON column when its value is not a boolean.
+    Bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An `extras` key written as its own `extras_<key>` column.
+pub struct ExtrasColumn {
+    /// Key in the `extras` object.
+    pub key: String,
+    /// Column type.
+    pub kind: ExtrasKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// How `extras` is laid out in Parquet files.
+pub enum ExtrasLayout {
+    /// One `extras` JSON string column.
+    #[default]
+    Json,
+    /// Typed columns for these keys, plus the remaining keys in the `extras` JSON column.
+    Flatten(Vec<ExtrasColumn>),
+}
+
+impl FromStr for ExtrasLayout {
+    type Err = ParquetArchiveError;
+
+    /// Parses `json` or a comma-separated list of `<key>:<number|text|bool>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::output::parquet_archive::{ExtrasKind, ExtrasLayout};
+    ///
+    /// let ExtrasLayout::Flatten(columns) = "rpm:number, site:text".parse().unwrap() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(columns[0].kind, ExtrasKind::Number);
+    /// assert_eq!("json".parse::<ExtrasLayout>().unwrap(), ExtrasLayout::Json);
+    /// ```
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+        if spec.is_empty() || spec.eq_ignore_ascii_case("json") {
+            return Ok(Self::Json);
+        }
+        let invalid = || ParquetArchiveError::InvalidLayout(spec.to_string());
+        let mut columns = Vec::new();
+        for entry in spec.split(',').map(str::trim) {
+            let (key, kind) = entry.split_once(':').ok_or_else(invalid)?;
+            let key = key.trim();
+            if key.is_empty() || columns.iter().any(|c: &ExtrasColumn| c.key == key) {
+                return Err(invalid());
+            }
+            let kind = match kind.trim().to_ascii_lowercase().as_str() {
+                "number" => ExtrasKind::Number,
+                "text" => ExtrasKind::Text,
+                "bool" => ExtrasKind::Bool,
+                _ => return Err(invalid()),
+            };
+            columns.push(ExtrasColumn {
+                key: key.to_string(),
+                kind,
+            });
+        }
+        Ok(Self::Flatten(columns))
+    }
+}
+
+impl ExtrasLayout {
+    fn columns(&self) -> &[ExtrasColumn] {
+        match self {
+            Self::Json => &[],
+            Self::Flatten(columns) => columns,
+        }
+    }
+
+    /// Arrow schema of the files written with this layout.
+    pub fn schema(&self) -> SchemaRef {
+        let mut fields = vec![
+            Field::new("tenant_id", DataType::Utf8, false),
+            Field::new("source_id", DataType::Utf8, false),
+            Field::new("server_id", DataType::Utf8, false),
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("cpu", DataType::Float64, true),
+            Field::new("memory", DataType::Float64, true),
+            Field::new("temperature", DataType::Float32, true),
+        ];
+        for column in self.columns() {
+            let data_type = match column.kind {
+                ExtrasKind::Number => DataType::Float64,
+                ExtrasKind::Text => DataType::Utf8,
+                ExtrasKind::Bool => DataType::Boolean,
+            };
+            fields.push(Field::new(
+                format!("extras_{}", column.key),
+                data_type,
+                true,
+            ));
+        }
+        fields.push(Field::new("extras", DataType::Utf8, false));
+        Arc::new(Schema::new(fields))
+    }
+
+    fn record_batch(
+        &self,
+        schema: &SchemaRef,
+        tenant: &TenantId,
+        rows: &[Telemetry],
+    ) -> Result<RecordBatch, ArrowError> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(std::iter::repeat_n(
+                tenant.as_str(),
+                rows.len(),
+            ))),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|t| t.source_id.to_string()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|t| t.server_id.to_string()),
+            )),
+            Arc::new(
+                TimestampMicrosecondArray::from_iter_values(
+                    rows.iter().map(|t| t.timestamp.timestamp_micros()),
+                )
+                .with_timezone("UTC"),
+            ),
+            Arc::new(Float64Array::from_iter(rows.iter().map(|t| t.cpu))),
+            Arc::new(Float64Array::from_iter(rows.iter().map(|t| t.memory))),
+            Arc::new(Float32Array::from_iter(rows.iter().map(|t| t.temperature))),
+        ];
+
+        let mut remaining: Vec<Value> = rows.iter().map(|t| t.extras.clone()).collect();
+        for column in self.columns() {
+            let key = column.key.as_str();
+            let lifted: ArrayRef = match column.kind {
+                ExtrasKind::Number => Arc::new(Float64Array::from_iter(
+                    remaining
+                        .iter_mut()
+                        .map(|extras| take(extras, key, Value::as_f64)),
+                )),
+                ExtrasKind::Bool => Arc::new(BooleanArray::from_iter(
+                    remaining
+                        .iter_mut()
+                        .map(|extras| take(extras, key, Value::as_bool)),
+                )),
+                ExtrasKind::Text => {
+                    Arc::new(StringArray::from_iter(remaining.iter_mut().map(|extras| {
+                        take(extras, key, |value| match value {
+                            Value::String(text) => Some(text.clone()),
+                            other => Some(other.to_string()),
+                        })
+                    })))
+                }
+            };
+            columns.push(lifted);
+        }
+        columns.push(Arc::new(StringArray::from_iter_values(
+            remaining.iter().map(Value::to_string),
+        )));
+
+        RecordBatch::try_new(schema.clone(), columns)
+    }
+}
+
+/// Removes `key` from `extras` when `convert` accepts its value, returning the converted value.
+fn take<T>(extras: &mut Value, key: &str, convert: impl Fn(&Value) -> Option<T>) -> Option<T> {
+    let object = extras.as_object_mut()?;
+    let converted = convert(object.get(key)?)?;
+    object.remove(key);
+    Some(converted)
+}
+
+/// Encodes telemetry into a single Snappy-compressed Parquet file.
+pub struct ParquetTelemetryWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    layout: ExtrasLayout,
+    rows: u64,
+}
+
+impl<W: Write + Send> ParquetTelemetryWriter<W> {
+    /// Starts a file on `out` with the columns of `layout`.
+    pub fn new(out: W, layout: &ExtrasLayout) -> Result<Self, ParquetArchiveError> {
+        let schema = layout.schema();
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Ok(Self {
+            writer: ArrowWriter::try_new(out, schema.clone(), Some(props))?,
+            schema,
+            layout: layout.clone(),
+            rows: 0,
+        })
+    }
+
+    /// Appends `tenant`'s `rows`.
+    pub fn write(
+        &mut self,
+        tenant: &TenantId,
+        rows: &[Telemetry],
+    ) -> Result<(), ParquetArchiveError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let batch = self.layout.record_batch(&self.schema, tenant, rows)?;
+        self.writer.write(&batch)?;
+        self.rows += rows.len() as u64;
+        Ok(())
+    }
+
+    /// Ends the current row group, bounding the memory buffered by the writer.
+    pub fn flush(&mut self) -> Result<(), ParquetArchiveError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the footer and returns the number of records in the file.
+    pub fn finish(self) -> Result<u64, ParquetArchiveError> {
+        self.writer.close()?;
+        Ok(self.rows)
+    }
+}
+
+/// Writes `tenant`'s telemetry matching `query` to `out`, oldest first, and returns the record count.
+///
+/// Pages of [`EXPORT_PAGE_SIZE`] records are read from `repo` and written as row groups, so memory
+/// stays bounded however large the range is. `query.limit`, `cursor` and `order` are overridden.
+pub async fn export_range<W: Write + Send>(
+    repo: &(dyn TelemetryRepository + Send + Sync),
+    tenant: &TenantId,
+    query: TelemetryQuery,
+    out: W,
+    layout: &ExtrasLayout,
+) -> anyhow::Result<u64> {
+    let mut writer = ParquetTelemetryWriter::new(out, layout)?;
+    let mut query = TelemetryQuery {
+        limit: Some(EXPORT_PAGE_SIZE),
+        cursor: None,
+        order: Default::default(),
+        ..query
+    };
+    loop {
+        let page = repo.query_page(tenant, query.clone()).await?;
+        writer.write(tenant, &page.items)?;
+        writer.flush()?;
+        match page.next_cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => break,
+        }
+    }
+    let rows = writer.finish()?;
+    tracing::info!(rows, tenant = %tenant.as_str(), "export.parquet");
+    Ok(rows)
+}
+
+#[derive(Debug, Clone)]
+/// Tuning knobs for [`ParquetArchiveRepo`].
+pub struct ParquetArchiveConfig {
+    /// Column layout of the archive files.
+    pub layout: ExtrasLayout,
+    /// How long after an hour ends its records are still waited for before the file is written.
+    pub grace: TimeDelta,
+    /// Buffered records of one tenant-hour that trigger writing a part early.
+    pub max_buffered_rows: usize,
+    /// Buffered records across all tenant-hours that trigger writing the largest one early.
+    pub max_total_buffered_rows: usize,
+    /// How far past the current time a record may be dated and still be archived.
+    pub max_future: TimeDelta,
+    /// Time between checks for hours to roll.
+    pub roll_interval: Duration,
+}
+
+impl Default for ParquetArchiveConfig {
+    fn default() -> Self {
+        Self {
+            layout: ExtrasLayout::Json,
+            grace: TimeDelta::minutes(5),
+            max_buffered_rows: 100_000,
+            max_total_buffered_rows: 1_000_000,
+            max_future: TimeDelta::hours(1),
+            roll_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+type HourKey = (TenantId, DateTime<Utc>);
+
+/// Records waiting for their hour to be written.
+#[derive(Default)]
+struct Buffers {
+    hours: BTreeMap<HourKey, Vec<Telemetry>>,
+    rows: usize,
+}
+
+impl Buffers {
+    /// Buffers `telemetry`, returning how many records its hour now holds.
+    fn push(&mut self, key: &HourKey, telemetry: Telemetry) -> usize {
+        let rows = self.hours.entry(key.clone()).or_default();
+        rows.push(telemetry);
+        self.rows += 1;
+        rows.len()
+    }
+
+    fn put_back(&mut self, key: HourKey, rows: Vec<Telemetry>) {
+        self.rows += rows.len();
+        self.hours.entry(key).or_default().extend(rows);
+    }
+
+    fn take(&mut self, key: &HourKey) -> Option<(HourKey, Vec<Telemetry>)> {
+        let rows = self.hours.remove(key)?;
+        self.rows -= rows.len();
+        Some((key.clone(), rows))
+    }
+
+    fn take_largest(&mut self) -> Option<(HourKey, Vec<Telemetry>)> {
+        let key = self
+            .hours
+            .iter()
+            .max_by_key(|(_, rows)| rows.len())
+            .map(|(key, _)| key.clone())?;
+        self.take(&key)
+    }
+}
+
+/// Stores telemetry in an inner repository and archives it into hourly Parquet files.
+///
+/// Records are buffered per tenant and hour of their `timestamp`, and written once the hour
+/// (plus [`ParquetArchiveConfig::grace`]) has passed. Late records for an hour already written
+/// go into a new part of it. Buffers are bounded, and records dated too far in the future are
+/// not archived. Buffers are lost if the process dies without [`Self::flush_all`]; the inner
+/// repository stays the source of truth. Reads are served by the inner repository.
+pub struct ParquetArchiveRepo {
+    dir: PathBuf,
+    inner: Arc<dyn TelemetryRepository + Send + Sync>,
+    config: ParquetArchiveConfig,
+    buffers: Mutex<Buffers>,
+}
+
+/// Directory holding the parts of `tenant`'s `hour`.
+fn hour_dir(dir: &Path, tenant: &TenantId, hour: DateTime<Utc>) -> PathBuf {
+    dir.join(format!("tenant={}", tenant.as_str()))
+        .join(hour.format("date=%Y-%m-%d").to_string())
+        .join(hour.format("hour=%H").to_string())
+}
+
+/// Writes `rows` as the next `part-<n>.parquet` of `hour_dir`, atomically.
+///
+/// Safe to call concurrently for the same hour: each write gets its own part.
+fn write_part(
+    hour_dir: &Path,
+    tenant: &TenantId,
+    rows: &[Telemetry],
+    layout: &ExtrasLayout,
+) -> Result<PathBuf, ParquetArchiveError> {
+    fs::create_dir_all(hour_dir)?;
+    // Readers never see a half-written file: it only gets its final name once complete.
+    let tmp = hour_dir.join(format!(".part-{}.parquet.tmp", Uuid::new_v4()));
+    let result = write_tmp(&tmp, tenant, rows, layout).and_then(|()| claim_part(hour_dir, &tmp));
+    // Linked under its final name by now, or failed.
+    let _ = fs::remove_file(&tmp);
+    result
+}
+
+fn write_tmp(
+    tmp: &Path,
+    tenant: &TenantId,
+    rows: &[Telemetry],
+    layout: &ExtrasLayout,
+) -> Result<(), ParquetArchiveError> {
+    let mut writer = ParquetTelemetryWriter::new(fs::File::create(tmp)?, layout)?;
+    writer.write(tenant, rows)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Gives `tmp` the first free `part-<n>.parquet` name of `hour_dir`.
+fn claim_part(hour_dir: &Path, tmp: &Path) -> Result<PathBuf, ParquetArchiveError> {
+    for part in 0.. {
+        let path = hour_dir.join(format!("part-{part}.parquet"));
+        // Unlike a rename, a hard link never replaces a part another writer just claimed.
+        match fs::hard_link(tmp, &path) {
+            Ok(()) => return Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    unreachable!("part numbers are unbounded")
+}
+
+impl ParquetArchiveRepo {
+    /// Archives into `dir` everything stored through this repository into `inner`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # fn demo(inner: std::sync::Arc<dyn rustpulse::core::application::telemetry::TelemetryRepository + Send + Sync>) -> std::io::Result<()> {
+    /// use rustpulse::adapters::output::parquet_archive::{ParquetArchiveConfig, ParquetArchiveRepo};
+    /// use std::sync::Arc;
+    ///
+    /// let archive = Arc::new(ParquetArchiveRepo::open("/var/lib/rustpulse/archive", inner, ParquetArchiveConfig::default())?);
+    /// let _roller = archive.clone().spawn_roller();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        inner: Arc<dyn TelemetryRepository + Send + Sync>,
+        config: ParquetArchiveConfig,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            inner,
+            config,
+            buffers: Mutex::new(Buffers::default()),
+        })
+    }
+
+    /// Buffers `batch`, returning the tenant-hours to write now to stay within the limits.
+    fn buffer(
+        &self,
+        tenant: &TenantId,
+        batch: &[Telemetry],
+        now: DateTime<Utc>,
+    ) -> Vec<(HourKey, Vec<Telemetry>)> {
+        let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut full = Vec::new();
+        let mut skipped = 0_u64;
+        for telemetry in batch {
+            // Its hour would stay buffered until then.
+            if telemetry.timestamp > now + self.config.max_future {
+                skipped += 1;
+                continue;
+            }
+            let hour = telemetry
+                .timestamp
+                .duration_trunc(TimeDelta::hours(1))
+                .unwrap_or(telemetry.timestamp);
+            let key = (tenant.clone(), hour);
+            if buffers.push(&key, telemetry.clone()) >= self.config.max_buffered_rows {
+                full.extend(buffers.take(&key));
+            }
+            if buffers.rows >= self.config.max_total_buffered_rows.max(1) {
+                full.extend(buffers.take_largest());
+            }
+        }
+        if skipped > 0 {
+            tracing::warn!(
+                tenant = %tenant.as_str(),
+                records = skipped,
+                "archive.parquet.future_records_skipped"
+            );
+            metrics::counter!("rustpulse_parquet_skipped_records_total").increment(skipped);
+        }
+        full
+    }
+
+    /// Writes the given tenant-hours; the records of every hour that fails go back into the
+    /// buffers.
+    ///
+    /// A failed hour does not stop the others from being written; the first error is
+    /// returned once all were tried.
+    async fn write_hours(&self, hours: Vec<(HourKey, Vec<Telemetry>)>) -> anyhow::Result<usize> {
+        let mut written = 0;
+        let mut failures: Vec<anyhow::Error> = Vec::new();
+        for ((tenant, hour), rows) in hours {
+            let dir = hour_dir(&self.dir, &tenant, hour);
+            let layout = self.config.layout.clone();
+            let (tenant_for_write, rows_for_write) = (tenant.clone(), rows.clone());
+            let result = tokio::task::spawn_blocking(move || {
+                write_part(&dir, &tenant_for_write, &rows_for_write, &layout)
+            })
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result.map_err(anyhow::Error::from));
+            match result {
+                Ok(path) => {
+                    tracing::info!(path = %path.display(), rows = rows.len(), "archive.parquet.part");
+                    metrics::counter!("rustpulse_parquet_archived_records_total")
+                        .increment(rows.len() as u64);
+                    written += rows.len();
+                }
+                Err(e) => {
+                    let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+                    buffers.put_back((tenant, hour), rows);
+                    failures.push(e);
+                }
+            }
+        }
+        let failed = failures.len();
+        match failures.into_iter().next() {
+            Some(first) => Err(first.context(format!("{failed} archive hours not written"))),
+            None => Ok(written),
+        }
+    }
+
+    /// Writes every hour that ended at least the grace period before `now`; returns the records written.
+    pub async fn roll(&self, now: DateTime<Utc>) -> anyhow::Result<usize> {
+        let due = {
+            let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+            let keys: Vec<HourKey> = buffers
+                .hours
+                .keys()
+                .filter(|(_, hour)| *hour + TimeDelta::hours(1) + self.config.grace <= now)
+                .cloned()
+                .collect();
+            keys.iter().filter_map(|key| buffers.take(key)).collect()
+        };
+        self.write_hours(due).await
+    }
+
+    /// Writes every buffered record, whatever its hour (e.g. before shutting down).
+    pub async fn flush_all(&self) -> anyhow::Result<usize> {
+        let all = std::mem::take(&mut *self.buffers.lock().unwrap_or_else(|e| e.into_inner()));
+        self.write_hours(all.hours.into_iter().collect()).await
+    }
+
+    /// Rolls finished hours every [`ParquetArchiveConfig::roll_interval`] until the process exits.
+    pub fn spawn_roller(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.roll_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.roll(Utc::now()).await {
+                    tracing::warn!(error = %e, "parquet archive roll failed");
+                }
+            }
+        })
+    }
+
+    async fn archive(&self, tenant: &TenantId, batch: &[Telemetry]) {
+        let full = self.buffer(tenant, batch, Utc::now());
+        if !full.is_empty()
+            && let Err(e) = self.write_hours(full).await
+        {
+            // The records are back in the buffers; the roller retries them.
+            tracing::warn!(error = %e, "parquet archive write failed");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TelemetryRepository for ParquetArchiveRepo {
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        self.inner.save(tenant, telemetry.clone()).await?;
+        self.archive(tenant, std::slice::from_ref(&telemetry)).await;
+        Ok(())
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        self.inner.save_batch(tenant, batch.clone()).await?;
+        self.archive(tenant, &batch).await;
+        Ok(())
+    }
+
+    fn atomic_batches(&self) -> bool {
+        self.inner.atomic_batches()
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        self.inner.query_all(tenant, node_id).await
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        self.inner.query_page(tenant, query).await
+    }
+
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        self.inner.query_stream(tenant, query).await
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        self.inner.aggregate(tenant, query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::output::in_memory_telemetry_repo::InMemoryTelemetryRepo;
+    use arrow_array::Array;
+    use chrono::TimeZone;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use uuid::Uuid;
+
+    fn telemetry_at(timestamp: DateTime<Utc>, cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id: Uuid::from_u128(1),
+            server_id: Uuid::nil(),
+            timestamp,
+            cpu: Some(cpu),
+            memory: None,
+            temperature: Some(20.5),
+            extras: serde_json::json!({ "rpm": 1200, "site": "lab", "note": "ok" }),
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("rustpulse-parquet-{}", Uuid::new_v4()))
+    }
+
+    fn read_batches(path: &Path) -> Vec<RecordBatch> {
+        ParquetRecordBatchReaderBuilder::try_new(fs::File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_range_pages_through_repository_and_flattens_known_extras() {
+        let repo = InMemoryTelemetryRepo::default();
+        let tenant = TenantId::parse("acme").unwrap();
+        let base = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+        let batch = (0..5)
+            .map(|i| telemetry_at(base + TimeDelta::seconds(i), i as f64))
+            .collect();
+        repo.save_batch(&tenant, batch).await.unwrap();
+
+        let layout: ExtrasLayout = "rpm:number,site:text".parse().unwrap();
+        let query = TelemetryQuery {
+            from: Some(base + TimeDelta::seconds(1)),
+            ..Default::default()
+        };
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("export.parquet");
+        let out = fs::File::create(&path).unwrap();
+        let rows = export_range(&repo, &tenant, query, out, &layout)
+            .await
+            .unwrap();
+        let batches = read_batches(&path);
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(rows, 4);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 4);
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let rpm = column("extras_rpm");
+        let rpm = rpm.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(rpm.value(0), 1200.0);
+        let extras = column("extras");
+        let extras = extras.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(extras.value(0), r#"{"note":"ok"}"#);
+        let timestamps = column("timestamp");
+        let timestamps = timestamps
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(
+            timestamps.value(0),
+            (base + TimeDelta::seconds(1)).timestamp_micros()
+        );
+        assert!(column("memory").is_null(0));
+    }
+
+    #[tokio::test]
+    async fn test_archive_rolls_finished_hours_into_partitioned_parts() {
+        let dir = temp_dir();
+        let inner = InMemoryTelemetryRepo::default();
+        let archive = ParquetArchiveRepo::open(
+            &dir,
+            Arc::new(inner.clone()),
+            ParquetArchiveConfig::default(),
+        )
+        .unwrap();
+        let tenant = TenantId::default();
+        let ten = Utc.with_ymd_and_hms(2026, 4, 1, 10, 15, 0).unwrap();
+
+        archive
+            .save_batch(
+                &tenant,
+                vec![
+                    telemetry_at(ten, 1.0),
+                    telemetry_at(ten + TimeDelta::hours(1), 2.0),
+                ],
+            )
+            .await
+            .unwrap();
+        // 11:04 is still within the grace period of the 10:00 hour.
+        assert_eq!(archive.roll(ten + TimeDelta::minutes(49)).await.unwrap(), 0);
+        assert_eq!(archive.roll(ten + TimeDelta::minutes(50)).await.unwrap(), 1);
+        // A late record for the same hour becomes a second part.
+        archive.save(&tenant, telemetry_at(ten, 3.0)).await.unwrap();
+        assert_eq!(archive.flush_all().await.unwrap(), 2);
+
+        let ten_dir = dir.join("tenant=default/date=2026-04-01/hour=10");
+        let parts: Vec<_> = [0, 1]
+            .map(|part| read_batches(&ten_dir.join(format!("part-{part}.parquet")))[0].num_rows())
+            .to_vec();
+        let eleven = dir
+            .join("tenant=default/date=2026-04-01/hour=11/part-0.parquet")
+            .exists();
+        let stored = inner.query_all(&tenant, None).await.unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(parts, vec![1, 1]);
+        assert!(eleven);
+        assert_eq!(stored.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_failed_hour_is_kept_and_does_not_stop_the_others() {
+        let dir = temp_dir();
+        let archive = ParquetArchiveRepo::open(
+            &dir,
+            Arc::new(InMemoryTelemetryRepo::default()),
+            ParquetArchiveConfig::default(),
+        )
+        .unwrap();
+        let tenant = TenantId::default();
+        let ten = Utc.with_ymd_and_hms(2026, 4, 1, 10, 15, 0).unwrap();
+        let hours = [ten - TimeDelta::hours(1), ten, ten + TimeDelta::hours(1)];
+        for (cpu, at) in hours.iter().enumerate() {
+            archive
+                .save(&tenant, telemetry_at(*at, cpu as f64))
+                .await
+                .unwrap();
+        }
+        // A file where the 10:00 directory should be makes that hour's write fail.
+        let blocked = hour_dir(&dir, &tenant, ten);
+        fs::create_dir_all(blocked.parent().unwrap()).unwrap();
+        fs::write(&blocked, b"").unwrap();
+
+        assert!(archive.flush_all().await.is_err());
+        let parts = hours.map(|at| hour_dir(&dir, &tenant, at).join("part-0.parquet").exists());
+        let buffered = archive.buffers.lock().unwrap().rows;
+
+        fs::remove_file(&blocked).unwrap();
+        let retried = archive.flush_all().await.unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(parts, [true, false, true]);
+        assert_eq!(buffered, 1);
+        assert_eq!(retried, 1);
+    }
+
+    #[test]
+    fn test_concurrent_parts_of_one_hour_never_overwrite_each_other() {
+        let dir = temp_dir();
+        let tenant = TenantId::default();
+        let ten = Utc.with_ymd_and_hms(2026, 4, 1, 10, 0, 0).unwrap();
+        let hour = hour_dir(&dir, &tenant, ten);
+
+        std::thread::scope(|scope| {
+            for cpu in 0..8 {
+                let (hour, tenant) = (&hour, &tenant);
+                scope.spawn(move || {
+                    let rows = [telemetry_at(ten, f64::from(cpu))];
+                    write_part(hour, tenant, &rows, &ExtrasLayout::Json).unwrap();
+                });
+            }
+        });
+        let mut names: Vec<_> = fs::read_dir(&hour)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        fs::remove_dir_all(&dir).ok();
+
+        let expected: Vec<_> = (0..8).map(|n| format!("part-{n}.parquet")).collect();
+        assert_eq!(names, expected);
+    }
+
+    #[tokio::test]
+    async fn test_buffers_are_bounded_and_skip_far_future_records() {
+        let dir = temp_dir();
+        let config = ParquetArchiveConfig {
+            max_total_buffered_rows: 3,
+            ..ParquetArchiveConfig::default()
+        };
+        let archive =
+            ParquetArchiveRepo::open(&dir, Arc::new(InMemoryTelemetryRepo::default()), config)
+                .unwrap();
+        let tenant = TenantId::default();
+        let now = Utc::now();
+        let ten = Utc.with_ymd_and_hms(2026, 4, 1, 10, 15, 0).unwrap();
+
+        archive
+            .save_batch(
+                &tenant,
+                vec![
+                    telemetry_at(now + TimeDelta::days(365), 0.0),
+                    telemetry_at(ten, 1.0),
+                    telemetry_at(ten, 2.0),
+                    telemetry_at(ten + TimeDelta::hours(1), 3.0),
+                ],
+            )
+            .await
+            .unwrap();
+        let early = dir
+            .join("tenant=default/date=2026-04-01/hour=10/part-0.parquet")
+            .exists();
+        let remaining = archive.flush_all().await.unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        // The third buffered record wrote the largest hour early.
+        assert!(early);
+        assert_eq!(remaining, 1);
+    }
+}
@@ -0,0 +1,175 @@
+//! Postgres-backed API key repository.
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::core::application::api_keys::ApiKeyRepository;
+use crate::core::domains::api_key::{ApiKey, StoredApiKey};
+use crate::core::domains::tenant::TenantId;
+
+/// Stores API keys (salted hashes only) in the `api_keys` table.
+pub struct PostgresApiKeyRepo {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyRepo {
+    /// Creates a repository backed by the given connection pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn demo() -> anyhow::Result<()> {
+    /// use rustpulse::adapters::output::{postgres_db, postgres_api_key_repo::PostgresApiKeyRepo};
+    ///
+    /// let database_url = std::env::var("DATABASE_URL")?;
+    /// let pool = postgres_db::connect_pool(&database_url).await?;
+    /// let _repo = PostgresApiKeyRepo::new(pool);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_api_key(row: &PgRow) -> Result<ApiKey, sqlx::Error> {
+    Ok(ApiKey {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        prefix: row.try_get("prefix")?,
+        scopes: row.try_get("scopes")?,
+        server_id: row.try_get("server_id")?,
+        tenant_id: TenantId::parse(row.try_get("tenant_id")?).map_err(|e| {
+            sqlx::Error::ColumnDecode {
+                index: "tenant_id".to_string(),
+                source: e.into(),
+            }
+        })?,
+        created_at: row.try_get("created_at")?,
+        revoked_at: row.try_get("revoked_at")?,
+    })
+}
+
+#[async_trait::async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepo {
+    async fn insert(&self, key: StoredApiKey) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO api_keys (id, name, prefix, salt, secret_hash, scopes, server_id, tenant_id, created_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+"#,
+        )
+        .bind(key.key.id)
+        .bind(&key.key.name)
+        .bind(&key.key.prefix)
+        .bind(&key.salt)
+        .bind(&key.secret_hash)
+        .bind(&key.key.scopes)
+        .bind(key.key.server_id)
+        .bind(key.key.tenant_id.as_str())
+        .bind(key.key.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+        let rows = sqlx::query(
+            r#"
+SELECT id, name, prefix, scopes, server_id, tenant_id, created_at, revoked_at
+FROM api_keys
+ORDER BY created_at DESC
+"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_api_key).collect::<Result<_, _>>()?)
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<StoredApiKey>> {
+        let row = sqlx::query(
+            r#"
+SELECT id, name, prefix, salt, secret_hash, scopes, server_id, tenant_id, created_at, revoked_at
+FROM api_keys
+WHERE prefix = $1
+"#,
+        )
+        .bind(prefix)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(Some(StoredApiKey {
+            key: row_to_api_key(&row)?,
+            salt: row.try_get("salt")?,
+            secret_hash: row.try_get("secret_hash")?,
+        }))
+    }
+
+    async fn revoke(&self, id: Uuid, at: DateTime<Utc>) -> anyhow::Result<bool> {
+        let result =
+            sqlx::query("UPDATE api_keys SET revoked_at = $2 WHERE id = $1 AND revoked_at IS NULL")
+                .bind(id)
+                .bind(at)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::output::postgres_db;
+    use crate::infra::startup::init_postgres_schema;
+
+    #[tokio::test]
+    async fn test_postgres_api_key_repo_insert_find_and_revoke() {
+        let Ok(database_url) = std::env::var("DATABASE_URL") else {
+            return;
+        };
+
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        init_postgres_schema(&pool).await.unwrap();
+        let repo = PostgresApiKeyRepo::new(pool);
+
+        let prefix = format!("{:08x}", rand::random::<u32>());
+        let key = StoredApiKey {
+            key: ApiKey {
+                id: Uuid::new_v4(),
+                name: "agent".to_string(),
+                prefix: prefix.clone(),
+                scopes: vec!["telemetry:write".to_string()],
+                server_id: Some(Uuid::new_v4()),
+                tenant_id: TenantId::parse("acme").unwrap(),
+                created_at: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+                revoked_at: None,
+            },
+            salt: "00".to_string(),
+            secret_hash: "ff".to_string(),
+        };
+        repo.insert(key.clone()).await.unwrap();
+
+        assert_eq!(
+            repo.find_by_prefix(&prefix).await.unwrap(),
+            Some(key.clone())
+        );
+        assert!(
+            repo.list()
+                .await
+                .unwrap()
+                .iter()
+                .any(|k| k.id == key.key.id)
+        );
+
+        assert!(repo.revoke(key.key.id, Utc::now()).await.unwrap());
+        assert!(!repo.revoke(key.key.id, Utc::now()).await.unwrap());
+        let revoked = repo.find_by_prefix(&prefix).await.unwrap().unwrap();
+        assert!(!revoked.key.is_active());
+    }
+}
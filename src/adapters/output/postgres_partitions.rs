@@ -0,0 +1,527 @@
+//! Partition management for the range-partitioned Postgres `telemetry` table.
+//!
+//! `migrations/0004_partition_telemetry.sql` partitions the table on `timestamp`. The
+//! [`PartitionManager`] keeps partitions `telemetry_pYYYYMMDD_YYYYMMDD` (start inclusive,
+//! end exclusive, UTC) created a few periods ahead, moving any rows that already landed in
+//! `telemetry_default` into them, and detaches partitions once they are old enough.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use rustpulse::adapters::output::postgres_db;
+//! use rustpulse::adapters::output::postgres_partitions::{PartitionInterval, PartitionManager};
+//! use std::sync::Arc;
+//!
+//! let pool = postgres_db::connect_pool(&std::env::var("DATABASE_URL")?).await?;
+//! let manager = Arc::new(PartitionManager::new(pool, PartitionInterval::Daily));
+//! manager.run_once(chrono::Utc::now()).await?;
+//! let _task = manager.spawn();
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Periods created ahead of the current one by default.
+pub const DEFAULT_PREMAKE: u32 = 7;
+
+/// Time between two partition maintenance runs.
+pub const PARTITION_MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const PARTITION_PREFIX: &str = "telemetry_p";
+const DATE_FORMAT: &str = "%Y%m%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Time span covered by each partition.
+pub enum PartitionInterval {
+    /// One partition per UTC day.
+    #[default]
+    Daily,
+    /// One partition per ISO week, starting Monday 00:00 UTC.
+    Weekly,
+}
+
+impl FromStr for PartitionInterval {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "daily" | "day" => Ok(Self::Daily),
+            "weekly" | "week" => Ok(Self::Weekly),
+            other => Err(format!("unknown partition interval `{other}`")),
+        }
+    }
+}
+
+impl PartitionInterval {
+    fn days(self) -> u64 {
+        match self {
+            Self::Daily => 1,
+            Self::Weekly => 7,
+        }
+    }
+
+    /// First day of the period containing `day`.
+    fn period_start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Daily => day,
+            Self::Weekly => day.week(chrono::Weekday::Mon).first_day(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A partition created by the [`PartitionManager`], identified by its name.
+pub struct ManagedPartition {
+    /// Table name, `telemetry_pYYYYMMDD_YYYYMMDD`.
+    pub name: String,
+    /// First day covered (inclusive, UTC).
+    pub start: NaiveDate,
+    /// Day after the last one covered (exclusive, UTC).
+    pub end: NaiveDate,
+}
+
+impl ManagedPartition {
+    fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            name: format!(
+                "{PARTITION_PREFIX}{}_{}",
+                start.format(DATE_FORMAT),
+                end.format(DATE_FORMAT)
+            ),
+            start,
+            end,
+        }
+    }
+
+    /// Parses a partition name; `None` for tables the manager did not create.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::adapters::output::postgres_partitions::ManagedPartition;
+    ///
+    /// let partition = ManagedPartition::parse("telemetry_p20260420_20260427").unwrap();
+    /// assert_eq!(partition.start.to_string(), "2026-04-20");
+    /// assert!(ManagedPartition::parse("telemetry_legacy").is_none());
+    /// ```
+    pub fn parse(name: &str) -> Option<Self> {
+        let (start, end) = name.strip_prefix(PARTITION_PREFIX)?.split_once('_')?;
+        let start = NaiveDate::parse_from_str(start, DATE_FORMAT).ok()?;
+        let end = NaiveDate::parse_from_str(end, DATE_FORMAT).ok()?;
+        (start < end).then(|| Self::new(start, end))
+    }
+
+    /// Instant at which the partition's range ends.
+    pub fn end_instant(&self) -> DateTime<Utc> {
+        self.end.and_time(chrono::NaiveTime::MIN).and_utc()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+/// What a [`PartitionManager::run_once`] changed.
+pub struct PartitionReport {
+    /// Partitions created.
+    pub created: Vec<String>,
+    /// Partitions detached from `telemetry`.
+    pub detached: Vec<String>,
+}
+
+/// Creates upcoming `telemetry` partitions and detaches old ones.
+pub struct PartitionManager {
+    pool: PgPool,
+    interval: PartitionInterval,
+    premake: u32,
+    detach_after: Option<TimeDelta>,
+}
+
+/// Lists the partitions of `telemetry` created by the manager, oldest first.
+pub(crate) async fn managed_partitions(
+    pool: &PgPool,
+) -> Result<Vec<ManagedPartition>, sqlx::Error> {
+    let names: Vec<String> = sqlx::query_scalar(
+        r#"
+SELECT c.relname::text
+FROM pg_inherits i
+JOIN pg_class c ON c.oid = i.inhrelid
+WHERE i.inhparent = 'telemetry'::regclass
+"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(parse_partitions(&names))
+}
+
+/// Lists the partitions the manager detached from `telemetry` that still exist, oldest first.
+pub(crate) async fn detached_partitions(
+    pool: &PgPool,
+) -> Result<Vec<ManagedPartition>, sqlx::Error> {
+    let names: Vec<String> = sqlx::query_scalar(
+        r#"
+SELECT c.relname::text
+FROM pg_class c
+WHERE c.relkind = 'r'
+  AND NOT c.relispartition
+  AND c.relname LIKE 'telemetry\_p%'
+  AND c.relnamespace = (SELECT relnamespace FROM pg_class WHERE oid = 'telemetry'::regclass)
+"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(parse_partitions(&names))
+}
+
+fn parse_partitions(names: &[String]) -> Vec<ManagedPartition> {
+    let mut partitions: Vec<ManagedPartition> = names
+        .iter()
+        .filter_map(|name| ManagedPartition::parse(name))
+        .collect();
+    partitions.sort_by_key(|p| p.start);
+    partitions
+}
+
+/// SQLSTATE raised when a new partition would overlap an existing one.
+const OVERLAPPING_PARTITION: &str = "42P17";
+
+impl PartitionManager {
+    /// Creates a manager making `interval` partitions [`DEFAULT_PREMAKE`] periods ahead, never detaching.
+    pub fn new(pool: PgPool, interval: PartitionInterval) -> Self {
+        Self {
+            pool,
+            interval,
+            premake: DEFAULT_PREMAKE,
+            detach_after: None,
+        }
+    }
+
+    /// Overrides how many periods after the current one are created ahead of time.
+    pub fn with_premake(mut self, periods: u32) -> Self {
+        self.premake = periods;
+        self
+    }
+
+    /// Detaches partitions whose whole range is older than `age`.
+    ///
+    /// Detached partitions stay in the database as plain tables, for archiving, until retention
+    /// drops them like attached ones.
+    pub fn with_detach_after(mut self, age: TimeDelta) -> Self {
+        self.detach_after = Some(age);
+        self
+    }
+
+    /// Creates missing partitions from the period containing `now` onwards and detaches old ones.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<PartitionReport, sqlx::Error> {
+        let mut report = PartitionReport::default();
+        let existing = managed_partitions(&self.pool).await?;
+
+        let mut start = self.interval.period_start(now.date_naive());
+        for _ in 0..=self.premake {
+            let end = start + chrono::Days::new(self.interval.days());
+            if !existing.iter().any(|p| p.start == start && p.end == end) {
+                report.created.extend(
+                    self.create_covering(ManagedPartition::new(start, end))
+                        .await?,
+                );
+            }
+            start = end;
+        }
+
+        if let Some(age) = self.detach_after {
+            let horizon = now - age;
+            for partition in existing.iter().filter(|p| p.end_instant() <= horizon) {
+                sqlx::query(&format!(
+                    "ALTER TABLE telemetry DETACH PARTITION {}",
+                    partition.name
+                ))
+                .execute(&self.pool)
+                .await?;
+                report.detached.push(partition.name.clone());
+            }
+        }
+
+        tracing::info!(
+            created = report.created.len(),
+            detached = report.detached.len(),
+            "repo.telemetry.partitions"
+        );
+        Ok(report)
+    }
+
+    /// Creates `partition`, or one daily partition per day of it when it overlaps existing ones
+    /// (e.g. the legacy partition, or daily partitions made before switching to weekly).
+    async fn create_covering(
+        &self,
+        partition: ManagedPartition,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        if self.create(&partition).await? {
+            return Ok(vec![partition.name]);
+        }
+
+        let mut created = Vec::new();
+        if partition.end - partition.start > TimeDelta::days(1) {
+            for day in partition
+                .start
+                .iter_days()
+                .take_while(|day| *day < partition.end)
+            {
+                let daily = ManagedPartition::new(day, day + chrono::Days::new(1));
+                if self.create(&daily).await? {
+                    created.push(daily.name);
+                }
+            }
+        }
+        Ok(created)
+    }
+
+    /// Creates and attaches `partition`, moving its rows out of `telemetry_default` first.
+    ///
+    /// Returns `false` when it would overlap an existing partition.
+    async fn create(&self, partition: &ManagedPartition) -> Result<bool, sqlx::Error> {
+        let start = partition.start.and_time(chrono::NaiveTime::MIN).and_utc();
+        let end = partition.end_instant();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!(
+            "CREATE TABLE {} (LIKE telemetry INCLUDING DEFAULTS INCLUDING CONSTRAINTS)",
+            partition.name
+        ))
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(&format!(
+            r#"
+WITH moved AS (
+    DELETE FROM telemetry_default WHERE timestamp >= $1 AND timestamp < $2 RETURNING *
+)
+INSERT INTO {} SELECT * FROM moved
+"#,
+            partition.name
+        ))
+        .bind(start)
+        .bind(end)
+        .execute(&mut *tx)
+        .await?;
+        let attached = sqlx::query(&format!(
+            "ALTER TABLE telemetry ATTACH PARTITION {} FOR VALUES FROM ('{}') TO ('{}')",
+            partition.name,
+            start.to_rfc3339(),
+            end.to_rfc3339()
+        ))
+        .execute(&mut *tx)
+        .await;
+
+        match attached {
+            Ok(_) => {
+                tx.commit().await?;
+                Ok(true)
+            }
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(OVERLAPPING_PARTITION) => {
+                tx.rollback().await?;
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Runs [`Self::run_once`] every [`PARTITION_MAINTENANCE_INTERVAL`] until the process exits.
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(PARTITION_MAINTENANCE_INTERVAL);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    tracing::warn!(error = %e, "partition maintenance failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::output::postgres_db;
+    use crate::adapters::output::postgres_telemetry_repo::PostgresTelemetryRepo;
+    use crate::adapters::output::postgres_telemetry_repo::tests::lock;
+    use crate::core::application::retention::{ExpiringStore, RetentionPolicy};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_weekly_periods_start_on_monday() {
+        let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert_eq!(
+            PartitionInterval::Weekly.period_start(sunday),
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+        );
+        assert_eq!(PartitionInterval::Daily.period_start(sunday), sunday);
+        assert_eq!(
+            "Weekly".parse::<PartitionInterval>(),
+            Ok(PartitionInterval::Weekly)
+        );
+    }
+
+    async fn drop_managed_partitions(pool: &PgPool) {
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT tablename::text FROM pg_tables WHERE tablename LIKE 'telemetry\\_p%'",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        for name in names {
+            sqlx::query(&format!("DROP TABLE {name}"))
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn fresh_schema(database_url: &str) -> PgPool {
+        let pool = postgres_db::connect_pool(database_url).await.unwrap();
+        crate::infra::startup::init_postgres_schema(&pool)
+            .await
+            .unwrap();
+        drop_managed_partitions(&pool).await;
+        sqlx::query("TRUNCATE TABLE telemetry")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn insert_at(pool: &PgPool, timestamp: DateTime<Utc>) {
+        sqlx::query(
+            "INSERT INTO telemetry (source_id, server_id, timestamp, extras) VALUES ($1, $1, $2, '{}')",
+        )
+        .bind(uuid::Uuid::nil())
+        .bind(timestamp)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_manager_creates_ahead_moves_default_rows_and_detaches() {
+        let Some(database_url) = std::env::var("DATABASE_URL").ok() else {
+            return;
+        };
+        let _guard = lock().await;
+        let pool = fresh_schema(&database_url).await;
+
+        // Far past the legacy partition, so this row lands in the default partition.
+        let now = Utc.with_ymd_and_hms(2031, 1, 2, 12, 0, 0).unwrap();
+        insert_at(&pool, now).await;
+
+        let manager = PartitionManager::new(pool.clone(), PartitionInterval::Daily)
+            .with_premake(2)
+            .with_detach_after(TimeDelta::days(2));
+        let report = manager.run_once(now).await.unwrap();
+        assert_eq!(
+            report.created,
+            vec![
+                "telemetry_p20310102_20310103",
+                "telemetry_p20310103_20310104",
+                "telemetry_p20310104_20310105",
+            ]
+        );
+        let home: String =
+            sqlx::query_scalar("SELECT tableoid::regclass::text FROM telemetry LIMIT 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(home, "telemetry_p20310102_20310103");
+
+        // A second run is a no-op; three days later the first partition is detached.
+        assert_eq!(
+            manager.run_once(now).await.unwrap(),
+            PartitionReport::default()
+        );
+        let later = manager.run_once(now + TimeDelta::days(3)).await.unwrap();
+        assert_eq!(later.detached, vec!["telemetry_p20310102_20310103"]);
+        assert_eq!(later.created.len(), 3);
+        assert_eq!(
+            managed_partitions(&pool).await.unwrap().len(),
+            later.created.len() + 2
+        );
+
+        drop_managed_partitions(&pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_retention_drops_partitions_older_than_every_retention() {
+        let Some(database_url) = std::env::var("DATABASE_URL").ok() else {
+            return;
+        };
+        let _guard = lock().await;
+        let pool = fresh_schema(&database_url).await;
+
+        let day = Utc.with_ymd_and_hms(2032, 3, 1, 12, 0, 0).unwrap();
+        PartitionManager::new(pool.clone(), PartitionInterval::Daily)
+            .with_premake(1)
+            .run_once(day)
+            .await
+            .unwrap();
+        insert_at(&pool, day).await;
+        insert_at(&pool, day + TimeDelta::days(1)).await;
+        // Dropped partitions are counted from planner statistics.
+        sqlx::query("ANALYZE telemetry")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Only the first day is entirely older than the longest retention.
+        let policy = RetentionPolicy::new(Some(TimeDelta::days(1)))
+            .with_source(uuid::Uuid::from_u128(9), TimeDelta::days(2));
+        let repo = PostgresTelemetryRepo::new(pool.clone());
+        let now = Utc.with_ymd_and_hms(2032, 3, 4, 0, 0, 0).unwrap();
+        assert_eq!(repo.expire(&policy, now).await.unwrap(), 2);
+
+        let remaining: Vec<String> = managed_partitions(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(remaining, vec!["telemetry_p20320302_20320303"]);
+
+        drop_managed_partitions(&pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_retention_drops_detached_partitions() {
+        let Some(database_url) = std::env::var("DATABASE_URL").ok() else {
+            return;
+        };
+        let _guard = lock().await;
+        let pool = fresh_schema(&database_url).await;
+
+        let day = Utc.with_ymd_and_hms(2033, 5, 1, 12, 0, 0).unwrap();
+        let manager = PartitionManager::new(pool.clone(), PartitionInterval::Daily)
+            .with_premake(1)
+            .with_detach_after(TimeDelta::days(1));
+        manager.run_once(day).await.unwrap();
+        insert_at(&pool, day).await;
+        insert_at(&pool, day).await;
+        sqlx::query("ANALYZE telemetry")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let later = day + TimeDelta::days(2);
+        let report = manager.run_once(later).await.unwrap();
+        assert_eq!(report.detached, vec!["telemetry_p20330501_20330502"]);
+        assert_eq!(detached_partitions(&pool).await.unwrap().len(), 1);
+
+        let repo = PostgresTelemetryRepo::new(pool.clone());
+        let policy = RetentionPolicy::new(Some(TimeDelta::days(1)));
+        assert_eq!(repo.expire(&policy, later).await.unwrap(), 2);
+        assert!(detached_partitions(&pool).await.unwrap().is_empty());
+
+        drop_managed_partitions(&pool).await;
+    }
+}
@@ -1,13 +1,29 @@
 //! Postgres-backed telemetry repository.
+//!
+//! Every statement filters on `tenant_id`; optionally the tenant is also exposed to
+//! Postgres row-level security through the `rustpulse.tenant_id` setting.
 
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use sqlx::PgPool;
-use sqlx::Row;
+use futures_util::TryStreamExt;
+
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Row, Transaction};
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
-use crate::core::application::telemetry::TelemetryRepository;
+use crate::adapters::output::{postgres_db, postgres_partitions};
+use crate::core::application::health::DependencyProbe;
+use crate::core::application::retention::{ExpiringStore, ExpiryRule, RetentionPolicy};
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, MetricStats, SortOrder, TelemetryCursor, TelemetryPage,
+    TelemetryQuery, TelemetryRepository, TelemetryStream,
+};
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
 
 #[derive(thiserror::Error, Debug)]
 /// Errors produced by the Postgres telemetry repository.
@@ -27,9 +43,12 @@ pub enum PostgresRepoError {
     },
 }
 
-/// Stores and queries telemetry rows from Postgres.
+/// Stores and queries telemetry rows from Postgres, scoped by `tenant_id`.
 pub struct PostgresTelemetryRepo {
     pool: PgPool,
+    row_level_security: bool,
+    /// Limits how many pooled connections streaming queries can hold at once.
+    streams: Arc<Semaphore>,
 }
 
 impl PostgresTelemetryRepo {
@@ -48,28 +67,257 @@ impl PostgresTelemetryRepo {
     /// # }
     /// ```
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            row_level_security: false,
+            streams: Arc::new(Semaphore::new(MAX_CONCURRENT_STREAMS)),
+        }
+    }
+
+    /// Runs every statement in a transaction that sets `rustpulse.tenant_id`, so the
+    /// `telemetry_tenant_isolation` policy can be enforced by Postgres as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn demo() -> anyhow::Result<()> {
+    /// use rustpulse::adapters::output::{postgres_db, postgres_telemetry_repo::PostgresTelemetryRepo};
+    ///
+    /// let database_url = std::env::var("DATABASE_URL")?;
+    /// let pool = postgres_db::connect_pool(&database_url).await?;
+    /// let _repo = PostgresTelemetryRepo::new(pool).with_row_level_security(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_row_level_security(mut self, enabled: bool) -> Self {
+        self.row_level_security = enabled;
+        self
+    }
+
+    /// Acquires a connection scoped to `tenant`.
+    ///
+    /// Only with row-level security does this open a transaction (to set the tenant);
+    /// otherwise statements run on a plain pooled connection, without BEGIN/COMMIT.
+    async fn acquire(&self, tenant: &TenantId) -> Result<TenantConnection, sqlx::Error> {
+        if !self.row_level_security {
+            return Ok(TenantConnection::Plain(self.pool.acquire().await?));
+        }
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT set_config('rustpulse.tenant_id', $1, true)")
+            .bind(tenant.as_str())
+            .execute(&mut *tx)
+            .await?;
+        Ok(TenantConnection::Scoped(tx))
+    }
+
+    /// Opens the transaction a streaming query runs in.
+    ///
+    /// Its settings are local to the transaction, so the connection goes back to the pool
+    /// unchanged; Postgres cancels the query once it has run for [`STREAM_STATEMENT_TIMEOUT`].
+    async fn begin_stream(
+        &self,
+        tenant: &TenantId,
+    ) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let timeout_ms = STREAM_STATEMENT_TIMEOUT.as_millis().to_string();
+        sqlx::query(
+            "SELECT set_config('statement_timeout', $1, true), \
+             set_config('idle_in_transaction_session_timeout', $1, true)",
+        )
+        .bind(&timeout_ms)
+        .execute(&mut *tx)
+        .await?;
+        if self.row_level_security {
+            sqlx::query("SELECT set_config('rustpulse.tenant_id', $1, true)")
+                .bind(tenant.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+        Ok(tx)
+    }
+
+    async fn fetch_rows(
+        &self,
+        tenant: &TenantId,
+        mut qb: QueryBuilder<'_, Postgres>,
+    ) -> Result<Vec<PgRow>, sqlx::Error> {
+        let mut conn = self.acquire(tenant).await?;
+        let rows = qb.build().fetch_all(&mut *conn).await?;
+        conn.finish().await?;
+        Ok(rows)
     }
 }
 
+/// Connection returned by [`PostgresTelemetryRepo::acquire`].
+enum TenantConnection {
+    /// Row-level security is off: no tenant setting, no transaction.
+    Plain(PoolConnection<Postgres>),
+    /// Transaction with `rustpulse.tenant_id` set for its duration.
+    Scoped(Transaction<'static, Postgres>),
+}
+
+impl TenantConnection {
+    /// Commits the transaction, if there is one.
+    async fn finish(self) -> Result<(), sqlx::Error> {
+        match self {
+            Self::Plain(_) => Ok(()),
+            Self::Scoped(tx) => tx.commit().await,
+        }
+    }
+}
+
+impl std::ops::Deref for TenantConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            Self::Plain(conn) => conn,
+            Self::Scoped(tx) => tx,
+        }
+    }
+}
+
+impl std::ops::DerefMut for TenantConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            Self::Plain(conn) => conn,
+            Self::Scoped(tx) => tx,
+        }
+    }
+}
+
+fn row_to_telemetry(row: &PgRow) -> Result<Telemetry, sqlx::Error> {
+    Ok(Telemetry {
+        source_id: row.try_get("source_id")?,
+        server_id: row.try_get("server_id")?,
+        timestamp: row.try_get("timestamp")?,
+        cpu: row.try_get("cpu")?,
+        memory: row.try_get("memory")?,
+        temperature: row.try_get("temperature")?,
+        extras: row.try_get("extras")?,
+    })
+}
+
+fn row_to_stats(row: &PgRow, metric: &str) -> Result<Option<MetricStats>, sqlx::Error> {
+    let count: i64 = row.try_get(format!("{metric}_count").as_str())?;
+    if count == 0 {
+        return Ok(None);
+    }
+
+    let pct: Vec<f64> = row.try_get(format!("{metric}_pct").as_str())?;
+    let [p50, p95, p99] = pct[..] else {
+        return Err(sqlx::Error::ColumnDecode {
+            index: format!("{metric}_pct"),
+            source: "expected three percentiles".into(),
+        });
+    };
+
+    Ok(Some(MetricStats {
+        count: count as u64,
+        min: row.try_get(format!("{metric}_min").as_str())?,
+        max: row.try_get(format!("{metric}_max").as_str())?,
+        avg: row.try_get(format!("{metric}_avg").as_str())?,
+        p50,
+        p95,
+        p99,
+    }))
+}
+
+fn push_metric_aggregates(qb: &mut QueryBuilder<Postgres>, metric: &str) {
+    qb.push(format_args!(
+        ", COUNT({metric}) AS {metric}_count\
+         , MIN({metric}) AS {metric}_min\
+         , MAX({metric}) AS {metric}_max\
+         , AVG({metric}) AS {metric}_avg\
+         , percentile_cont(ARRAY[0.5, 0.95, 0.99]) WITHIN GROUP (ORDER BY {metric}) AS {metric}_pct"
+    ));
+}
+
+/// `SELECT` of `tenant`'s rows passing `query`'s filters and cursor, in `query.order` (no `LIMIT`).
+fn select_matching<'a>(
+    tenant: &'a TenantId,
+    source: Option<Uuid>,
+    query: &TelemetryQuery,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+        "SELECT id, source_id, server_id, timestamp, cpu, memory, temperature, extras FROM telemetry WHERE tenant_id = ",
+    );
+    qb.push_bind(tenant.as_str());
+    if let Some(source_id) = source {
+        qb.push(" AND source_id = ").push_bind(source_id);
+    }
+    if let Some(from) = query.from {
+        qb.push(" AND timestamp >= ").push_bind(from);
+    }
+    if let Some(to) = query.to {
+        qb.push(" AND timestamp < ").push_bind(to);
+    }
+    if let Some(cursor) = query.cursor {
+        let op = match query.order {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        };
+        qb.push(format_args!(" AND (timestamp, source_id, id) {op} ("))
+            .push_bind(cursor.timestamp)
+            .push(", ")
+            .push_bind(cursor.source_id)
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(")");
+    }
+    let order = query.order.as_sql();
+    qb.push(format_args!(
+        " ORDER BY timestamp {order}, source_id {order}, id {order}"
+    ));
+    qb
+}
+
+/// Rows a streaming query reads ahead of its consumer.
+const STREAM_BUFFER: usize = 256;
+
+/// Streaming queries allowed at once, so exports never hold more than this many of the
+/// pool's connections and ingest keeps the rest.
+pub const MAX_CONCURRENT_STREAMS: usize = 2;
+
+/// How long a streaming query may run, including time spent waiting on a slow consumer.
+pub const STREAM_STATEMENT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Records how long a repository operation took in `rustpulse_repo_duration_seconds`.
+fn record_latency(operation: &'static str, outcome: &'static str, start: Instant) {
+    metrics::histogram!(
+        "rustpulse_repo_duration_seconds",
+        "backend" => "postgres",
+        "operation" => operation,
+        "outcome" => outcome,
+    )
+    .record(start.elapsed().as_secs_f64());
+}
+
 #[async_trait::async_trait]
 impl TelemetryRepository for PostgresTelemetryRepo {
-    async fn save(&self, telemetry: Telemetry) -> anyhow::Result<()> {
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
         let start = Instant::now();
-        let res = sqlx::query(
-            r#"
-INSERT INTO telemetry (source_id, server_id, timestamp, cpu, memory, temperature, extras)
-VALUES ($1, $2, $3, $4, $5, $6, $7)
+        let res = async {
+            let mut conn = self.acquire(tenant).await?;
+            let done = sqlx::query(
+                r#"
+INSERT INTO telemetry (tenant_id, source_id, server_id, timestamp, cpu, memory, temperature, extras)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
 "#,
-        )
-        .bind(telemetry.source_id)
-        .bind(telemetry.server_id)
-        .bind(telemetry.timestamp)
-        .bind(telemetry.cpu)
-        .bind(telemetry.memory)
-        .bind(telemetry.temperature)
-        .bind(telemetry.extras)
-        .execute(&self.pool)
+            )
+            .bind(tenant.as_str())
+            .bind(telemetry.source_id)
+            .bind(telemetry.server_id)
+            .bind(telemetry.timestamp)
+            .bind(telemetry.cpu)
+            .bind(telemetry.memory)
+            .bind(telemetry.temperature)
+            .bind(telemetry.extras)
+            .execute(&mut *conn)
+            .await?;
+            conn.finish().await?;
+            Ok::<_, sqlx::Error>(done)
+        }
         .await;
 
         match res {
@@ -79,6 +327,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
                     row_count = done.rows_affected(),
                     "repo.telemetry.save"
                 );
+                record_latency("save", "ok", start);
                 Ok(())
             }
             Err(e) => {
@@ -87,12 +336,90 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
                     error = %e,
                     "repo.telemetry.save"
                 );
+                record_latency("save", "error", start);
+                Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }))
+            }
+        }
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let batch_size = batch.len();
+
+        let mut source_ids = Vec::with_capacity(batch_size);
+        let mut server_ids = Vec::with_capacity(batch_size);
+        let mut timestamps = Vec::with_capacity(batch_size);
+        let mut cpus = Vec::with_capacity(batch_size);
+        let mut memories = Vec::with_capacity(batch_size);
+        let mut temperatures = Vec::with_capacity(batch_size);
+        let mut extras = Vec::with_capacity(batch_size);
+        for telemetry in batch {
+            source_ids.push(telemetry.source_id);
+            server_ids.push(telemetry.server_id);
+            timestamps.push(telemetry.timestamp);
+            cpus.push(telemetry.cpu);
+            memories.push(telemetry.memory);
+            temperatures.push(telemetry.temperature);
+            extras.push(telemetry.extras);
+        }
+
+        // One round-trip regardless of batch size: each column travels as an array.
+        let res = async {
+            let mut conn = self.acquire(tenant).await?;
+            let done = sqlx::query(
+                r#"
+INSERT INTO telemetry (tenant_id, source_id, server_id, timestamp, cpu, memory, temperature, extras)
+SELECT $1, * FROM UNNEST($2::uuid[], $3::uuid[], $4::timestamptz[], $5::float8[], $6::float8[], $7::real[], $8::jsonb[])
+"#,
+            )
+            .bind(tenant.as_str())
+            .bind(source_ids)
+            .bind(server_ids)
+            .bind(timestamps)
+            .bind(cpus)
+            .bind(memories)
+            .bind(temperatures)
+            .bind(extras)
+            .execute(&mut *conn)
+            .await?;
+            conn.finish().await?;
+            Ok::<_, sqlx::Error>(done)
+        }
+        .await;
+
+        match res {
+            Ok(done) => {
+                tracing::info!(
+                    elapsed_ms = start.elapsed().as_millis(),
+                    batch_size,
+                    row_count = done.rows_affected(),
+                    "repo.telemetry.save_batch"
+                );
+                record_latency("save_batch", "ok", start);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::info!(
+                    elapsed_ms = start.elapsed().as_millis(),
+                    batch_size,
+                    error = %e,
+                    "repo.telemetry.save_batch"
+                );
+                record_latency("save_batch", "error", start);
                 Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }))
             }
         }
     }
 
-    async fn query_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+    fn atomic_batches(&self) -> bool {
+        true
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
         let start = Instant::now();
 
         let filter: Option<Uuid> = match node_id {
@@ -102,47 +429,20 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
             None => None,
         };
 
-        let rows = match filter {
-            None => {
-                sqlx::query(
-                    r#"
-SELECT source_id, server_id, timestamp, cpu, memory, temperature, extras
-FROM telemetry
-ORDER BY timestamp ASC
-"#,
-                )
-                .fetch_all(&self.pool)
-                .await
-            }
-            Some(source_id) => {
-                sqlx::query(
-                    r#"
-SELECT source_id, server_id, timestamp, cpu, memory, temperature, extras
-FROM telemetry
-WHERE source_id = $1
-ORDER BY timestamp ASC
-"#,
-                )
-                .bind(source_id)
-                .fetch_all(&self.pool)
-                .await
-            }
-        };
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT source_id, server_id, timestamp, cpu, memory, temperature, extras FROM telemetry WHERE tenant_id = ",
+        );
+        qb.push_bind(tenant.as_str());
+        if let Some(source_id) = filter {
+            qb.push(" AND source_id = ").push_bind(source_id);
+        }
+        qb.push(" ORDER BY timestamp ASC");
 
-        match rows {
+        match self.fetch_rows(tenant, qb).await {
             Ok(rows) => {
                 let mut out = Vec::with_capacity(rows.len());
                 for row in rows {
-                    let telemetry = Telemetry {
-                        source_id: row.try_get("source_id")?,
-                        server_id: row.try_get("server_id")?,
-                        timestamp: row.try_get("timestamp")?,
-                        cpu: row.try_get("cpu")?,
-                        memory: row.try_get("memory")?,
-                        temperature: row.try_get("temperature")?,
-                        extras: row.try_get("extras")?,
-                    };
-                    out.push(telemetry);
+                    out.push(row_to_telemetry(&row)?);
                 }
 
                 tracing::info!(
@@ -150,6 +450,7 @@ ORDER BY timestamp ASC
                     row_count = out.len(),
                     "repo.telemetry.query_all"
                 );
+                record_latency("query_all", "ok", start);
                 Ok(out)
             }
             Err(e) => {
@@ -158,14 +459,341 @@ ORDER BY timestamp ASC
                     error = %e,
                     "repo.telemetry.query_all"
                 );
+                record_latency("query_all", "error", start);
                 Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }))
             }
         }
     }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let start = Instant::now();
+
+        let source = query
+            .source_filter()
+            .map_err(|e| anyhow::Error::new(PostgresRepoError::InvalidSourceId { source: e }))?;
+
+        let mut qb = select_matching(tenant, source, &query);
+        if let Some(limit) = query.limit {
+            // Fetch one extra row to learn whether another page exists.
+            qb.push(" LIMIT ")
+                .push_bind((limit as i64).saturating_add(1));
+        }
+
+        let rows = match self.fetch_rows(tenant, qb).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::info!(
+                    elapsed_ms = start.elapsed().as_millis(),
+                    error = %e,
+                    "repo.telemetry.query_page"
+                );
+                record_latency("query_page", "error", start);
+                return Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }));
+            }
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in &rows {
+            items.push((row.try_get::<i64, _>("id")?, row_to_telemetry(row)?));
+        }
+
+        let next_cursor = match query.limit {
+            Some(limit) if items.len() > limit => {
+                items.truncate(limit);
+                items
+                    .last()
+                    .map(|(id, telemetry)| TelemetryCursor::after(*id, telemetry))
+            }
+            _ => None,
+        };
+        let items: Vec<Telemetry> = items.into_iter().map(|(_, t)| t).collect();
+
+        tracing::info!(
+            elapsed_ms = start.elapsed().as_millis(),
+            row_count = items.len(),
+            has_more = next_cursor.is_some(),
+            "repo.telemetry.query_page"
+        );
+        record_latency("query_page", "ok", start);
+        Ok(TelemetryPage { items, next_cursor })
+    }
+
+    /// Streams rows as Postgres sends them, from a task that owns the connection.
+    ///
+    /// At most [`STREAM_BUFFER`] rows wait for the consumer; dropping the stream cancels the query.
+    /// Callers queue once [`MAX_CONCURRENT_STREAMS`] streams are open, and each stream is cut
+    /// off after [`STREAM_STATEMENT_TIMEOUT`].
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        let source = query
+            .source_filter()
+            .map_err(|e| anyhow::Error::new(PostgresRepoError::InvalidSourceId { source: e }))?;
+        let permit = Arc::clone(&self.streams)
+            .acquire_owned()
+            .await
+            .expect("stream semaphore is never closed");
+        // Connection errors are reported here rather than as the first item.
+        let mut conn = self
+            .begin_stream(tenant)
+            .await
+            .map_err(|e| anyhow::Error::new(PostgresRepoError::Sqlx { source: e }))?;
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(STREAM_BUFFER);
+        let tenant = tenant.clone();
+        tokio::spawn(async move {
+            let _permit = permit;
+            let start = Instant::now();
+            let mut qb = select_matching(&tenant, source, &query);
+            if let Some(limit) = query.limit {
+                qb.push(" LIMIT ").push_bind(limit as i64);
+            }
+
+            let mut rows = qb.build().fetch(&mut *conn);
+            let mut row_count = 0_usize;
+            let outcome = loop {
+                let item = match rows.try_next().await {
+                    Ok(Some(row)) => row_to_telemetry(&row),
+                    Ok(None) => break "ok",
+                    Err(e) => Err(e),
+                };
+                let failed = item.is_err();
+                let item =
+                    item.map_err(|e| anyhow::Error::new(PostgresRepoError::Sqlx { source: e }));
+                if sender.send(item).await.is_err() {
+                    break "cancelled";
+                }
+                if failed {
+                    break "error";
+                }
+                row_count += 1;
+            };
+            // Hand the connection back before the permit is released.
+            drop(rows);
+            drop(conn);
+
+            tracing::info!(
+                elapsed_ms = start.elapsed().as_millis(),
+                row_count,
+                outcome,
+                "repo.telemetry.query_stream"
+            );
+            record_latency("query_stream", outcome, start);
+        });
+
+        Ok(Box::pin(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move { receiver.recv().await.map(|item| (item, receiver)) },
+        )))
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let start = Instant::now();
+
+        let source = query
+            .source_filter()
+            .map_err(|e| anyhow::Error::new(PostgresRepoError::InvalidSourceId { source: e }))?;
+
+        // Temperature is REAL; cast it so every metric decodes as f64.
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT source_id, \
+             date_bin(INTERVAL '{}', timestamp, TIMESTAMPTZ '1970-01-01 00:00:00+00') AS bucket_start, \
+             COUNT(*) AS row_count",
+            query.bucket.as_pg_interval()
+        ));
+        for metric in ["cpu", "memory", "temperature"] {
+            push_metric_aggregates(&mut qb, metric);
+        }
+        qb.push(
+            " FROM (SELECT source_id, timestamp, cpu, memory, temperature::float8 AS temperature \
+             FROM telemetry WHERE tenant_id = ",
+        );
+        qb.push_bind(tenant.as_str());
+        if let Some(source_id) = source {
+            qb.push(" AND source_id = ").push_bind(source_id);
+        }
+        if let Some(from) = query.from {
+            qb.push(" AND timestamp >= ").push_bind(from);
+        }
+        if let Some(to) = query.to {
+            qb.push(" AND timestamp < ").push_bind(to);
+        }
+        qb.push(") AS t GROUP BY source_id, bucket_start ORDER BY source_id, bucket_start");
+
+        let rows = match self.fetch_rows(tenant, qb).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::info!(
+                    elapsed_ms = start.elapsed().as_millis(),
+                    error = %e,
+                    "repo.telemetry.aggregate"
+                );
+                record_latency("aggregate", "error", start);
+                return Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }));
+            }
+        };
+
+        let mut buckets = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let count: i64 = row.try_get("row_count")?;
+            buckets.push(AggregateBucket {
+                source_id: row.try_get("source_id")?,
+                bucket_start: row.try_get("bucket_start")?,
+                count: count as u64,
+                cpu: row_to_stats(row, "cpu")?,
+                memory: row_to_stats(row, "memory")?,
+                temperature: row_to_stats(row, "temperature")?,
+            });
+        }
+
+        tracing::info!(
+            elapsed_ms = start.elapsed().as_millis(),
+            bucket_count = buckets.len(),
+            "repo.telemetry.aggregate"
+        );
+        record_latency("aggregate", "ok", start);
+        Ok(buckets)
+    }
+}
+
+/// Rows deleted per statement, so expiry never holds long locks or bloats one transaction.
+const EXPIRE_BATCH_SIZE: i64 = 10_000;
+
+impl PostgresTelemetryRepo {
+    /// Deletes the rows matched by `rule` in batches of [`EXPIRE_BATCH_SIZE`].
+    async fn expire_rule(&self, rule: &ExpiryRule) -> Result<u64, sqlx::Error> {
+        let except_tenants: Vec<&str> = rule.except_tenants.iter().map(|t| t.as_str()).collect();
+        let mut deleted = 0;
+        loop {
+            let done = sqlx::query(
+                r#"
+DELETE FROM telemetry WHERE (tableoid, ctid) IN (
+    SELECT tableoid, ctid FROM telemetry
+    WHERE timestamp < $1
+      AND ($2::text IS NULL OR tenant_id = $2)
+      AND ($3::uuid IS NULL OR source_id = $3)
+      AND tenant_id <> ALL($4::text[])
+      AND source_id <> ALL($5::uuid[])
+    LIMIT $6
+)
+"#,
+            )
+            .bind(rule.cutoff)
+            .bind(rule.tenant.as_ref().map(|t| t.as_str()))
+            .bind(rule.source_id)
+            .bind(&except_tenants)
+            .bind(&rule.except_sources)
+            .bind(EXPIRE_BATCH_SIZE)
+            .execute(&self.pool)
+            .await?;
+            deleted += done.rows_affected();
+            if done.rows_affected() < EXPIRE_BATCH_SIZE as u64 {
+                return Ok(deleted);
+            }
+        }
+    }
+
+    /// Applies `policy`, adding the rows removed so far to `deleted`.
+    async fn expire_all(
+        &self,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+        deleted: &mut u64,
+    ) -> Result<(), sqlx::Error> {
+        if let Some(floor) = policy.floor(now) {
+            *deleted += self.drop_expired_partitions(floor).await?;
+        }
+        for rule in policy.rules(now) {
+            *deleted += self.expire_rule(&rule).await?;
+        }
+        Ok(())
+    }
+
+    /// Drops managed partitions, attached or detached, whose whole range is older than `floor`.
+    ///
+    /// Returns their row count as estimated by the planner statistics (`reltuples`), which
+    /// avoids scanning a partition just to count what is about to be dropped.
+    async fn drop_expired_partitions(&self, floor: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let mut partitions = postgres_partitions::managed_partitions(&self.pool).await?;
+        partitions.extend(postgres_partitions::detached_partitions(&self.pool).await?);
+
+        let mut dropped = 0;
+        for partition in partitions.iter().filter(|p| p.end_instant() <= floor) {
+            // -1 (never analyzed) counts as empty.
+            let rows: i64 = sqlx::query_scalar(
+                "SELECT GREATEST(reltuples, 0)::bigint FROM pg_class WHERE oid = $1::regclass",
+            )
+            .bind(&partition.name)
+            .fetch_one(&self.pool)
+            .await?;
+            sqlx::query(&format!("DROP TABLE {}", partition.name))
+                .execute(&self.pool)
+                .await?;
+            dropped += rows as u64;
+        }
+        Ok(dropped)
+    }
+}
+
+#[async_trait::async_trait]
+impl ExpiringStore for PostgresTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    /// Drops partitions older than every retention, then runs one batched delete per
+    /// [`ExpiryRule`] of `policy`.
+    ///
+    /// Statements run without a tenant setting, so with row-level security enabled
+    /// the role must own the table or have `BYPASSRLS`.
+    async fn expire(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        let mut deleted = 0;
+        if let Err(e) = self.expire_all(policy, now, &mut deleted).await {
+            tracing::info!(
+                elapsed_ms = start.elapsed().as_millis(),
+                row_count = deleted,
+                error = %e,
+                "repo.telemetry.expire"
+            );
+            record_latency("expire", "error", start);
+            return Err(anyhow::Error::new(PostgresRepoError::Sqlx { source: e }));
+        }
+
+        tracing::info!(
+            elapsed_ms = start.elapsed().as_millis(),
+            row_count = deleted,
+            "repo.telemetry.expire"
+        );
+        record_latency("expire", "ok", start);
+        Ok(deleted)
+    }
+}
+
+#[async_trait::async_trait]
+impl DependencyProbe for PostgresTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn probe(&self) -> anyhow::Result<()> {
+        postgres_db::ping(&self.pool).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::sync::OnceLock;
 
     use chrono::{DateTime, Utc};
@@ -173,7 +801,6 @@ mod tests {
     use tokio::sync::Mutex;
 
     use super::*;
-    use crate::adapters::output::postgres_db;
 
     static TEST_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
@@ -181,26 +808,13 @@ mod tests {
         std::env::var("DATABASE_URL").ok()
     }
 
-    async fn lock() -> tokio::sync::MutexGuard<'static, ()> {
+    /// Serializes tests sharing the `DATABASE_URL` database.
+    pub(crate) async fn lock() -> tokio::sync::MutexGuard<'static, ()> {
         TEST_LOCK.get_or_init(|| Mutex::new(())).lock().await
     }
 
     async fn ensure_schema(pool: &PgPool) -> anyhow::Result<()> {
-        sqlx::query(
-            r#"
-CREATE TABLE IF NOT EXISTS telemetry (
-    source_id UUID NOT NULL,
-    server_id UUID NOT NULL,
-    timestamp TIMESTAMPTZ NOT NULL,
-    cpu DOUBLE PRECISION NULL,
-    memory DOUBLE PRECISION NULL,
-    temperature REAL NULL,
-    extras JSONB NOT NULL
-)
-"#,
-        )
-        .execute(pool)
-        .await?;
+        crate::infra::startup::init_postgres_schema(pool).await?;
 
         sqlx::query("TRUNCATE TABLE telemetry")
             .execute(pool)
@@ -223,6 +837,7 @@ CREATE TABLE IF NOT EXISTS telemetry (
         ensure_schema(&pool).await.unwrap();
 
         let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
         let telemetry = Telemetry {
             source_id: Uuid::new_v4(),
             server_id: Uuid::new_v4(),
@@ -233,8 +848,8 @@ CREATE TABLE IF NOT EXISTS telemetry (
             extras: json!({"k":"v"}),
         };
 
-        repo.save(telemetry.clone()).await.unwrap();
-        let got = repo.query_all(None).await.unwrap();
+        repo.save(&tenant, telemetry.clone()).await.unwrap();
+        let got = repo.query_all(&tenant, None).await.unwrap();
 
         assert_eq!(got.len(), 1);
         assert_eq!(got[0].source_id, telemetry.source_id);
@@ -246,6 +861,43 @@ CREATE TABLE IF NOT EXISTS telemetry (
         assert_eq!(got[0].extras, telemetry.extras);
     }
 
+    #[tokio::test]
+    async fn test_postgres_repo_save_batch_inserts_all_rows_including_nulls() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
+        let batch: Vec<Telemetry> = (0..3)
+            .map(|i| Telemetry {
+                source_id: Uuid::new_v4(),
+                server_id: Uuid::new_v4(),
+                timestamp: fixed_time() + chrono::Duration::seconds(i),
+                cpu: (i % 2 == 0).then_some(i as f64),
+                memory: None,
+                temperature: Some(20.5),
+                extras: json!({"i": i}),
+            })
+            .collect();
+
+        repo.save_batch(&tenant, batch.clone()).await.unwrap();
+        let got = repo.query_all(&tenant, None).await.unwrap();
+
+        assert_eq!(got.len(), 3);
+        for (got, want) in got.iter().zip(&batch) {
+            assert_eq!(got.source_id, want.source_id);
+            assert_eq!(got.cpu, want.cpu);
+            assert_eq!(got.memory, want.memory);
+            assert_eq!(got.temperature, want.temperature);
+            assert_eq!(got.extras, want.extras);
+        }
+    }
+
     #[tokio::test]
     async fn test_postgres_repo_query_all_filters_by_source_id() {
         let Some(database_url) = database_url() else {
@@ -257,6 +909,7 @@ CREATE TABLE IF NOT EXISTS telemetry (
         ensure_schema(&pool).await.unwrap();
 
         let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
         let source_a = Uuid::new_v4();
         let source_b = Uuid::new_v4();
 
@@ -279,15 +932,283 @@ CREATE TABLE IF NOT EXISTS telemetry (
             extras: json!({"b":2}),
         };
 
-        repo.save(t1.clone()).await.unwrap();
-        repo.save(t2).await.unwrap();
+        repo.save(&tenant, t1.clone()).await.unwrap();
+        repo.save(&tenant, t2).await.unwrap();
 
-        let got = repo.query_all(Some(source_a.to_string())).await.unwrap();
+        let got = repo
+            .query_all(&tenant, Some(source_a.to_string()))
+            .await
+            .unwrap();
         assert_eq!(got.len(), 1);
         assert_eq!(got[0].source_id, t1.source_id);
         assert_eq!(got[0].extras, t1.extras);
     }
 
+    #[tokio::test]
+    async fn test_postgres_repo_query_page_applies_range_limit_and_cursor() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
+        let source_id = Uuid::new_v4();
+        for i in 0..5 {
+            repo.save(
+                &tenant,
+                Telemetry {
+                    source_id,
+                    server_id: Uuid::new_v4(),
+                    timestamp: fixed_time() + chrono::Duration::seconds(i),
+                    cpu: Some(i as f64),
+                    memory: None,
+                    temperature: None,
+                    extras: json!({}),
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let mut query = TelemetryQuery {
+            from: Some(fixed_time() + chrono::Duration::seconds(1)),
+            limit: Some(2),
+            order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let first = repo.query_page(&tenant, query.clone()).await.unwrap();
+        let cpus: Vec<_> = first.items.iter().map(|t| t.cpu).collect();
+        assert_eq!(cpus, vec![Some(4.0), Some(3.0)]);
+
+        query.cursor = first.next_cursor;
+        let second = repo.query_page(&tenant, query).await.unwrap();
+        let cpus: Vec<_> = second.items.iter().map(|t| t.cpu).collect();
+        assert_eq!(cpus, vec![Some(2.0), Some(1.0)]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_pages_rows_sharing_timestamp_and_source() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
+        let source_id = Uuid::new_v4();
+        let batch = (0..5)
+            .map(|i| Telemetry {
+                source_id,
+                server_id: Uuid::nil(),
+                timestamp: fixed_time(),
+                cpu: Some(i as f64),
+                memory: None,
+                temperature: None,
+                extras: json!({}),
+            })
+            .collect();
+        repo.save_batch(&tenant, batch).await.unwrap();
+
+        for order in [SortOrder::Asc, SortOrder::Desc] {
+            let mut query = TelemetryQuery {
+                source_id: Some(source_id.to_string()),
+                limit: Some(2),
+                order,
+                ..Default::default()
+            };
+            let mut cpus = Vec::new();
+            loop {
+                let page = repo.query_page(&tenant, query.clone()).await.unwrap();
+                cpus.extend(page.items.iter().filter_map(|t| t.cpu));
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor),
+                    None => break,
+                }
+            }
+            cpus.sort_by(f64::total_cmp);
+            assert_eq!(cpus, vec![0.0, 1.0, 2.0, 3.0, 4.0], "{order:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_query_stream_yields_every_match_in_order() {
+        use futures_util::TryStreamExt;
+
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool).with_row_level_security(true);
+        let tenant = TenantId::parse("stream").unwrap();
+        let source_id = Uuid::new_v4();
+        let batch = (0..600)
+            .map(|i| Telemetry {
+                source_id,
+                server_id: Uuid::nil(),
+                timestamp: fixed_time() + chrono::Duration::seconds(i),
+                cpu: Some(i as f64),
+                memory: None,
+                temperature: None,
+                extras: json!({}),
+            })
+            .collect();
+        repo.save_batch(&tenant, batch).await.unwrap();
+
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let stream = repo.query_stream(&tenant, query.clone()).await.unwrap();
+        let items: Vec<Telemetry> = stream.try_collect().await.unwrap();
+        assert_eq!(items.len(), 600);
+        assert_eq!(items[0].cpu, Some(599.0));
+        assert_eq!(items[599].cpu, Some(0.0));
+
+        // Dropping a stream part-way releases its connection.
+        let mut stream = repo
+            .query_stream(
+                &tenant,
+                TelemetryQuery {
+                    limit: Some(10),
+                    ..query
+                },
+            )
+            .await
+            .unwrap();
+        assert!(stream.try_next().await.unwrap().is_some());
+        drop(stream);
+        assert!(repo.probe().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_stalled_streams_leave_connections_for_writes() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = Arc::new(PostgresTelemetryRepo::new(pool));
+        let tenant = TenantId::parse("stalled").unwrap();
+        let source_id = Uuid::new_v4();
+        let batch = (0..STREAM_BUFFER as i64 * 2)
+            .map(|i| Telemetry {
+                source_id,
+                server_id: Uuid::nil(),
+                timestamp: fixed_time() + chrono::Duration::seconds(i),
+                cpu: Some(i as f64),
+                memory: None,
+                temperature: None,
+                extras: json!({}),
+            })
+            .collect();
+        repo.save_batch(&tenant, batch).await.unwrap();
+
+        // Streams nobody reads keep their connection until they are dropped.
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            ..Default::default()
+        };
+        let mut open = Vec::new();
+        for _ in 0..MAX_CONCURRENT_STREAMS {
+            open.push(repo.query_stream(&tenant, query.clone()).await.unwrap());
+        }
+
+        // One more stream queues instead of taking another connection...
+        let queued = tokio::spawn({
+            let repo = Arc::clone(&repo);
+            let tenant = tenant.clone();
+            let query = query.clone();
+            async move { repo.query_stream(&tenant, query).await.map(drop) }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!queued.is_finished());
+
+        // ...so writes still get one.
+        let telemetry = Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: fixed_time(),
+            cpu: Some(1.0),
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        };
+        repo.save(&tenant, telemetry).await.unwrap();
+
+        drop(open);
+        queued.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_aggregate_matches_in_memory_aggregator() {
+        use crate::core::application::telemetry::{BucketAggregator, BucketWidth};
+
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
+        let source_id = Uuid::new_v4();
+        let batch: Vec<Telemetry> = (0..10)
+            .map(|i| Telemetry {
+                source_id,
+                server_id: Uuid::new_v4(),
+                timestamp: fixed_time() + chrono::Duration::seconds(i * 37),
+                cpu: Some(i as f64 * 1.5),
+                memory: (i % 3 != 0).then_some(100.0 + i as f64),
+                temperature: Some(20.0 + i as f32),
+                extras: json!({}),
+            })
+            .collect();
+        repo.save_batch(&tenant, batch.clone()).await.unwrap();
+
+        let query = AggregateQuery {
+            source_id: Some(source_id.to_string()),
+            bucket: BucketWidth::FiveMinutes,
+            ..Default::default()
+        };
+        let got = repo.aggregate(&tenant, query.clone()).await.unwrap();
+
+        let mut expected = BucketAggregator::new(query.bucket);
+        batch.iter().for_each(|t| expected.push(t));
+        let expected = expected.finish();
+
+        assert_eq!(got.len(), expected.len());
+        for (got, want) in got.iter().zip(&expected) {
+            assert_eq!(got.bucket_start, want.bucket_start);
+            assert_eq!(got.count, want.count);
+            let (g, w) = (got.cpu.as_ref().unwrap(), want.cpu.as_ref().unwrap());
+            assert_eq!(g.count, w.count);
+            assert!((g.avg - w.avg).abs() < 1e-9);
+            assert!((g.p95 - w.p95).abs() < 1e-9);
+            assert_eq!(
+                got.memory.as_ref().map(|m| m.count),
+                want.memory.as_ref().map(|m| m.count)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_postgres_repo_query_all_rejects_invalid_uuid_filter() {
         let Some(database_url) = database_url() else {
@@ -299,8 +1220,9 @@ CREATE TABLE IF NOT EXISTS telemetry (
         ensure_schema(&pool).await.unwrap();
 
         let repo = PostgresTelemetryRepo::new(pool);
+        let tenant = TenantId::default();
         let err = repo
-            .query_all(Some("not-a-uuid".to_string()))
+            .query_all(&tenant, Some("not-a-uuid".to_string()))
             .await
             .unwrap_err();
         assert!(
@@ -308,4 +1230,119 @@ CREATE TABLE IF NOT EXISTS telemetry (
                 .is_some_and(|e| matches!(e, PostgresRepoError::InvalidSourceId { .. }))
         );
     }
+
+    #[tokio::test]
+    async fn test_postgres_repo_never_returns_rows_of_another_tenant() {
+        use crate::core::application::telemetry::BucketWidth;
+
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        // The RLS setting only adds `set_config` calls, so it is safe to enable here too.
+        let repo = PostgresTelemetryRepo::new(pool).with_row_level_security(true);
+        let acme = TenantId::parse("acme").unwrap();
+        let globex = TenantId::parse("globex").unwrap();
+        let source_id = Uuid::new_v4();
+        let record = |cpu: f64| Telemetry {
+            source_id,
+            server_id: Uuid::new_v4(),
+            timestamp: fixed_time(),
+            cpu: Some(cpu),
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        };
+
+        repo.save(&acme, record(1.0)).await.unwrap();
+        repo.save_batch(&globex, vec![record(2.0), record(3.0)])
+            .await
+            .unwrap();
+
+        let all = repo.query_all(&acme, None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].cpu, Some(1.0));
+
+        let page = repo
+            .query_page(&globex, TelemetryQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 2);
+
+        let query = AggregateQuery {
+            source_id: Some(source_id.to_string()),
+            bucket: BucketWidth::OneMinute,
+            ..Default::default()
+        };
+        let buckets = repo.aggregate(&acme, query).await.unwrap();
+        assert_eq!(buckets.iter().map(|b| b.count).sum::<u64>(), 1);
+
+        let other = TenantId::parse("initech").unwrap();
+        assert!(repo.query_all(&other, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_probe_pings_database() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        let repo = PostgresTelemetryRepo::new(pool.clone());
+        assert!(repo.probe().await.is_ok());
+
+        pool.close().await;
+        assert!(repo.probe().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_repo_expire_applies_default_and_overrides() {
+        let Some(database_url) = database_url() else {
+            return;
+        };
+
+        let _guard = lock().await;
+        let pool = postgres_db::connect_pool(&database_url).await.unwrap();
+        ensure_schema(&pool).await.unwrap();
+
+        let repo = PostgresTelemetryRepo::new(pool);
+        let acme = TenantId::parse("acme").unwrap();
+        let short_lived = Uuid::new_v4();
+        let now = fixed_time();
+        let aged = |source_id, days| Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: now - chrono::Duration::days(days),
+            cpu: Some(days as f64),
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        };
+
+        let source = Uuid::new_v4();
+        repo.save_batch(
+            &TenantId::default(),
+            vec![aged(source, 1), aged(source, 40), aged(short_lived, 3)],
+        )
+        .await
+        .unwrap();
+        repo.save_batch(&acme, vec![aged(source, 40), aged(source, 100)])
+            .await
+            .unwrap();
+
+        let policy = RetentionPolicy::new(Some(chrono::Duration::days(30)))
+            .with_tenant(acme.clone(), chrono::Duration::days(90))
+            .with_source(short_lived, chrono::Duration::days(2));
+        assert_eq!(repo.expire(&policy, now).await.unwrap(), 3);
+
+        let kept = |rows: Vec<Telemetry>| rows.iter().map(|t| t.cpu).collect::<Vec<_>>();
+        let default_rows = repo.query_all(&TenantId::default(), None).await.unwrap();
+        let acme_rows = repo.query_all(&acme, None).await.unwrap();
+        assert_eq!(kept(default_rows), vec![Some(1.0)]);
+        assert_eq!(kept(acme_rows), vec![Some(40.0)]);
+    }
 }
@@ -0,0 +1,610 @@
+//! Embedded SQLite telemetry repository, for single-node deployments without Postgres.
+//!
+//! The database file is opened in WAL mode so queries never block ingest, and migrated
+//! from `migrations_sqlite/` on open. Every statement filters on `tenant_id`.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow, SqliteSynchronous,
+};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+use crate::core::application::health::DependencyProbe;
+use crate::core::application::retention::{ExpiringStore, ExpiryRule, RetentionPolicy};
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, BucketAggregator, SortOrder, TelemetryCursor, TelemetryPage,
+    TelemetryQuery, TelemetryRepository,
+};
+use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+/// Rows deleted per statement, so expiry never holds the write lock for long.
+const EXPIRE_BATCH_SIZE: i64 = 10_000;
+
+#[derive(thiserror::Error, Debug)]
+/// Errors produced by the SQLite telemetry repository.
+pub enum SqliteRepoError {
+    /// The provided `source_id` filter is not a valid UUID.
+    #[error("invalid source id filter")]
+    InvalidSourceId {
+        /// Underlying UUID parse error.
+        source: uuid::Error,
+    },
+
+    /// The timestamp cannot be stored as Unix nanoseconds (outside 1677–2262).
+    #[error("timestamp out of range: {timestamp}")]
+    TimestampOutOfRange {
+        /// Offending timestamp.
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Applying the embedded migrations failed.
+    #[error("sqlite migration failed")]
+    Migrate {
+        /// Underlying migration error.
+        source: sqlx::migrate::MigrateError,
+    },
+
+    /// A database error occurred while saving or querying.
+    #[error("database error")]
+    Sqlx {
+        /// Underlying driver error.
+        source: sqlx::Error,
+    },
+}
+
+impl From<sqlx::Error> for SqliteRepoError {
+    fn from(source: sqlx::Error) -> Self {
+        Self::Sqlx { source }
+    }
+}
+
+/// Stores and queries telemetry rows in a local SQLite database, scoped by `tenant_id`.
+pub struct SqliteTelemetryRepo {
+    pool: SqlitePool,
+}
+
+impl SqliteTelemetryRepo {
+    /// Opens (creating if needed) the database at `path`, enables WAL mode and migrates it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn demo() -> anyhow::Result<()> {
+    /// use rustpulse::adapters::output::sqlite_telemetry_repo::SqliteTelemetryRepo;
+    ///
+    /// let _repo = SqliteTelemetryRepo::open("/var/lib/rustpulse/telemetry.db").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, SqliteRepoError> {
+        let start = Instant::now();
+        let options = SqliteConnectOptions::new()
+            .filename(path.as_ref())
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await?;
+        SQLITE_MIGRATOR
+            .run(&pool)
+            .await
+            .map_err(|source| SqliteRepoError::Migrate { source })?;
+
+        tracing::info!(
+            path = %path.as_ref().display(),
+            elapsed_ms = start.elapsed().as_millis(),
+            "db.sqlite.ready"
+        );
+        Ok(Self { pool })
+    }
+}
+
+fn timestamp_ns(timestamp: DateTime<Utc>) -> Result<i64, SqliteRepoError> {
+    timestamp
+        .timestamp_nanos_opt()
+        .ok_or(SqliteRepoError::TimestampOutOfRange { timestamp })
+}
+
+fn source_filter(raw: Option<&str>) -> Result<Option<Uuid>, SqliteRepoError> {
+    raw.map(Uuid::parse_str)
+        .transpose()
+        .map_err(|source| SqliteRepoError::InvalidSourceId { source })
+}
+
+fn row_to_telemetry(row: &SqliteRow) -> Result<Telemetry, sqlx::Error> {
+    let extras: sqlx::types::Json<serde_json::Value> = row.try_get("extras")?;
+    Ok(Telemetry {
+        source_id: row.try_get("source_id")?,
+        server_id: row.try_get("server_id")?,
+        timestamp: DateTime::from_timestamp_nanos(row.try_get("timestamp_ns")?),
+        cpu: row.try_get("cpu")?,
+        memory: row.try_get("memory")?,
+        temperature: row.try_get("temperature")?,
+        extras: extras.0,
+    })
+}
+
+/// Starts a `SELECT` of `tenant`'s rows, optionally of one source and within `[from, to)`.
+fn select_rows<'a>(
+    tenant: &'a TenantId,
+    source: Option<Uuid>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<QueryBuilder<'a, Sqlite>, SqliteRepoError> {
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT rowid, source_id, server_id, timestamp_ns, cpu, memory, temperature, extras FROM telemetry WHERE tenant_id = ",
+    );
+    qb.push_bind(tenant.as_str());
+    if let Some(source_id) = source {
+        qb.push(" AND source_id = ").push_bind(source_id);
+    }
+    if let Some(from) = from {
+        qb.push(" AND timestamp_ns >= ")
+            .push_bind(timestamp_ns(from)?);
+    }
+    if let Some(to) = to {
+        qb.push(" AND timestamp_ns < ").push_bind(timestamp_ns(to)?);
+    }
+    Ok(qb)
+}
+
+/// Logs and times one repository operation, as `repo.telemetry.<operation>`.
+fn observe<T>(
+    operation: &'static str,
+    start: Instant,
+    result: Result<T, SqliteRepoError>,
+    row_count: impl FnOnce(&T) -> usize,
+) -> anyhow::Result<T> {
+    let outcome = match &result {
+        Ok(value) => {
+            tracing::info!(
+                elapsed_ms = start.elapsed().as_millis(),
+                row_count = row_count(value),
+                "repo.telemetry.{operation}"
+            );
+            "ok"
+        }
+        Err(e) => {
+            tracing::info!(
+                elapsed_ms = start.elapsed().as_millis(),
+                error = %e,
+                "repo.telemetry.{operation}"
+            );
+            "error"
+        }
+    };
+    metrics::histogram!(
+        "rustpulse_repo_duration_seconds",
+        "backend" => "sqlite",
+        "operation" => operation,
+        "outcome" => outcome,
+    )
+    .record(start.elapsed().as_secs_f64());
+    result.map_err(anyhow::Error::new)
+}
+
+impl SqliteTelemetryRepo {
+    async fn insert(
+        &self,
+        tenant: &TenantId,
+        batch: Vec<Telemetry>,
+    ) -> Result<(), SqliteRepoError> {
+        let mut tx = self.pool.begin().await?;
+        for telemetry in batch {
+            sqlx::query(
+                r#"
+INSERT INTO telemetry (tenant_id, source_id, server_id, timestamp_ns, cpu, memory, temperature, extras)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+"#,
+            )
+            .bind(tenant.as_str())
+            .bind(telemetry.source_id)
+            .bind(telemetry.server_id)
+            .bind(timestamp_ns(telemetry.timestamp)?)
+            .bind(telemetry.cpu)
+            .bind(telemetry.memory)
+            .bind(telemetry.temperature)
+            .bind(sqlx::types::Json(telemetry.extras))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn fetch(
+        &self,
+        mut qb: QueryBuilder<'_, Sqlite>,
+    ) -> Result<Vec<Telemetry>, SqliteRepoError> {
+        let rows = qb.build().fetch_all(&self.pool).await?;
+        rows.iter()
+            .map(|row| row_to_telemetry(row).map_err(SqliteRepoError::from))
+            .collect()
+    }
+
+    async fn page(
+        &self,
+        tenant: &TenantId,
+        query: &TelemetryQuery,
+    ) -> Result<TelemetryPage, SqliteRepoError> {
+        let source = source_filter(query.source_id.as_deref())?;
+        let mut qb = select_rows(tenant, source, query.from, query.to)?;
+        if let Some(cursor) = query.cursor {
+            let op = match query.order {
+                SortOrder::Asc => ">",
+                SortOrder::Desc => "<",
+            };
+            qb.push(format_args!(" AND (timestamp_ns, source_id, rowid) {op} ("))
+                .push_bind(timestamp_ns(cursor.timestamp)?)
+                .push(", ")
+                .push_bind(cursor.source_id)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        let order = query.order.as_sql();
+        qb.push(format_args!(
+            " ORDER BY timestamp_ns {order}, source_id {order}, rowid {order}"
+        ));
+        if let Some(limit) = query.limit {
+            // Fetch one extra row to learn whether another page exists.
+            qb.push(" LIMIT ")
+                .push_bind((limit as i64).saturating_add(1));
+        }
+
+        let mut rows = qb.build().fetch_all(&self.pool).await?;
+        let next_cursor = match query.limit {
+            Some(limit) if rows.len() > limit => {
+                rows.truncate(limit);
+                match rows.last() {
+                    Some(row) => Some(TelemetryCursor::after(
+                        row.try_get("rowid")?,
+                        &row_to_telemetry(row)?,
+                    )),
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        let items = rows
+            .iter()
+            .map(row_to_telemetry)
+            .collect::<Result<_, _>>()?;
+        Ok(TelemetryPage { items, next_cursor })
+    }
+
+    /// Deletes the rows matched by `rule` in batches of [`EXPIRE_BATCH_SIZE`].
+    async fn expire_rule(&self, rule: &ExpiryRule) -> Result<u64, SqliteRepoError> {
+        let mut deleted = 0;
+        loop {
+            let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "DELETE FROM telemetry WHERE rowid IN (SELECT rowid FROM telemetry WHERE timestamp_ns < ",
+            );
+            qb.push_bind(timestamp_ns(rule.cutoff)?);
+            if let Some(tenant) = &rule.tenant {
+                qb.push(" AND tenant_id = ").push_bind(tenant.as_str());
+            }
+            if let Some(source_id) = rule.source_id {
+                qb.push(" AND source_id = ").push_bind(source_id);
+            }
+            for tenant in &rule.except_tenants {
+                qb.push(" AND tenant_id <> ").push_bind(tenant.as_str());
+            }
+            for source_id in &rule.except_sources {
+                qb.push(" AND source_id <> ").push_bind(*source_id);
+            }
+            qb.push(" LIMIT ").push_bind(EXPIRE_BATCH_SIZE).push(")");
+
+            let done = qb.build().execute(&self.pool).await?;
+            deleted += done.rows_affected();
+            if done.rows_affected() < EXPIRE_BATCH_SIZE as u64 {
+                return Ok(deleted);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TelemetryRepository for SqliteTelemetryRepo {
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let res = self.insert(tenant, vec![telemetry]).await;
+        observe("save", start, res, |_| 1)
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let batch_size = batch.len();
+        // One transaction per batch: a single fsync however many rows it holds.
+        let res = self.insert(tenant, batch).await;
+        observe("save_batch", start, res, |_| batch_size)
+    }
+
+    fn atomic_batches(&self) -> bool {
+        true
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        let start = Instant::now();
+        let res = async {
+            let mut qb = select_rows(tenant, source_filter(node_id.as_deref())?, None, None)?;
+            qb.push(" ORDER BY timestamp_ns ASC");
+            self.fetch(qb).await
+        }
+        .await;
+        observe("query_all", start, res, Vec::len)
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let start = Instant::now();
+        let res = self.page(tenant, &query).await;
+        observe("query_page", start, res, |page| page.items.len())
+    }
+
+    /// Filters in SQL and buckets in memory: SQLite has no `percentile_cont`.
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let start = Instant::now();
+        let res = async {
+            let source = source_filter(query.source_id.as_deref())?;
+            let mut qb = select_rows(tenant, source, query.from, query.to)?;
+            // Streamed: only the per-bucket state is held, never the matching rows.
+            let mut rows = qb.build().fetch(&self.pool);
+            let mut aggregator = BucketAggregator::new(query.bucket);
+            while let Some(row) = rows.try_next().await? {
+                aggregator.push(&row_to_telemetry(&row)?);
+            }
+            Ok(aggregator.finish())
+        }
+        .await;
+        observe("aggregate", start, res, Vec::len)
+    }
+}
+
+#[async_trait::async_trait]
+impl ExpiringStore for SqliteTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    /// Runs one batched delete per [`ExpiryRule`] of `policy`.
+    async fn expire(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        let res = async {
+            let mut deleted = 0;
+            for rule in policy.rules(now) {
+                deleted += self.expire_rule(&rule).await?;
+            }
+            Ok(deleted)
+        }
+        .await;
+        observe("expire", start, res, |deleted| *deleted as usize)
+    }
+}
+
+#[async_trait::async_trait]
+impl DependencyProbe for SqliteTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn probe(&self) -> anyhow::Result<()> {
+        sqlx::query_scalar::<_, i64>("SELECT 1")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::application::telemetry::BucketWidth;
+    use std::path::PathBuf;
+
+    /// A fresh directory, since WAL mode adds `-wal` and `-shm` files next to the database.
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rustpulse-sqlite-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn telemetry_at(source_id: Uuid, timestamp: DateTime<Utc>, cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp,
+            cpu: Some(cpu),
+            memory: None,
+            temperature: Some(21.5),
+            extras: serde_json::json!({ "rpm": 1200 }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_enables_wal_and_roundtrips_rows_per_tenant() {
+        let dir = temp_dir();
+        let repo = SqliteTelemetryRepo::open(dir.join("telemetry.db"))
+            .await
+            .unwrap();
+        let acme = TenantId::parse("acme").unwrap();
+        let now = DateTime::<Utc>::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+
+        repo.save(&TenantId::default(), telemetry_at(Uuid::nil(), now, 1.0))
+            .await
+            .unwrap();
+        repo.save_batch(
+            &acme,
+            vec![
+                telemetry_at(Uuid::from_u128(1), now, 2.0),
+                telemetry_at(Uuid::from_u128(2), now, 3.0),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&repo.pool)
+            .await
+            .unwrap();
+        let default_rows = repo.query_all(&TenantId::default(), None).await.unwrap();
+        let acme_rows = repo
+            .query_all(&acme, Some(Uuid::from_u128(2).to_string()))
+            .await
+            .unwrap();
+        let invalid = repo.query_all(&acme, Some("nope".to_string())).await;
+        repo.probe().await.unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(journal_mode, "wal");
+        // Nanosecond timestamps, nullable metrics and extras all survive the roundtrip.
+        let json = |rows: Vec<Telemetry>| serde_json::to_value(rows).unwrap();
+        assert_eq!(
+            json(default_rows),
+            json(vec![telemetry_at(Uuid::nil(), now, 1.0)])
+        );
+        assert_eq!(
+            json(acme_rows),
+            json(vec![telemetry_at(Uuid::from_u128(2), now, 3.0)])
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_page_and_aggregate_push_filters_down() {
+        let dir = temp_dir();
+        let repo = SqliteTelemetryRepo::open(dir.join("telemetry.db"))
+            .await
+            .unwrap();
+        let tenant = TenantId::default();
+        let source_id = Uuid::new_v4();
+        let base = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let batch = (0..5)
+            .map(|i| telemetry_at(source_id, base + chrono::Duration::seconds(i), i as f64))
+            .chain([telemetry_at(Uuid::new_v4(), base, 9.0)])
+            .collect();
+        repo.save_batch(&tenant, batch).await.unwrap();
+        // Rows sharing a timestamp and source straddle a page boundary.
+        let twins = Uuid::new_v4();
+        repo.save_batch(
+            &tenant,
+            (0..3)
+                .map(|i| telemetry_at(twins, base, i as f64))
+                .collect(),
+        )
+        .await
+        .unwrap();
+        let twins_query = TelemetryQuery {
+            source_id: Some(twins.to_string()),
+            limit: Some(2),
+            order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let twins_first = repo.query_page(&tenant, twins_query.clone()).await.unwrap();
+        let twins_second = repo
+            .query_page(
+                &tenant,
+                TelemetryQuery {
+                    cursor: twins_first.next_cursor,
+                    ..twins_query
+                },
+            )
+            .await
+            .unwrap();
+
+        let query = TelemetryQuery {
+            source_id: Some(source_id.to_string()),
+            from: Some(base + chrono::Duration::seconds(1)),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first = repo.query_page(&tenant, query.clone()).await.unwrap();
+        let second = repo
+            .query_page(
+                &tenant,
+                TelemetryQuery {
+                    cursor: first.next_cursor,
+                    ..query
+                },
+            )
+            .await
+            .unwrap();
+        let buckets = repo
+            .aggregate(
+                &tenant,
+                AggregateQuery {
+                    source_id: Some(source_id.to_string()),
+                    from: None,
+                    to: Some(base + chrono::Duration::seconds(4)),
+                    bucket: BucketWidth::OneMinute,
+                },
+            )
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let cpus = |page: &TelemetryPage| page.items.iter().map(|t| t.cpu).collect::<Vec<_>>();
+        assert_eq!(cpus(&first), vec![Some(1.0), Some(2.0)]);
+        assert_eq!(cpus(&twins_first), vec![Some(2.0), Some(1.0)]);
+        assert_eq!(cpus(&twins_second), vec![Some(0.0)]);
+        assert_eq!(cpus(&second), vec![Some(3.0), Some(4.0)]);
+        assert!(second.next_cursor.is_none());
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].count, 4);
+        assert_eq!(buckets[0].cpu.as_ref().map(|s| s.max), Some(3.0));
+    }
+
+    #[tokio::test]
+    async fn test_expire_applies_default_and_overrides() {
+        let dir = temp_dir();
+        let repo = SqliteTelemetryRepo::open(dir.join("telemetry.db"))
+            .await
+            .unwrap();
+        let acme = TenantId::parse("acme").unwrap();
+        let now = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let old = now - chrono::Duration::days(10);
+        repo.save(&TenantId::default(), telemetry_at(Uuid::nil(), old, 1.0))
+            .await
+            .unwrap();
+        repo.save(&acme, telemetry_at(Uuid::nil(), old, 2.0))
+            .await
+            .unwrap();
+        repo.save(&acme, telemetry_at(Uuid::nil(), now, 3.0))
+            .await
+            .unwrap();
+
+        let policy = RetentionPolicy::new(Some(chrono::TimeDelta::days(7)))
+            .with_tenant(acme.clone(), chrono::TimeDelta::days(30));
+        let expired = repo.expire(&policy, now).await.unwrap();
+        let default_rows = repo.query_all(&TenantId::default(), None).await.unwrap();
+        let acme_rows = repo.query_all(&acme, None).await.unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(expired, 1);
+        assert!(default_rows.is_empty());
+        assert_eq!(acme_rows.len(), 2);
+    }
+}
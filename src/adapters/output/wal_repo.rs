@@ -0,0 +1,820 @@
+//! Write-ahead buffered telemetry repository.
+//!
+//! Writes are appended to a local, segmented write-ahead log (one JSON record per line,
+//! fsynced before the write returns) and acknowledged immediately. A background flusher
+//! drains the log into the wrapped repository in per-tenant batches, retrying with
+//! backoff until storage accepts them. Full segments are deleted once stored; the active
+//! one is drained in place and started over once caught up. Records storage rejects for
+//! good (bad data rather than an outage) go to a dead-letter file instead of being retried.
+//! Segments left on disk by a previous run are replayed on startup.
+//!
+//! Delivery is at-least-once: a crash replays what is still on disk, including records
+//! already stored from it. Reads go straight to the wrapped repository, so records still
+//! in the log are not visible to queries until flushed.
+//!
+//! When the log holds more than [`WalConfig::max_bytes`], writes fail with
+//! [`StorageBackpressure`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use rustpulse::adapters::output::jsonl_repo::JsonlTelemetryRepo;
+//! use rustpulse::adapters::output::wal_repo::{WalConfig, WalTelemetryRepo};
+//! use std::sync::Arc;
+//!
+//! let inner = Arc::new(JsonlTelemetryRepo::new("metrics.jsonl"));
+//! let wal = Arc::new(WalTelemetryRepo::open("wal", inner, WalConfig::default())?);
+//! let _flusher = wal.clone().spawn_flusher();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::application::health::DependencyProbe;
+use crate::core::application::telemetry::{
+    AggregateBucket, AggregateQuery, StorageBackpressure, TelemetryPage, TelemetryQuery,
+    TelemetryRepository, TelemetryStream,
+};
+use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+
+const SEGMENT_PREFIX: &str = "wal-";
+const SEGMENT_SUFFIX: &str = ".log";
+const DEAD_LETTER_FILE: &str = "dead-letter.log";
+const MAX_FLUSH_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+/// Sizing and pacing of a [`WalTelemetryRepo`].
+pub struct WalConfig {
+    /// Log size above which writes are rejected with [`StorageBackpressure`].
+    pub max_bytes: u64,
+    /// Size at which the active segment is sealed and a new one started.
+    pub segment_bytes: u64,
+    /// Most records handed to the wrapped repository in one `save_batch` call.
+    pub batch_size: usize,
+    /// Longest a record waits in the log before a flush is attempted.
+    pub flush_interval: Duration,
+}
+
+impl Default for WalConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            segment_bytes: 8 * 1024 * 1024,
+            batch_size: 1000,
+            flush_interval: Duration::from_millis(200),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WalRecord {
+    tenant: TenantId,
+    telemetry: Telemetry,
+}
+
+struct ActiveSegment {
+    seq: u64,
+    // Shared with the blocking task writing to it.
+    file: Arc<File>,
+    bytes: u64,
+}
+
+/// What has been stored from one segment.
+#[derive(Default)]
+struct Progress {
+    records: usize,
+    bytes: u64,
+}
+
+struct WalState {
+    next_seq: u64,
+    active: Option<ActiveSegment>,
+    total_bytes: u64,
+}
+
+/// [`TelemetryRepository`] decorator that acknowledges writes once they are in a local
+/// write-ahead log and stores them in the wrapped repository asynchronously.
+pub struct WalTelemetryRepo {
+    dir: PathBuf,
+    inner: Arc<dyn TelemetryRepository + Send + Sync>,
+    config: WalConfig,
+    state: Mutex<WalState>,
+    // Per segment, so a retried flush does not store records twice.
+    flushed: Mutex<HashMap<u64, Progress>>,
+    appended: Notify,
+}
+
+fn segment_path(dir: &Path, seq: u64) -> PathBuf {
+    dir.join(format!("{SEGMENT_PREFIX}{seq:020}{SEGMENT_SUFFIX}"))
+}
+
+/// Sequence numbers of the segments in `dir`, oldest first.
+fn list_segments(dir: &Path) -> io::Result<Vec<u64>> {
+    let mut seqs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let seq = name
+            .to_str()
+            .and_then(|n| n.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|n| n.strip_suffix(SEGMENT_SUFFIX))
+            .and_then(|n| n.parse::<u64>().ok());
+        seqs.extend(seq);
+    }
+    seqs.sort_unstable();
+    Ok(seqs)
+}
+
+/// Reads the first `limit` bytes of a segment with each record's size in bytes, skipping
+/// lines torn by a crash mid-append.
+fn read_segment(path: &Path, limit: u64) -> io::Result<Vec<(WalRecord, u64)>> {
+    let mut reader = BufReader::new(File::open(path)?.take(limit));
+    let mut records = Vec::new();
+    let mut line = Vec::new();
+    for index in 1.. {
+        line.clear();
+        let len = reader.read_until(b'\n', &mut line)?;
+        if len == 0 {
+            break;
+        }
+        match serde_json::from_slice::<WalRecord>(&line) {
+            Ok(record) => records.push((record, len as u64)),
+            Err(e) => tracing::warn!(
+                segment = %path.display(),
+                line = index,
+                error = %e,
+                "wal.record.skipped"
+            ),
+        }
+    }
+    Ok(records)
+}
+
+/// Appends `lines` to `file`, which holds `offset` bytes, and fsyncs it.
+///
+/// On failure the file is cut back to `offset`, so a retry cannot replay part of it twice.
+fn write_lines(mut file: &File, offset: u64, lines: &[u8]) -> anyhow::Result<()> {
+    if let Err(e) = file.write_all(lines).and_then(|()| file.sync_data()) {
+        if let Err(truncate) = file.set_len(offset) {
+            // Not an io::Error, so the batch is not retried on top of its partial copy.
+            anyhow::bail!("append failed ({e}) and could not be rolled back: {truncate}");
+        }
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Whether storage rejected records for what they contain, so retrying cannot succeed.
+fn is_permanent(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause.is::<serde_json::Error>()
+            || cause
+                .downcast_ref::<sqlx::Error>()
+                .is_some_and(|e| match e {
+                    // Constraint violations, and Postgres class 22 (data exception).
+                    sqlx::Error::Database(db) => {
+                        db.kind() != sqlx::error::ErrorKind::Other
+                            || db.code().is_some_and(|code| code.starts_with("22"))
+                    }
+                    sqlx::Error::Encode(_) => true,
+                    _ => false,
+                })
+    })
+}
+
+impl WalTelemetryRepo {
+    /// Opens (or creates) the log in `dir`; segments already there are replayed by the flusher.
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        inner: Arc<dyn TelemetryRepository + Send + Sync>,
+        config: WalConfig,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let segments = list_segments(&dir)?;
+        let mut total_bytes = 0;
+        for seq in &segments {
+            total_bytes += fs::metadata(segment_path(&dir, *seq))?.len();
+        }
+        if !segments.is_empty() {
+            tracing::info!(
+                segments = segments.len(),
+                bytes = total_bytes,
+                "wal.replay.pending"
+            );
+        }
+        metrics::gauge!("rustpulse_wal_backlog_bytes").set(total_bytes as f64);
+
+        Ok(Self {
+            dir,
+            inner,
+            config,
+            state: Mutex::new(WalState {
+                next_seq: segments.last().map_or(0, |seq| seq + 1),
+                active: None,
+                total_bytes,
+            }),
+            flushed: Mutex::new(HashMap::new()),
+            appended: Notify::new(),
+        })
+    }
+
+    /// Bytes currently held in the log.
+    pub async fn backlog_bytes(&self) -> u64 {
+        self.state.lock().await.total_bytes
+    }
+
+    async fn append(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        let mut lines = String::new();
+        for telemetry in batch {
+            lines.push_str(&serde_json::to_string(&WalRecord {
+                tenant: tenant.clone(),
+                telemetry,
+            })?);
+            lines.push('\n');
+        }
+        let len = lines.len() as u64;
+
+        let mut state = self.state.lock().await;
+        if state.total_bytes + len > self.config.max_bytes {
+            return Err(StorageBackpressure {
+                retry_after_secs: self.config.flush_interval.as_secs().max(1),
+            }
+            .into());
+        }
+
+        let (seq, file, offset) = match &state.active {
+            Some(active) if active.bytes < self.config.segment_bytes => {
+                (active.seq, Some(active.file.clone()), active.bytes)
+            }
+            _ => (state.next_seq, None, 0),
+        };
+        let path = segment_path(&self.dir, seq);
+        // The state lock is held across the fsync, which keeps appends in order.
+        let written = tokio::task::spawn_blocking(move || {
+            let file = match file {
+                Some(file) => file,
+                None => Arc::new(OpenOptions::new().create(true).append(true).open(path)?),
+            };
+            write_lines(&file, offset, lines.as_bytes())?;
+            anyhow::Ok(file)
+        })
+        .await?;
+
+        match (written, state.active.as_mut()) {
+            (Ok(_), Some(active)) if active.seq == seq => active.bytes += len,
+            (Ok(file), _) => {
+                state.next_seq = seq + 1;
+                state.active = Some(ActiveSegment {
+                    seq,
+                    file,
+                    bytes: len,
+                });
+            }
+            (Err(e), _) => {
+                // Never append after a failed write.
+                if state
+                    .active
+                    .as_ref()
+                    .is_some_and(|active| active.seq == seq)
+                {
+                    state.active = None;
+                }
+                return Err(e);
+            }
+        }
+        state.total_bytes += len;
+        metrics::gauge!("rustpulse_wal_backlog_bytes").set(state.total_bytes as f64);
+        drop(state);
+
+        self.appended.notify_one();
+        Ok(())
+    }
+
+    /// Stores every record of the log in the wrapped repository.
+    ///
+    /// Returns the number of records stored. On error, what was stored so far is
+    /// remembered and the next call resumes after it.
+    pub async fn flush(&self) -> anyhow::Result<usize> {
+        let mut flushed = self.flushed.lock().await;
+
+        let (active, next_seq) = {
+            let state = self.state.lock().await;
+            let active = state.active.as_ref().map(|a| (a.seq, a.bytes));
+            (active, state.next_seq)
+        };
+
+        let dir = self.dir.clone();
+        let seqs = tokio::task::spawn_blocking(move || list_segments(&dir)).await??;
+        let mut stored = 0;
+        for seq in seqs.into_iter().filter(|seq| *seq < next_seq) {
+            let path = segment_path(&self.dir, seq);
+            // The active segment is drained in place, up to what it held when the flush began.
+            let limit = active
+                .filter(|(active_seq, _)| *active_seq == seq)
+                .map(|(_, bytes)| bytes);
+            let read_path = path.clone();
+            let records = tokio::task::spawn_blocking(move || {
+                read_segment(&read_path, limit.unwrap_or(u64::MAX))
+            })
+            .await??;
+            let progress = flushed.entry(seq).or_default();
+
+            // Consecutive records of one tenant, at most `batch_size` at a time.
+            let mut pending = records.into_iter().skip(progress.records).peekable();
+            while let Some((first, len)) = pending.next() {
+                let tenant = first.tenant;
+                let mut batch = vec![(first.telemetry, len)];
+                while batch.len() < self.config.batch_size.max(1)
+                    && let Some((next, len)) = pending.next_if(|(r, _)| r.tenant == tenant)
+                {
+                    batch.push((next.telemetry, len));
+                }
+                stored += self.store(&tenant, batch, progress).await?;
+            }
+
+            if limit.is_none() {
+                let unaccounted = progress.bytes;
+                let len = tokio::task::spawn_blocking(move || {
+                    let len = fs::metadata(&path)?.len();
+                    fs::remove_file(&path).map(|()| len)
+                })
+                .await??;
+                flushed.remove(&seq);
+                // Lines skipped as torn were never counted as stored.
+                self.release(len.saturating_sub(unaccounted)).await;
+            }
+        }
+
+        // Caught up with nothing appended since: start the segment over rather than sealing it.
+        if let Some((seq, bytes)) = active
+            && bytes > 0
+            && flushed.get(&seq).is_some_and(|p| p.bytes == bytes)
+        {
+            let mut state = self.state.lock().await;
+            if let Some(current) = state.active.as_mut()
+                && current.seq == seq
+                && current.bytes == bytes
+            {
+                let file = current.file.clone();
+                match tokio::task::spawn_blocking(move || file.set_len(0)).await? {
+                    Ok(()) => {
+                        current.bytes = 0;
+                        flushed.remove(&seq);
+                    }
+                    Err(e) => {
+                        // Sealed instead: the next flush finds it fully stored and deletes it.
+                        tracing::warn!(error = %e, "wal.segment.reset failed");
+                        state.active = None;
+                    }
+                }
+            }
+        }
+
+        Ok(stored)
+    }
+
+    /// Stores one batch of `tenant`'s records (with their size in the log), advancing `progress`.
+    ///
+    /// Records rejected for good are dead-lettered; only transient failures are returned.
+    async fn store(
+        &self,
+        tenant: &TenantId,
+        batch: Vec<(Telemetry, u64)>,
+        progress: &mut Progress,
+    ) -> anyhow::Result<usize> {
+        let records: Vec<Telemetry> = batch.iter().map(|(t, _)| t.clone()).collect();
+        let err = match self.inner.save_batch(tenant, records.clone()).await {
+            Ok(()) => {
+                self.advance(progress, &batch).await;
+                metrics::counter!("rustpulse_wal_flushed_records_total")
+                    .increment(batch.len() as u64);
+                return Ok(batch.len());
+            }
+            Err(e) if is_permanent(&e) => e,
+            Err(e) => return Err(e),
+        };
+
+        // A non-atomic batch may be partly stored already; storing its records again
+        // one by one would duplicate them.
+        if batch.len() == 1 || !self.inner.atomic_batches() {
+            self.dead_letter(tenant, records, &err).await?;
+            self.advance(progress, &batch).await;
+            return Ok(0);
+        }
+
+        // Isolate the offending records so the rest of the batch is still stored.
+        let mut stored = 0;
+        for record in batch {
+            match self.inner.save(tenant, record.0.clone()).await {
+                Ok(()) => {
+                    stored += 1;
+                    metrics::counter!("rustpulse_wal_flushed_records_total").increment(1);
+                }
+                Err(e) if is_permanent(&e) => {
+                    self.dead_letter(tenant, vec![record.0.clone()], &e).await?
+                }
+                Err(e) => return Err(e),
+            }
+            self.advance(progress, std::slice::from_ref(&record)).await;
+        }
+        Ok(stored)
+    }
+
+    /// Records `batch` as done with its segment and drops it from the backlog.
+    async fn advance(&self, progress: &mut Progress, batch: &[(Telemetry, u64)]) {
+        let bytes: u64 = batch.iter().map(|(_, len)| len).sum();
+        progress.records += batch.len();
+        progress.bytes += bytes;
+        self.release(bytes).await;
+    }
+
+    async fn release(&self, bytes: u64) {
+        let mut state = self.state.lock().await;
+        state.total_bytes = state.total_bytes.saturating_sub(bytes);
+        metrics::gauge!("rustpulse_wal_backlog_bytes").set(state.total_bytes as f64);
+    }
+
+    /// Appends records storage rejected for good to the dead-letter file, in log format.
+    async fn dead_letter(
+        &self,
+        tenant: &TenantId,
+        records: Vec<Telemetry>,
+        err: &anyhow::Error,
+    ) -> anyhow::Result<()> {
+        let count = records.len();
+        let mut lines = String::new();
+        for telemetry in records {
+            lines.push_str(&serde_json::to_string(&WalRecord {
+                tenant: tenant.clone(),
+                telemetry,
+            })?);
+            lines.push('\n');
+        }
+
+        let path = self.dir.join(DEAD_LETTER_FILE);
+        tokio::task::spawn_blocking(move || {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            let offset = file.metadata()?.len();
+            write_lines(&file, offset, lines.as_bytes())
+        })
+        .await??;
+
+        tracing::error!(
+            tenant = %tenant,
+            records = count,
+            error = format!("{err:#}"),
+            "wal.dead_letter"
+        );
+        metrics::counter!("rustpulse_wal_dead_lettered_records_total").increment(count as u64);
+        Ok(())
+    }
+
+    /// Starts the background task that drains the log until the process exits.
+    ///
+    /// Flushes after every [`WalConfig::flush_interval`] (sooner when records arrive) and
+    /// backs off exponentially, up to 30 s, while the wrapped repository keeps failing.
+    pub fn spawn_flusher(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut backoff = self.config.flush_interval;
+            loop {
+                tokio::select! {
+                    _ = self.appended.notified() => {}
+                    _ = tokio::time::sleep(self.config.flush_interval) => {}
+                }
+                // Let a burst of appends be stored in the same batches.
+                tokio::time::sleep(self.config.flush_interval).await;
+
+                match self.flush().await {
+                    Ok(stored) => {
+                        if stored > 0 {
+                            tracing::info!(records = stored, "wal.flush");
+                        }
+                        backoff = self.config.flush_interval;
+                    }
+                    Err(e) => {
+                        metrics::counter!("rustpulse_wal_flush_failures_total").increment(1);
+                        tracing::warn!(
+                            error = %e,
+                            backoff_ms = backoff.as_millis(),
+                            "wal.flush failed"
+                        );
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(MAX_FLUSH_BACKOFF);
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TelemetryRepository for WalTelemetryRepo {
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+        self.append(tenant, vec![telemetry]).await
+    }
+
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        self.append(tenant, batch).await
+    }
+
+    fn atomic_batches(&self) -> bool {
+        true
+    }
+
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
+        self.inner.query_all(tenant, node_id).await
+    }
+
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        self.inner.query_page(tenant, query).await
+    }
+
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        self.inner.query_stream(tenant, query).await
+    }
+
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        self.inner.aggregate(tenant, query).await
+    }
+}
+
+#[async_trait::async_trait]
+impl DependencyProbe for WalTelemetryRepo {
+    fn name(&self) -> &'static str {
+        "wal"
+    }
+
+    /// A full log only rejects writes; reads still work, so it is not critical.
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn probe(&self) -> anyhow::Result<()> {
+        let backlog = self.backlog_bytes().await;
+        if backlog >= self.config.max_bytes {
+            anyhow::bail!("write-ahead log is full ({backlog} bytes)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use std::sync::Mutex as StdMutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingRepo {
+        saved: StdMutex<Vec<(TenantId, f64)>>,
+        calls: AtomicUsize,
+        // 1-based `save_batch` call that fails; 0 never fails.
+        fail_call: AtomicUsize,
+        // Records with this cpu are rejected as invalid data.
+        poison_cpu: Option<f64>,
+    }
+
+    #[async_trait::async_trait]
+    impl TelemetryRepository for RecordingRepo {
+        async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
+            self.save_batch(tenant, vec![telemetry]).await
+        }
+
+        async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call == self.fail_call.load(Ordering::SeqCst) {
+                anyhow::bail!("database unavailable");
+            }
+            if batch.iter().any(|t| t.cpu == self.poison_cpu) {
+                let invalid = serde_json::from_str::<f64>("poison").unwrap_err();
+                return Err(anyhow::Error::new(invalid).context("invalid record"));
+            }
+            let mut saved = self.saved.lock().unwrap();
+            saved.extend(batch.iter().map(|t| (tenant.clone(), t.cpu.unwrap())));
+            Ok(())
+        }
+
+        fn atomic_batches(&self) -> bool {
+            true
+        }
+
+        async fn query_all(
+            &self,
+            _tenant: &TenantId,
+            _node_id: Option<String>,
+        ) -> anyhow::Result<Vec<Telemetry>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn telemetry(cpu: f64) -> Telemetry {
+        Telemetry {
+            source_id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            timestamp: Utc::now(),
+            cpu: Some(cpu),
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("rustpulse-wal-{}", Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_flush_stores_records_per_tenant_in_order_and_empties_the_log() {
+        let dir = temp_dir();
+        let inner = Arc::new(RecordingRepo::default());
+        let wal = WalTelemetryRepo::open(&dir, inner.clone(), WalConfig::default()).unwrap();
+        let acme = TenantId::parse("acme").unwrap();
+
+        wal.save(&TenantId::default(), telemetry(1.0))
+            .await
+            .unwrap();
+        wal.save_batch(&acme, vec![telemetry(2.0), telemetry(3.0)])
+            .await
+            .unwrap();
+        assert!(inner.saved.lock().unwrap().is_empty());
+
+        assert_eq!(wal.flush().await.unwrap(), 3);
+        assert_eq!(
+            *inner.saved.lock().unwrap(),
+            vec![(TenantId::default(), 1.0), (acme.clone(), 2.0), (acme, 3.0)]
+        );
+        assert_eq!(wal.backlog_bytes().await, 0);
+        assert_eq!(list_segments(&dir).unwrap(), vec![0]);
+        assert_eq!(fs::metadata(segment_path(&dir, 0)).unwrap().len(), 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flushes_reuse_the_active_segment() {
+        let dir = temp_dir();
+        let inner = Arc::new(RecordingRepo::default());
+        let wal = WalTelemetryRepo::open(&dir, inner.clone(), WalConfig::default()).unwrap();
+        let tenant = TenantId::default();
+
+        for cpu in [1.0, 2.0, 3.0] {
+            wal.save(&tenant, telemetry(cpu)).await.unwrap();
+            assert_eq!(wal.flush().await.unwrap(), 1);
+        }
+        // Appended after the flush read the segment: stored by the next one, not twice.
+        wal.save(&tenant, telemetry(4.0)).await.unwrap();
+        wal.save(&tenant, telemetry(5.0)).await.unwrap();
+        assert_eq!(wal.flush().await.unwrap(), 2);
+
+        let cpus: Vec<f64> = inner.saved.lock().unwrap().iter().map(|s| s.1).collect();
+        assert_eq!(cpus, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(list_segments(&dir).unwrap(), vec![0]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_records_are_dead_lettered_not_retried() {
+        let dir = temp_dir();
+        let inner = Arc::new(RecordingRepo {
+            poison_cpu: Some(2.0),
+            ..Default::default()
+        });
+        let wal = WalTelemetryRepo::open(&dir, inner.clone(), WalConfig::default()).unwrap();
+        let tenant = TenantId::default();
+
+        wal.save_batch(
+            &tenant,
+            vec![telemetry(1.0), telemetry(2.0), telemetry(3.0)],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(wal.flush().await.unwrap(), 2);
+        assert_eq!(wal.flush().await.unwrap(), 0);
+        let cpus: Vec<f64> = inner.saved.lock().unwrap().iter().map(|s| s.1).collect();
+        assert_eq!(cpus, vec![1.0, 3.0]);
+        assert_eq!(wal.backlog_bytes().await, 0);
+
+        let dead = read_segment(&dir.join(DEAD_LETTER_FILE), u64::MAX).unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].0.telemetry.cpu, Some(2.0));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_resumes_without_storing_twice() {
+        let dir = temp_dir();
+        let inner = Arc::new(RecordingRepo::default());
+        let config = WalConfig {
+            batch_size: 1,
+            ..Default::default()
+        };
+        let wal = WalTelemetryRepo::open(&dir, inner.clone(), config).unwrap();
+        let tenant = TenantId::default();
+
+        wal.save_batch(
+            &tenant,
+            vec![telemetry(1.0), telemetry(2.0), telemetry(3.0)],
+        )
+        .await
+        .unwrap();
+
+        // The first batch is stored, the second fails.
+        inner.fail_call.store(2, Ordering::SeqCst);
+        assert!(wal.flush().await.is_err());
+        assert_eq!(wal.flush().await.unwrap(), 2);
+
+        let cpus: Vec<f64> = inner.saved.lock().unwrap().iter().map(|s| s.1).collect();
+        assert_eq!(cpus, vec![1.0, 2.0, 3.0]);
+        assert_eq!(wal.backlog_bytes().await, 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unflushed_segments_are_replayed_after_restart() {
+        let dir = temp_dir();
+        {
+            let wal = WalTelemetryRepo::open(
+                &dir,
+                Arc::new(RecordingRepo::default()),
+                WalConfig::default(),
+            )
+            .unwrap();
+            wal.save(&TenantId::default(), telemetry(7.0))
+                .await
+                .unwrap();
+        }
+
+        let inner = Arc::new(RecordingRepo::default());
+        let wal = WalTelemetryRepo::open(&dir, inner.clone(), WalConfig::default()).unwrap();
+        assert!(wal.backlog_bytes().await > 0);
+
+        wal.save(&TenantId::default(), telemetry(8.0))
+            .await
+            .unwrap();
+        assert_eq!(wal.flush().await.unwrap(), 2);
+        let cpus: Vec<f64> = inner.saved.lock().unwrap().iter().map(|s| s.1).collect();
+        assert_eq!(cpus, vec![7.0, 8.0]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_full_log_rejects_writes_with_backpressure() {
+        let dir = temp_dir();
+        let config = WalConfig {
+            max_bytes: 300,
+            ..Default::default()
+        };
+        let wal = WalTelemetryRepo::open(&dir, Arc::new(RecordingRepo::default()), config).unwrap();
+
+        wal.save(&TenantId::default(), telemetry(1.0))
+            .await
+            .unwrap();
+        let err = wal
+            .save(&TenantId::default(), telemetry(2.0))
+            .await
+            .unwrap_err();
+        assert!(err.is::<StorageBackpressure>());
+        assert!(wal.probe().await.is_ok());
+
+        wal.flush().await.unwrap();
+        wal.save(&TenantId::default(), telemetry(2.0))
+            .await
+            .unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+//! Sources of records.
+//!
+//! Each configured collector runs on its own interval and every reading becomes one record.
+//! Besides the built-in host collector, `command` and `file` collectors read JSON produced by
+//! any other program, so new metrics need no change to the agent.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+
+use crate::config::CollectorConfig;
+use crate::host::HostCollector;
+
+/// One reading of a collector.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Reading {
+    /// CPU usage, in percent.
+    pub cpu: Option<f64>,
+    /// Memory usage, in percent.
+    pub memory: Option<f64>,
+    /// Temperature, degrees Celsius.
+    pub temperature: Option<f32>,
+    /// Everything else.
+    pub extras: Map<String, Value>,
+}
+
+/// A source of readings.
+#[async_trait]
+pub trait Collector: Send {
+    /// Name used in logs and as `extras.collector`.
+    fn name(&self) -> &str;
+
+    /// Takes one reading.
+    async fn collect(&mut self) -> anyhow::Result<Reading>;
+}
+
+/// Builds the collector described by `config`.
+pub fn build(config: &CollectorConfig) -> Box<dyn Collector> {
+    match config {
+        CollectorConfig::Host { root, .. } => Box::new(HostCollector::new(root.clone())),
+        CollectorConfig::Command {
+            name,
+            command,
+            timeout_ms,
+            ..
+        } => Box::new(CommandCollector {
+            name: name.clone(),
+            command: command.clone(),
+            timeout: Duration::from_millis(*timeout_ms),
+        }),
+        CollectorConfig::File { name, path, .. } => Box::new(FileCollector {
+            name: name.clone(),
+            path: path.clone(),
+        }),
+    }
+}
+
+/// Runs a program on every reading and parses its standard output with [`parse_output`].
+pub struct CommandCollector {
+    name: String,
+    command: Vec<String>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl Collector for CommandCollector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn collect(&mut self) -> anyhow::Result<Reading> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("empty command"))?;
+        let child = tokio::process::Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {:?}", self.timeout))??;
+        if !output.status.success() {
+            anyhow::bail!(
+                "exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(parse_output(
+            &self.name,
+            &String::from_utf8_lossy(&output.stdout),
+        ))
+    }
+}
+
+/// Reads a file on every reading and parses it with [`parse_output`].
+pub struct FileCollector {
+    name: String,
+    path: PathBuf,
+}
+
+#[async_trait]
+impl Collector for FileCollector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn collect(&mut self) -> anyhow::Result<Reading> {
+        let text = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", self.path.display()))?;
+        Ok(parse_output(&self.name, &text))
+    }
+}
+
+/// Turns what a command printed or a file holds into a reading.
+///
+/// A JSON object is merged into `extras`, except numeric `cpu`, `memory` and `temperature`,
+/// which fill the typed fields. Any other JSON value, or text that is not JSON, is stored as
+/// `extras.<name>`.
+pub fn parse_output(name: &str, text: &str) -> Reading {
+    let mut reading = Reading::default();
+    match serde_json::from_str::<Value>(text.trim()) {
+        Ok(Value::Object(mut object)) => {
+            let mut number = |key: &str| match object.get(key) {
+                Some(Value::Number(value)) => {
+                    let value = value.as_f64();
+                    object.remove(key);
+                    value
+                }
+                _ => None,
+            };
+            reading.cpu = number("cpu");
+            reading.memory = number("memory");
+            reading.temperature = number("temperature").map(|celsius| celsius as f32);
+            reading.extras = object;
+        }
+        Ok(value) => {
+            reading.extras.insert(name.to_string(), value);
+        }
+        Err(_) => {
+            reading
+                .extras
+                .insert(name.to_string(), Value::from(text.trim()));
+        }
+    }
+    reading
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_output_maps_typed_fields_and_extras() {
+        let reading = parse_output("ups", r#"{"cpu": 12.5, "temperature": 40, "battery": 97}"#);
+        assert_eq!(reading.cpu, Some(12.5));
+        assert_eq!(reading.memory, None);
+        assert_eq!(reading.temperature, Some(40.0));
+        assert_eq!(Value::Object(reading.extras), json!({"battery": 97}));
+
+        let reading = parse_output("ups", r#"{"cpu": "high"}"#);
+        assert_eq!(reading.cpu, None);
+        assert_eq!(Value::Object(reading.extras), json!({"cpu": "high"}));
+
+        let reading = parse_output("queue_depth", "42\n");
+        assert_eq!(Value::Object(reading.extras), json!({"queue_depth": 42}));
+        let reading = parse_output("status", "all good\n");
+        assert_eq!(Value::Object(reading.extras), json!({"status": "all good"}));
+    }
+
+    #[tokio::test]
+    async fn test_command_and_file_collectors() {
+        let command = |script: &str, timeout_ms| CollectorConfig::Command {
+            name: "script".to_string(),
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            interval_ms: None,
+            timeout_ms,
+        };
+
+        let mut ok = build(&command(r#"echo '{"battery": 97}'"#, 5_000));
+        assert_eq!(ok.name(), "script");
+        assert_eq!(
+            Value::Object(ok.collect().await.unwrap().extras),
+            json!({"battery": 97})
+        );
+        let failing = build(&command("echo broken >&2; exit 3", 5_000))
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(failing.to_string().contains("broken"), "{failing}");
+        assert!(
+            build(&command("sleep 5", 50))
+                .collect()
+                .await
+                .unwrap_err()
+                .to_string()
+                .contains("timed out")
+        );
+
+        let path = std::env::temp_dir().join(format!("rustpulse-agent-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"{"memory": 50}"#).unwrap();
+        let mut file = build(&CollectorConfig::File {
+            name: "battery".to_string(),
+            path: path.clone(),
+            interval_ms: None,
+        });
+        let reading = file.collect().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reading.memory, Some(50.0));
+        assert!(file.collect().await.is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Agent settings: an optional TOML file, overridden by the environment variables the agent has
+//! always read.
+//!
+//! See `agent.example.toml` for every key.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+const DEFAULT_URL: &str = "http://127.0.0.1:3000/telemetry";
+const DEFAULT_CONFIG_FILE: &str = "agent.toml";
+/// The backend accepts at most this many items per batch.
+const MAX_BATCH_SIZE: usize = 5000;
+
+/// The whole agent configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentConfig {
+    /// Free-form label sent as `extras.host_id`.
+    pub host_id: String,
+    /// Records to take before exiting; `0` runs forever.
+    pub count: u64,
+    /// Interval of collectors that do not set their own.
+    pub interval_ms: u64,
+    /// After `count` records, how long to keep emptying the spools before exiting.
+    pub drain_timeout_s: u64,
+    /// Where the agent keeps its identity.
+    pub state_file: PathBuf,
+    /// Merged into the `extras` of every record, over what collectors report.
+    pub labels: serde_json::Map<String, serde_json::Value>,
+    /// Shared spool settings.
+    pub spool: SpoolConfig,
+    /// Backends every record is sent to.
+    pub outputs: Vec<OutputConfig>,
+    /// Sources of records.
+    pub collectors: Vec<CollectorConfig>,
+}
+
+/// Offline spool settings, shared by all outputs.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpoolConfig {
+    /// Parent directory of the per-output spools.
+    pub dir: PathBuf,
+    /// Size limit of each output's spool; the oldest records are dropped beyond it.
+    pub max_bytes: u64,
+    /// Records per batch when draining.
+    pub batch_size: usize,
+    /// Longest wait between retries when the backend sends no `Retry-After`.
+    pub max_backoff_s: u64,
+}
+
+/// One backend.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Name used in logs and as the spool subdirectory.
+    #[serde(default = "default_output_name")]
+    pub name: String,
+    /// `POST /telemetry` endpoint.
+    pub url: String,
+    /// `POST /telemetry/batch` endpoint; `<url>/batch` when unset.
+    #[serde(default)]
+    pub batch_url: Option<String>,
+    /// Bearer token or API key with the `telemetry:write` scope.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// File holding the token, read at startup; keeps the secret out of this file.
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+    /// Spool of this output; `<spool.dir>/<name>` when unset.
+    #[serde(default)]
+    pub spool_dir: Option<PathBuf>,
+    /// `Content-Encoding` of request bodies.
+    #[serde(default)]
+    pub compression: Compression,
+    /// Id of the key requests are signed with; unset sends unsigned requests.
+    #[serde(default)]
+    pub signing_key_id: Option<String>,
+    /// Secret of the signing key.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    /// File holding the signing secret, read at startup.
+    #[serde(default)]
+    pub signing_secret_file: Option<PathBuf>,
+}
+
+/// Key requests to an output are signed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    /// Sent as `X-Signature-Key-Id`.
+    pub id: String,
+    /// HMAC-SHA256 key.
+    pub secret: Vec<u8>,
+}
+
+/// Compression of request bodies sent to an output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    /// Bodies are sent as is.
+    #[default]
+    None,
+    /// `Content-Encoding: gzip`.
+    Gzip,
+    /// `Content-Encoding: zstd`.
+    Zstd,
+}
+
+impl std::str::FromStr for Compression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            other => anyhow::bail!("unknown compression `{other}`; expected none, gzip or zstd"),
+        }
+    }
+}
+
+/// One source of records; `type` selects the kind.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollectorConfig {
+    /// CPU, memory, load, disk, network and thermal zones from `/proc` and `/sys`.
+    Host {
+        /// Overrides the agent's `interval_ms`.
+        #[serde(default)]
+        interval_ms: Option<u64>,
+        /// Where `/proc` and `/sys` are mounted.
+        #[serde(default = "default_host_root")]
+        root: PathBuf,
+    },
+    /// Runs a program and reads its standard output.
+    Command {
+        /// Name used in logs and as `extras.collector`.
+        name: String,
+        /// Program and arguments; no shell is involved.
+        command: Vec<String>,
+        /// Overrides the agent's `interval_ms`.
+        #[serde(default)]
+        interval_ms: Option<u64>,
+        /// The program is killed past this.
+        #[serde(default = "default_command_timeout_ms")]
+        timeout_ms: u64,
+    },
+    /// Reads a file, e.g. one another process keeps up to date.
+    File {
+        /// Name used in logs and as `extras.collector`.
+        name: String,
+        /// File to read.
+        path: PathBuf,
+        /// Overrides the agent's `interval_ms`.
+        #[serde(default)]
+        interval_ms: Option<u64>,
+    },
+}
+
+fn default_output_name() -> String {
+    "backend".to_string()
+}
+
+fn default_host_root() -> PathBuf {
+    PathBuf::from("/")
+}
+
+fn default_command_timeout_ms() -> u64 {
+    5_000
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            host_id: "local-dev-machine".to_string(),
+            // Defaults are chosen so `cargo run --bin agent` "just works" as a demo:
+            // send 10 events, one per second.
+            count: 10,
+            interval_ms: 1000,
+            drain_timeout_s: 30,
+            state_file: PathBuf::from("agent-state.json"),
+            labels: Default::default(),
+            spool: SpoolConfig::default(),
+            outputs: Vec::new(),
+            collectors: Vec::new(),
+        }
+    }
+}
+
+impl Default for SpoolConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("agent-spool"),
+            max_bytes: 64 * 1024 * 1024,
+            batch_size: 500,
+            max_backoff_s: 300,
+        }
+    }
+}
+
+impl OutputConfig {
+    fn new(url: String) -> Self {
+        Self {
+            name: default_output_name(),
+            url,
+            batch_url: None,
+            token: None,
+            token_file: None,
+            spool_dir: None,
+            compression: Compression::None,
+            signing_key_id: None,
+            signing_secret: None,
+            signing_secret_file: None,
+        }
+    }
+
+    /// The batch endpoint.
+    pub fn batch_url(&self) -> String {
+        self.batch_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/batch", self.url.trim_end_matches('/')))
+    }
+
+    /// The token, read from `token_file` when that is set.
+    pub fn token(&self) -> anyhow::Result<Option<String>> {
+        match &self.token_file {
+            Some(path) => std::fs::read_to_string(path)
+                .map(|token| Some(token.trim().to_string()))
+                .map_err(|e| anyhow::anyhow!("cannot read token_file {}: {e}", path.display())),
+            None => Ok(self.token.clone()),
+        }
+    }
+
+    /// The signing key, with the secret read from `signing_secret_file` when that is set.
+    pub fn signing_key(&self) -> anyhow::Result<Option<SigningKey>> {
+        let Some(id) = &self.signing_key_id else {
+            return Ok(None);
+        };
+        let secret = match (&self.signing_secret_file, &self.signing_secret) {
+            (Some(path), _) => std::fs::read_to_string(path)
+                .map(|secret| secret.trim().to_string())
+                .map_err(|e| {
+                    anyhow::anyhow!("cannot read signing_secret_file {}: {e}", path.display())
+                })?,
+            (None, Some(secret)) => secret.clone(),
+            (None, None) => anyhow::bail!(
+                "output `{}` sets signing_key_id without signing_secret or signing_secret_file",
+                self.name
+            ),
+        };
+        Ok(Some(SigningKey {
+            id: id.clone(),
+            secret: secret.into_bytes(),
+        }))
+    }
+
+    /// This output's spool directory.
+    pub fn spool_dir(&self, spool: &SpoolConfig) -> PathBuf {
+        self.spool_dir
+            .clone()
+            .unwrap_or_else(|| spool.dir.join(&self.name))
+    }
+}
+
+impl CollectorConfig {
+    /// Interval of this collector, falling back to `default_ms`.
+    pub fn interval_ms(&self, default_ms: u64) -> u64 {
+        match self {
+            Self::Host { interval_ms, .. }
+            | Self::Command { interval_ms, .. }
+            | Self::File { interval_ms, .. } => interval_ms.unwrap_or(default_ms),
+        }
+    }
+}
+
+impl AgentConfig {
+    /// Reads `AGENT_CONFIG` (or `agent.toml` when present), then applies environment overrides.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = match std::env::var("AGENT_CONFIG") {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.exists()),
+        };
+        let config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.with_env(|key| std::env::var(key).ok())
+    }
+
+    fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read agent config {}: {e}", path.display()))?;
+        toml::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("invalid agent config {}: {e}", path.display()))
+    }
+
+    /// Applies the environment variables over the file, fills in the default output and
+    /// collector, and validates the result.
+    ///
+    /// `TELEMETRY_URL`, `TELEMETRY_BATCH_URL`, `AGENT_TOKEN`, `AGENT_COMPRESSION`,
+    /// `AGENT_SIGNING_KEY_ID` and `AGENT_SIGNING_SECRET` apply to the first output and
+    /// `HOST_ROOT` to the first host collector.
+    fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let number = |key: &str| -> anyhow::Result<Option<u64>> {
+            var(key)
+                .map(|raw| {
+                    raw.parse::<u64>()
+                        .map_err(|_| anyhow::anyhow!("{key} must be a non-negative integer"))
+                })
+                .transpose()
+        };
+
+        if let Some(host_id) = var("HOST_ID") {
+            self.host_id = host_id;
+        }
+        if let Some(state_file) = var("AGENT_STATE_FILE") {
+            self.state_file = PathBuf::from(state_file);
+        }
+        if let Some(dir) = var("AGENT_SPOOL_DIR") {
+            self.spool.dir = PathBuf::from(dir);
+        }
+        self.count = number("COUNT")?.unwrap_or(self.count);
+        self.interval_ms = number("INTERVAL_MS")?.unwrap_or(self.interval_ms);
+        self.drain_timeout_s = number("DRAIN_TIMEOUT_S")?.unwrap_or(self.drain_timeout_s);
+        self.spool.max_bytes = number("AGENT_SPOOL_MAX_BYTES")?.unwrap_or(self.spool.max_bytes);
+        self.spool.batch_size =
+            number("AGENT_BATCH_SIZE")?.map_or(self.spool.batch_size, |size| size as usize);
+        self.spool.max_backoff_s =
+            number("AGENT_MAX_BACKOFF_S")?.unwrap_or(self.spool.max_backoff_s);
+
+        if self.outputs.is_empty() {
+            self.outputs
+                .push(OutputConfig::new(DEFAULT_URL.to_string()));
+        }
+        let output = &mut self.outputs[0];
+        if let Some(url) = var("TELEMETRY_URL") {
+            output.url = url;
+        }
+        if let Some(batch_url) = var("TELEMETRY_BATCH_URL") {
+            output.batch_url = Some(batch_url);
+        }
+        if let Some(token) = var("AGENT_TOKEN") {
+            output.token = Some(token);
+            output.token_file = None;
+        }
+        if let Some(compression) = var("AGENT_COMPRESSION") {
+            output.compression = compression.parse()?;
+        }
+        if let Some(key_id) = var("AGENT_SIGNING_KEY_ID") {
+            output.signing_key_id = Some(key_id);
+        }
+        if let Some(secret) = var("AGENT_SIGNING_SECRET") {
+            output.signing_secret = Some(secret);
+            output.signing_secret_file = None;
+        }
+
+        if self.collectors.is_empty() {
+            self.collectors.push(CollectorConfig::Host {
+                interval_ms: None,
+                root: default_host_root(),
+            });
+        }
+        if let Some(root) = var("HOST_ROOT")
+            && let Some(CollectorConfig::Host {
+                root: host_root, ..
+            }) = self
+                .collectors
+                .iter_mut()
+                .find(|collector| matches!(collector, CollectorConfig::Host { .. }))
+        {
+            *host_root = PathBuf::from(root);
+        }
+
+        self.validate()?;
+        Ok(self)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if !(1..=MAX_BATCH_SIZE).contains(&self.spool.batch_size) {
+            anyhow::bail!("spool.batch_size must be between 1 and {MAX_BATCH_SIZE}");
+        }
+        let mut names = std::collections::HashSet::new();
+        for output in &self.outputs {
+            if !names.insert(output.name.as_str()) {
+                anyhow::bail!("output name `{}` is used twice", output.name);
+            }
+        }
+        for collector in &self.collectors {
+            if collector.interval_ms(self.interval_ms) == 0 {
+                anyhow::bail!("collector intervals must be greater than zero");
+            }
+            if let CollectorConfig::Command { name, command, .. } = collector
+                && command.is_empty()
+            {
+                anyhow::bail!("command collector `{name}` has an empty command");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_example_config_parses_and_env_overrides_it() {
+        let config: AgentConfig =
+            toml::from_str(include_str!("../../../agent.example.toml")).unwrap();
+        let config = config
+            .with_env(|key| match key {
+                "TELEMETRY_URL" => Some("http://backend:3000/telemetry".to_string()),
+                "AGENT_TOKEN" => Some("rpk_env".to_string()),
+                "COUNT" => Some("0".to_string()),
+                "HOST_ROOT" => Some("/host".to_string()),
+                "AGENT_COMPRESSION" => Some("gzip".to_string()),
+                "AGENT_SIGNING_KEY_ID" => Some("edge-01".to_string()),
+                "AGENT_SIGNING_SECRET" => Some("s3cret".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.count, 0);
+        assert_eq!(config.labels["site"], "lab");
+        assert_eq!(config.outputs.len(), 2);
+        assert_eq!(config.outputs[0].url, "http://backend:3000/telemetry");
+        assert_eq!(
+            config.outputs[0].token().unwrap().as_deref(),
+            Some("rpk_env")
+        );
+        assert_eq!(config.outputs[0].compression, Compression::Gzip);
+        assert_eq!(config.outputs[1].compression, Compression::Zstd);
+        assert_eq!(
+            config.outputs[0].signing_key().unwrap(),
+            Some(SigningKey {
+                id: "edge-01".to_string(),
+                secret: b"s3cret".to_vec(),
+            })
+        );
+        assert_eq!(config.outputs[1].signing_key_id.as_deref(), Some("edge-01"));
+        assert_eq!(
+            config.outputs[1].batch_url(),
+            "https://central.example.com/telemetry/batch"
+        );
+        assert_eq!(
+            config.outputs[1].spool_dir(&config.spool),
+            PathBuf::from("agent-spool/central")
+        );
+        assert_eq!(
+            config.collectors[0],
+            CollectorConfig::Host {
+                interval_ms: Some(5_000),
+                root: PathBuf::from("/host"),
+            }
+        );
+        assert_eq!(config.collectors[1].interval_ms(config.interval_ms), 30_000);
+    }
+
+    #[test]
+    fn test_empty_config_falls_back_to_defaults_and_typos_are_rejected() {
+        let config: AgentConfig = toml::from_str("").unwrap();
+        let config = config.with_env(no_env).unwrap();
+        assert_eq!(
+            config.outputs,
+            vec![OutputConfig::new(DEFAULT_URL.to_string())]
+        );
+        assert_eq!(
+            config.outputs[0].batch_url(),
+            format!("{DEFAULT_URL}/batch")
+        );
+        assert_eq!(config.collectors.len(), 1);
+
+        assert!(toml::from_str::<AgentConfig>("interval = 5").is_err());
+        assert!(toml::from_str::<AgentConfig>("[[collectors]]\ntype = \"snmp\"").is_err());
+
+        let zero: AgentConfig = toml::from_str(
+            "[[collectors]]\ntype = \"file\"\nname = \"f\"\npath = \"/tmp/x\"\ninterval_ms = 0",
+        )
+        .unwrap();
+        assert!(zero.with_env(no_env).is_err());
+        let bad_env = AgentConfig::default()
+            .with_env(|key| (key == "INTERVAL_MS").then(|| "soon".to_string()));
+        assert!(bad_env.is_err());
+    }
+}
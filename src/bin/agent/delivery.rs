@@ -0,0 +1,544 @@
+//! Posting records to the backend and deciding when to try again.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use std::io::Write;
+
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE, DATE, HeaderMap, RETRY_AFTER};
+use reqwest::{Client, Response, StatusCode, Url};
+use rustpulse::adapters::input::http::request_signing::sign;
+use rustpulse::adapters::input::http::telemetry_handler::crc32_ieee;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::config::{Compression, OutputConfig, SigningKey, SpoolConfig};
+use crate::spool::Spool;
+
+/// How the backend answered a request.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Stored (for a batch: at least one item was).
+    Accepted(StatusCode),
+    /// Refused for good (invalid payload); sending it again would not help.
+    Rejected(StatusCode),
+    /// Not delivered; try again after `retry_after` when the backend gave one.
+    Retry {
+        /// The backend's `Retry-After`.
+        retry_after: Option<Duration>,
+        /// Status or transport error, for the log.
+        reason: String,
+    },
+}
+
+/// Sends records to `POST /telemetry` and batches to `POST /telemetry/batch`.
+///
+/// Every request carries `X-CRC32` over the body exactly as sent, i.e. after compression,
+/// and is signed over the same bytes when the output has a signing key.
+pub struct Sender {
+    client: Client,
+    url: Url,
+    batch_url: Url,
+    token: Option<String>,
+    compression: Compression,
+    signing_key: Option<SigningKey>,
+    /// Seconds to add to the local clock when signing, learnt from the backend's `Date`
+    /// after a `stale_timestamp` answer.
+    clock_offset: AtomicI64,
+}
+
+impl Sender {
+    pub fn new(client: Client, config: &OutputConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            client,
+            url: Url::parse(&config.url)
+                .map_err(|e| anyhow::anyhow!("invalid url {}: {e}", config.url))?,
+            batch_url: Url::parse(&config.batch_url())
+                .map_err(|e| anyhow::anyhow!("invalid batch_url {}: {e}", config.batch_url()))?,
+            token: config.token()?,
+            compression: config.compression,
+            signing_key: config.signing_key()?,
+            clock_offset: AtomicI64::new(0),
+        })
+    }
+
+    /// Posts one record (a JSON object).
+    pub async fn send(&self, body: String) -> Outcome {
+        self.post(&self.url, body).await
+    }
+
+    /// Posts a JSON array of records.
+    pub async fn send_batch(&self, body: String) -> Outcome {
+        self.post(&self.batch_url, body).await
+    }
+
+    async fn post(&self, url: &Url, body: String) -> Outcome {
+        let body = match compress(self.compression, body.into_bytes()) {
+            Ok(body) => body,
+            Err(e) => {
+                return Outcome::Retry {
+                    retry_after: None,
+                    reason: format!("cannot compress body: {e}"),
+                };
+            }
+        };
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header("x-crc32", format!("{:08x}", crc32_ieee(&body)));
+        request = match self.compression {
+            Compression::None => request,
+            Compression::Gzip => request.header(CONTENT_ENCODING, "gzip"),
+            Compression::Zstd => request.header(CONTENT_ENCODING, "zstd"),
+        };
+        if let Some(key) = &self.signing_key {
+            // Signed at send time, so spooled records get a fresh timestamp and nonce.
+            let timestamp = Utc::now().timestamp() + self.clock_offset.load(Ordering::Relaxed);
+            let nonce = uuid::Uuid::new_v4().simple().to_string();
+            request = request
+                .header("x-signature-key-id", &key.id)
+                .header("x-signature-timestamp", timestamp.to_string())
+                .header(
+                    "x-signature",
+                    sign(&key.secret, "POST", url.path(), timestamp, &nonce, &body),
+                )
+                .header("x-signature-nonce", nonce);
+        }
+        let mut request = request.body(body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        match request.send().await {
+            Ok(response) if response.status() == StatusCode::UNAUTHORIZED => {
+                self.unauthorized(response).await
+            }
+            Ok(response) => classify(response.status(), response.headers()),
+            Err(e) => Outcome::Retry {
+                retry_after: None,
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
+impl Sender {
+    /// Holds the records after a `401`, naming the backend's error code in the reason.
+    ///
+    /// On `stale_timestamp` the signing clock is re-based on the backend's `Date`, so the
+    /// next attempt is signed with the backend's time even if the host clock is off.
+    async fn unauthorized(&self, response: Response) -> Outcome {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let code = response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body["code"].as_str().map(str::to_string))
+            .unwrap_or_default();
+        let hint = match code.as_str() {
+            "stale_timestamp" => match clock_offset(&headers) {
+                Some(offset) => {
+                    self.clock_offset.store(offset, Ordering::Relaxed);
+                    format!("signing clock is {offset:+}s off the backend; corrected")
+                }
+                None => "signing clock is off and the backend sent no Date".to_string(),
+            },
+            "replayed_nonce" => "nonce already seen; resending with a new one".to_string(),
+            _ => "check the output's token and signing key".to_string(),
+        };
+        Outcome::Retry {
+            retry_after: retry_after(&headers),
+            reason: format!("{status} {code}; {hint}"),
+        }
+    }
+}
+
+/// Seconds the backend's `Date` is ahead of the local clock.
+fn clock_offset(headers: &HeaderMap) -> Option<i64> {
+    let date = headers.get(DATE)?.to_str().ok()?;
+    let server = DateTime::parse_from_rfc2822(date).ok()?.timestamp();
+    Some(server - Utc::now().timestamp())
+}
+
+fn compress(compression: Compression, body: Vec<u8>) -> std::io::Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(body),
+        Compression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&body)?;
+            encoder.finish()
+        }
+        // One-shot compression sizes the frame window to the body, as the backend requires.
+        Compression::Zstd => zstd::bulk::compress(&body, 0),
+    }
+}
+
+/// `408`, `429` and `5xx` are worth retrying; so are `401` and `403`, which say nothing about
+/// the records and last only until the credentials are fixed. Any other error status is final.
+fn classify(status: StatusCode, headers: &HeaderMap) -> Outcome {
+    if status.is_success() {
+        Outcome::Accepted(status)
+    } else if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        Outcome::Retry {
+            retry_after: retry_after(headers),
+            reason: format!("{status}; check the output's token and signing key"),
+        }
+    } else if status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status.is_server_error()
+    {
+        Outcome::Retry {
+            retry_after: retry_after(headers),
+            reason: status.to_string(),
+        }
+    } else {
+        Outcome::Rejected(status)
+    }
+}
+
+/// `Retry-After` as delay-seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// One backend with its own spool and retry schedule.
+pub struct Output {
+    name: String,
+    sender: Sender,
+    spool: Spool,
+    backoff: Backoff,
+    /// While set, the backend is not contacted and new records go straight to the spool.
+    retry_at: Option<Instant>,
+}
+
+impl Output {
+    /// Opens the output's spool, reporting what a previous run left in it.
+    pub fn open(
+        client: Client,
+        config: &OutputConfig,
+        spool: &SpoolConfig,
+    ) -> anyhow::Result<Self> {
+        let dir = config.spool_dir(spool);
+        let output = Self {
+            name: config.name.clone(),
+            sender: Sender::new(client, config)?,
+            spool: Spool::open(&dir, spool.max_bytes, spool.batch_size)?,
+            backoff: Backoff::new(
+                Duration::from_secs(1),
+                Duration::from_secs(spool.max_backoff_s),
+            ),
+            retry_at: None,
+        };
+        if !output.spool.is_empty() {
+            println!(
+                "agent[{}]: {} records left in {} from a previous run",
+                output.name,
+                output.spool.len(),
+                dir.display()
+            );
+        }
+        Ok(output)
+    }
+
+    fn ready(&self) -> bool {
+        self.retry_at.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Delivers the records sent on `records` until the channel closes, then keeps emptying
+    /// the spool for at most `drain_timeout`.
+    ///
+    /// The spool is drained between records, so a long backlog or a slow backend never holds
+    /// up sampling. Spool errors are logged and the output carries on.
+    pub async fn run(mut self, mut records: mpsc::Receiver<String>, drain_timeout: Duration) {
+        loop {
+            let draining = !self.spool.is_empty();
+            let wake = self.retry_at.unwrap_or_else(Instant::now);
+            let result = tokio::select! {
+                biased;
+                record = records.recv() => match record {
+                    Some(record) => self.deliver(&record).await,
+                    None => break,
+                },
+                _ = tokio::time::sleep_until(wake), if draining => {
+                    self.drain_batch().await.map(drop)
+                }
+            };
+            if let Err(e) = result {
+                self.spool_failed(e);
+            }
+        }
+
+        if let Err(e) = self.flush(Instant::now() + drain_timeout).await {
+            eprintln!("agent[{}]: could not empty the spool: {e}", self.name);
+        }
+    }
+
+    /// Logs a spool error and waits before touching the spool again, so a full or failing
+    /// disk is not retried in a tight loop.
+    fn spool_failed(&mut self, error: anyhow::Error) {
+        let delay = self.backoff.next_delay(None);
+        self.retry_at = Some(Instant::now() + delay);
+        eprintln!(
+            "agent[{}]: spool error: {error}; retrying in {delay:?}",
+            self.name
+        );
+    }
+
+    /// Sends `record`, or spools it when the backend is unavailable or records are already
+    /// waiting (so records arrive in order).
+    async fn deliver(&mut self, record: &str) -> anyhow::Result<()> {
+        if !self.ready() || !self.spool.is_empty() {
+            self.spool_record(record)?;
+        } else {
+            match self.sender.send(record.to_string()).await {
+                Outcome::Accepted(status) => {
+                    self.backoff.reset();
+                    println!("agent[{}]: Status: {status}", self.name);
+                }
+                Outcome::Rejected(status) => {
+                    eprintln!(
+                        "agent[{}]: backend rejected record ({status}); dropping it",
+                        self.name
+                    );
+                }
+                Outcome::Retry {
+                    retry_after,
+                    reason,
+                } => {
+                    self.spool_record(record)?;
+                    let delay = self.backoff.next_delay(retry_after);
+                    self.retry_at = Some(Instant::now() + delay);
+                    eprintln!(
+                        "agent[{}]: backend unavailable ({reason}); retrying in {delay:?}",
+                        self.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps draining until the spool is empty or `deadline` passes.
+    async fn flush(&mut self, deadline: Instant) -> anyhow::Result<()> {
+        while !self.spool.is_empty() && Instant::now() < deadline {
+            if let Some(at) = self.retry_at {
+                tokio::time::sleep_until(at.min(deadline)).await;
+            }
+            if self.ready() {
+                while self.drain_batch().await? {}
+            }
+        }
+        if !self.spool.is_empty() {
+            println!(
+                "agent[{}]: {} records left in the spool; they will be sent on the next run",
+                self.name,
+                self.spool.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Sends the oldest spooled batch; `true` while more remain and the backend takes them.
+    async fn drain_batch(&mut self) -> anyhow::Result<bool> {
+        while let Some(batch) = self.spool.oldest()? {
+            if batch.records.is_empty() {
+                self.spool.ack(&batch)?;
+                continue;
+            }
+            match self.sender.send_batch(batch.body()).await {
+                Outcome::Accepted(_) => {
+                    self.backoff.reset();
+                    self.retry_at = None;
+                    println!(
+                        "agent[{}]: delivered {} spooled records",
+                        self.name,
+                        batch.records.len()
+                    );
+                }
+                Outcome::Rejected(status) => {
+                    eprintln!(
+                        "agent[{}]: backend rejected {} spooled records ({status}); dropping them",
+                        self.name,
+                        batch.records.len()
+                    );
+                }
+                Outcome::Retry {
+                    retry_after,
+                    reason,
+                } => {
+                    let delay = self.backoff.next_delay(retry_after);
+                    self.retry_at = Some(Instant::now() + delay);
+                    eprintln!(
+                        "agent[{}]: backend unavailable ({reason}); {} records spooled, retrying in {delay:?}",
+                        self.name,
+                        self.spool.len()
+                    );
+                    return Ok(false);
+                }
+            }
+            self.spool.ack(&batch)?;
+            return Ok(!self.spool.is_empty());
+        }
+        Ok(false)
+    }
+
+    fn spool_record(&mut self, record: &str) -> anyhow::Result<()> {
+        let dropped = self.spool.push(record)?;
+        if dropped > 0 {
+            eprintln!(
+                "agent[{}]: spool is full; dropped {dropped} oldest records",
+                self.name
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Exponential backoff between failed attempts.
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    failures: u32,
+}
+
+impl Backoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            failures: 0,
+        }
+    }
+
+    /// Delay before the next attempt after another failure.
+    ///
+    /// The backend's `Retry-After` wins when given. Otherwise the delay doubles from `base` up
+    /// to `max`, and a random half of it is taken off so agents cut off together do not all
+    /// come back at the same moment.
+    pub fn next_delay(&mut self, retry_after: Option<Duration>) -> Duration {
+        self.failures = self.failures.saturating_add(1);
+        if let Some(delay) = retry_after {
+            return delay;
+        }
+        let ceiling = self
+            .base
+            .saturating_mul(2u32.saturating_pow(self.failures - 1))
+            .min(self.max);
+        let half = ceiling / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+
+    /// Starts over after a successful attempt.
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_doubles_with_jitter_up_to_max_and_honors_retry_after() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(8));
+        for ceiling in [1, 2, 4, 8, 8] {
+            let delay = backoff.next_delay(None);
+            let ceiling = Duration::from_secs(ceiling);
+            assert!(delay >= ceiling / 2 && delay <= ceiling, "{delay:?}");
+        }
+        assert_eq!(
+            backoff.next_delay(Some(Duration::from_secs(120))),
+            Duration::from_secs(120)
+        );
+
+        backoff.reset();
+        assert!(backoff.next_delay(None) <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_compressed_bodies_round_trip() {
+        use std::io::Read;
+
+        let body = br#"[{"seq":1},{"seq":2}]"#.repeat(50);
+        assert_eq!(compress(Compression::None, body.clone()).unwrap(), body);
+
+        let gzip = compress(Compression::Gzip, body.clone()).unwrap();
+        let mut inflated = Vec::new();
+        flate2::read::GzDecoder::new(gzip.as_slice())
+            .read_to_end(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, body);
+        assert!(gzip.len() < body.len());
+
+        let zstd = compress(Compression::Zstd, body.clone()).unwrap();
+        assert_eq!(zstd::decode_all(zstd.as_slice()).unwrap(), body);
+    }
+
+    #[test]
+    fn test_clock_offset_follows_backend_date() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(clock_offset(&headers), None);
+
+        let date = (Utc::now() - chrono::Duration::seconds(600)).to_rfc2822();
+        headers.insert(DATE, HeaderValue::from_str(&date).unwrap());
+        let offset = clock_offset(&headers).unwrap();
+        assert!((-601..=-599).contains(&offset), "{offset}");
+    }
+
+    #[test]
+    fn test_classify_retries_only_transient_statuses() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+
+        assert_eq!(
+            classify(StatusCode::ACCEPTED, &headers),
+            Outcome::Accepted(StatusCode::ACCEPTED)
+        );
+        assert_eq!(
+            classify(StatusCode::SERVICE_UNAVAILABLE, &headers),
+            Outcome::Retry {
+                retry_after: Some(Duration::from_secs(7)),
+                reason: "503 Service Unavailable".to_string(),
+            }
+        );
+        assert!(matches!(
+            classify(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()),
+            Outcome::Retry {
+                retry_after: None,
+                ..
+            }
+        ));
+        assert_eq!(
+            classify(StatusCode::UNPROCESSABLE_ENTITY, &headers),
+            Outcome::Rejected(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+        // Bad credentials hold records in the spool instead of discarding them.
+        for status in [StatusCode::UNAUTHORIZED, StatusCode::FORBIDDEN] {
+            assert!(matches!(
+                classify(status, &HeaderMap::new()),
+                Outcome::Retry {
+                    retry_after: None,
+                    ..
+                }
+            ));
+        }
+
+        let date = (Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+        let delay = retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(50) && delay <= Duration::from_secs(60));
+    }
+}
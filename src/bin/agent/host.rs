@@ -0,0 +1,410 @@
+//! Host metrics read from Linux `/proc` and `/sys`.
+//!
+//! Every source is optional: a file that is missing or unreadable (another OS, a container
+//! without `/sys`) leaves its fields out of the sample instead of failing it.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+
+use crate::collector::{Collector, Reading};
+
+/// Well-known `extras` keys written by [`HostCollector`].
+pub mod keys {
+    /// Load average over 1 minute.
+    pub const LOAD_1M: &str = "load_1m";
+    /// Load average over 5 minutes.
+    pub const LOAD_5M: &str = "load_5m";
+    /// Load average over 15 minutes.
+    pub const LOAD_15M: &str = "load_15m";
+    /// Physical memory, in bytes.
+    pub const MEM_TOTAL_BYTES: &str = "mem_total_bytes";
+    /// Memory available without swapping, in bytes.
+    pub const MEM_AVAILABLE_BYTES: &str = "mem_available_bytes";
+    /// Swap in use, in bytes.
+    pub const SWAP_USED_BYTES: &str = "swap_used_bytes";
+    /// Bytes read from whole disks since the previous sample.
+    pub const DISK_READ_BYTES: &str = "disk_read_bytes";
+    /// Bytes written to whole disks since the previous sample.
+    pub const DISK_WRITTEN_BYTES: &str = "disk_written_bytes";
+    /// Bytes received on non-loopback interfaces since the previous sample.
+    pub const NET_RX_BYTES: &str = "net_rx_bytes";
+    /// Bytes sent on non-loopback interfaces since the previous sample.
+    pub const NET_TX_BYTES: &str = "net_tx_bytes";
+    /// Object of thermal zone type to degrees Celsius.
+    pub const THERMAL_ZONES: &str = "thermal_zones";
+}
+
+const SECTOR_BYTES: u64 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+/// Reads the host, keeping the counters needed to turn totals into per-interval values.
+///
+/// `cpu` is the utilisation since the previous sample, `memory` the share in use
+/// (`MemTotal - MemAvailable`) and `temperature` the hottest thermal zone; the rest goes to
+/// `extras` under [`keys`].
+pub struct HostCollector {
+    root: PathBuf,
+    cpu: Option<CpuTimes>,
+    disk: Option<(u64, u64)>,
+    net: Option<(u64, u64)>,
+}
+
+impl HostCollector {
+    /// Collector reading `proc/` and `sys/` under `root` (`/` on a real host).
+    ///
+    /// Counters are read once here so the first sample covers the first interval rather than
+    /// the time since boot.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let mut collector = Self {
+            root: root.into(),
+            cpu: None,
+            disk: None,
+            net: None,
+        };
+        collector.cpu = collector.cpu_times();
+        collector.disk = collector.disk_counters();
+        collector.net = collector.net_counters();
+        collector
+    }
+
+    fn read(&self, relative: &str) -> Option<String> {
+        fs::read_to_string(self.root.join(relative)).ok()
+    }
+
+    fn cpu_times(&self) -> Option<CpuTimes> {
+        self.read("proc/stat").as_deref().and_then(parse_cpu_times)
+    }
+
+    fn disk_counters(&self) -> Option<(u64, u64)> {
+        // Only whole physical disks are summed: partitions, and virtual devices stacked on
+        // top of disks (`dm-*`, `md*`, `loop*`, ...), would count the same I/O twice. Only
+        // physical disks have a `device` link in `/sys/block/<name>`.
+        let disks: BTreeSet<String> = fs::read_dir(self.root.join("sys/block"))
+            .ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                if !entry.path().join("device").exists() {
+                    return None;
+                }
+                entry.file_name().into_string().ok()
+            })
+            .collect();
+        let text = self.read("proc/diskstats")?;
+        Some(parse_diskstats(&text, &disks))
+    }
+
+    fn net_counters(&self) -> Option<(u64, u64)> {
+        self.read("proc/net/dev").as_deref().map(parse_net_dev)
+    }
+
+    fn thermal_zones(&self) -> Vec<(String, f32)> {
+        let Ok(entries) = fs::read_dir(self.root.join("sys/class/thermal")) else {
+            return Vec::new();
+        };
+        let mut zones: Vec<(String, f32)> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?.to_string();
+                if !name.starts_with("thermal_zone") {
+                    return None;
+                }
+                let millidegrees: i64 = fs::read_to_string(path.join("temp"))
+                    .ok()?
+                    .trim()
+                    .parse()
+                    .ok()?;
+                let kind = fs::read_to_string(path.join("type"))
+                    .map(|kind| kind.trim().to_string())
+                    .unwrap_or(name);
+                Some((kind, millidegrees as f32 / 1000.0))
+            })
+            .collect();
+        zones.sort_by(|a, b| a.0.cmp(&b.0));
+        zones
+    }
+
+    /// Reads the host now.
+    pub fn sample(&mut self) -> Reading {
+        let mut sample = Reading::default();
+        let extras = &mut sample.extras;
+
+        let cpu = self.cpu_times();
+        if let (Some(previous), Some(current)) = (self.cpu, cpu) {
+            let total = current.total.saturating_sub(previous.total);
+            if total > 0 {
+                let busy = current.busy.saturating_sub(previous.busy);
+                sample.cpu = Some(busy as f64 * 100.0 / total as f64);
+            }
+        }
+        self.cpu = cpu.or(self.cpu);
+
+        if let Some(memory) = self.read("proc/meminfo").as_deref().and_then(parse_meminfo) {
+            sample.memory = Some(memory.used_percent());
+            extras.insert(keys::MEM_TOTAL_BYTES.into(), memory.total.into());
+            extras.insert(keys::MEM_AVAILABLE_BYTES.into(), memory.available.into());
+            extras.insert(keys::SWAP_USED_BYTES.into(), memory.swap_used.into());
+        }
+
+        if let Some([one, five, fifteen]) =
+            self.read("proc/loadavg").as_deref().and_then(parse_loadavg)
+        {
+            extras.insert(keys::LOAD_1M.into(), one.into());
+            extras.insert(keys::LOAD_5M.into(), five.into());
+            extras.insert(keys::LOAD_15M.into(), fifteen.into());
+        }
+
+        let disk = self.disk_counters();
+        if let (Some((read0, written0)), Some((read1, written1))) = (self.disk, disk) {
+            extras.insert(
+                keys::DISK_READ_BYTES.into(),
+                read1.saturating_sub(read0).into(),
+            );
+            extras.insert(
+                keys::DISK_WRITTEN_BYTES.into(),
+                written1.saturating_sub(written0).into(),
+            );
+        }
+        self.disk = disk.or(self.disk);
+
+        let net = self.net_counters();
+        if let (Some((rx0, tx0)), Some((rx1, tx1))) = (self.net, net) {
+            extras.insert(keys::NET_RX_BYTES.into(), rx1.saturating_sub(rx0).into());
+            extras.insert(keys::NET_TX_BYTES.into(), tx1.saturating_sub(tx0).into());
+        }
+        self.net = net.or(self.net);
+
+        let zones = self.thermal_zones();
+        sample.temperature = zones.iter().map(|(_, celsius)| *celsius).reduce(f32::max);
+        if !zones.is_empty() {
+            let zones: Map<String, Value> = zones
+                .into_iter()
+                .map(|(kind, celsius)| (kind, Value::from(celsius)))
+                .collect();
+            extras.insert(keys::THERMAL_ZONES.into(), zones.into());
+        }
+
+        sample
+    }
+}
+
+#[async_trait]
+impl Collector for HostCollector {
+    fn name(&self) -> &str {
+        "host"
+    }
+
+    async fn collect(&mut self) -> anyhow::Result<Reading> {
+        Ok(self.sample())
+    }
+}
+
+/// Aggregate `cpu` line of `/proc/stat`; `idle` and `iowait` count as not busy.
+fn parse_cpu_times(text: &str) -> Option<CpuTimes> {
+    let line = text.lines().find(|line| line.starts_with("cpu "))?;
+    // user nice system idle iowait irq softirq steal; guest time is already part of user.
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|field| field.parse().ok())
+        .collect::<Option<_>>()?;
+    if fields.len() < 4 {
+        return None;
+    }
+    let total: u64 = fields.iter().sum();
+    let idle = fields[3] + fields.get(4).copied().unwrap_or(0);
+    Some(CpuTimes {
+        busy: total - idle,
+        total,
+    })
+}
+
+struct Memory {
+    total: u64,
+    available: u64,
+    swap_used: u64,
+}
+
+impl Memory {
+    fn used_percent(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.total.saturating_sub(self.available) as f64 * 100.0 / self.total as f64
+    }
+}
+
+/// `/proc/meminfo`, converted from kB to bytes.
+fn parse_meminfo(text: &str) -> Option<Memory> {
+    let field = |key: &str| -> Option<u64> {
+        let line = text.lines().find(|line| {
+            line.split_once(':')
+                .is_some_and(|(name, _)| name.trim() == key)
+        })?;
+        let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kib * 1024)
+    };
+    Some(Memory {
+        total: field("MemTotal")?,
+        available: field("MemAvailable")?,
+        swap_used: field("SwapTotal")
+            .unwrap_or(0)
+            .saturating_sub(field("SwapFree").unwrap_or(0)),
+    })
+}
+
+fn parse_loadavg(text: &str) -> Option<[f64; 3]> {
+    let mut fields = text
+        .split_whitespace()
+        .map(|field| field.parse::<f64>().ok());
+    Some([fields.next()??, fields.next()??, fields.next()??])
+}
+
+/// Sectors read and written by `disks`, in bytes.
+fn parse_diskstats(text: &str, disks: &BTreeSet<String>) -> (u64, u64) {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || !disks.contains(fields[2]) {
+                return None;
+            }
+            let read: u64 = fields[5].parse().ok()?;
+            let written: u64 = fields[9].parse().ok()?;
+            Some((read * SECTOR_BYTES, written * SECTOR_BYTES))
+        })
+        .fold((0, 0), |(read, written), (r, w)| (read + r, written + w))
+}
+
+/// Bytes received and sent by every interface but `lo`.
+fn parse_net_dev(text: &str) -> (u64, u64) {
+    text.lines()
+        .filter_map(|line| {
+            let (name, counters) = line.split_once(':')?;
+            if name.trim() == "lo" {
+                return None;
+            }
+            let fields: Vec<&str> = counters.split_whitespace().collect();
+            let rx: u64 = fields.first()?.parse().ok()?;
+            let tx: u64 = fields.get(8)?.parse().ok()?;
+            Some((rx, tx))
+        })
+        .fold((0, 0), |(rx, tx), (r, t)| (rx + r, tx + t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn write(root: &std::path::Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn fixture_host(root: &std::path::Path, cpu_line: &str, sectors: (u64, u64), net: (u64, u64)) {
+        write(
+            root,
+            "proc/stat",
+            &format!("{cpu_line}\ncpu0 1 2 3 4 5 6 7 8 0 0\nintr 1\n"),
+        );
+        write(
+            root,
+            "proc/diskstats",
+            &format!(
+                "   8       0 sda 10 0 {} 0 20 0 {} 0 0 0 0\n   8       1 sda1 10 0 999 0 20 0 999 0 0 0 0\n   7       0 loop0 1 0 999 0 1 0 999 0 0 0 0\n 253       0 dm-0 10 0 {} 0 20 0 {} 0 0 0 0\n   9       0 md0 1 0 999 0 1 0 999 0 0 0 0\n",
+                sectors.0, sectors.1, sectors.0, sectors.1
+            ),
+        );
+        write(
+            root,
+            "proc/net/dev",
+            &format!(
+                "Inter-|   Receive                                                |  Transmit\n face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets\n    lo:  5000 10 0 0 0 0 0 0  5000 10 0 0 0 0 0 0\n  eth0: {} 10 0 0 0 0 0 0 {} 10 0 0 0 0 0 0\n",
+                net.0, net.1
+            ),
+        );
+    }
+
+    #[test]
+    fn test_sample_reads_fixture_host_and_reports_interval_deltas() {
+        let root = std::env::temp_dir().join(format!("rustpulse-agent-host-{}", Uuid::new_v4()));
+        write(&root, "sys/block/sda/size", "0");
+        write(&root, "sys/block/sda/device/model", "disk\n");
+        write(&root, "sys/block/loop0/size", "0");
+        write(&root, "sys/block/dm-0/size", "0");
+        write(&root, "sys/block/md0/size", "0");
+        write(
+            &root,
+            "proc/meminfo",
+            "MemTotal:       1000 kB\nMemFree:         100 kB\nMemAvailable:    250 kB\nSwapTotal:       400 kB\nSwapFree:        300 kB\n",
+        );
+        write(&root, "proc/loadavg", "0.52 0.58 0.59 1/467 12345\n");
+        write(&root, "sys/class/thermal/thermal_zone0/temp", "45000\n");
+        write(&root, "sys/class/thermal/thermal_zone0/type", "acpitz\n");
+        write(&root, "sys/class/thermal/thermal_zone1/temp", "61500\n");
+        write(
+            &root,
+            "sys/class/thermal/thermal_zone1/type",
+            "x86_pkg_temp\n",
+        );
+        write(
+            &root,
+            "sys/class/thermal/cooling_device0/type",
+            "Processor\n",
+        );
+
+        fixture_host(
+            &root,
+            "cpu  100 0 100 800 0 0 0 0 0 0",
+            (10, 20),
+            (1000, 2000),
+        );
+        let mut collector = HostCollector::new(&root);
+        // 300 jiffies elapse, 75 of them busy (iowait counts as idle).
+        fixture_host(
+            &root,
+            "cpu  150 0 125 1000 25 0 0 0 0 0",
+            (14, 28),
+            (1500, 2100),
+        );
+        let sample = collector.sample();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(sample.cpu, Some(25.0));
+        assert_eq!(sample.memory, Some(75.0));
+        assert_eq!(sample.temperature, Some(61.5));
+        assert_eq!(
+            Value::Object(sample.extras),
+            serde_json::json!({
+                "mem_total_bytes": 1_024_000,
+                "mem_available_bytes": 256_000,
+                "swap_used_bytes": 102_400,
+                "load_1m": 0.52,
+                "load_5m": 0.58,
+                "load_15m": 0.59,
+                "disk_read_bytes": 4 * 512,
+                "disk_written_bytes": 8 * 512,
+                "net_rx_bytes": 500,
+                "net_tx_bytes": 100,
+                "thermal_zones": {"acpitz": 45.0, "x86_pkg_temp": 61.5},
+            })
+        );
+    }
+
+    #[test]
+    fn test_sample_without_proc_or_sys_is_empty() {
+        let root = std::env::temp_dir().join(format!("rustpulse-agent-host-{}", Uuid::new_v4()));
+        let sample = HostCollector::new(&root).sample();
+        assert_eq!(sample, Reading::default());
+    }
+}
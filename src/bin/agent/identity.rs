@@ -0,0 +1,74 @@
+//! Stable identity of the agent, kept in a local state file across restarts.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// IDs sent with every sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    pub source_id: Uuid,
+    pub server_id: Uuid,
+}
+
+impl Identity {
+    /// Reads the identity from `path`, generating and saving a new one on first run.
+    ///
+    /// A state file that exists but cannot be read or parsed is an error rather than a reason to
+    /// start over under new IDs.
+    pub fn load_or_create(path: &Path) -> anyhow::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                anyhow::anyhow!("agent state file {} is invalid: {e}", path.display())
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let identity = Self {
+                    source_id: Uuid::new_v4(),
+                    server_id: Uuid::new_v4(),
+                };
+                identity.save(path)?;
+                Ok(identity)
+            }
+            Err(e) => Err(anyhow::anyhow!(
+                "cannot read agent state file {}: {e}",
+                path.display()
+            )),
+        }
+    }
+
+    /// Writes through a temporary file so a crash never leaves a truncated state file behind.
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_is_created_once_and_reloaded() {
+        let dir = std::env::temp_dir().join(format!("rustpulse-agent-{}", Uuid::new_v4()));
+        let path = dir.join("state.json");
+
+        let first = Identity::load_or_create(&path).unwrap();
+        let second = Identity::load_or_create(&path).unwrap();
+        fs::write(&path, b"{not json").unwrap();
+        let corrupt = Identity::load_or_create(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first.source_id, first.server_id);
+        assert!(corrupt.is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Telemetry agent: samples the host it runs on and posts the readings to the backend.
+//!
+//! By default the host collector fills CPU, memory and temperature and puts load, disk and
+//! network counters and individual thermal zones in `extras` (see [`host::keys`]). A TOML file
+//! ([`config`]) adds collectors, labels and outputs.
+//!
+//! Records the backend cannot take right now are written to an on-disk spool and sent later
+//! through `POST /telemetry/batch`, oldest first, with their original timestamps.
+
+mod collector;
+mod config;
+mod delivery;
+mod host;
+mod identity;
+mod spool;
+
+use chrono::Utc;
+use reqwest::Client;
+use serde::Serialize;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use collector::Collector;
+use config::AgentConfig;
+use delivery::Output;
+use identity::Identity;
+
+#[derive(Debug, Serialize)]
+struct TelemetryPayload {
+    source_id: Uuid,
+    server_id: Uuid,
+    timestamp: String,
+    cpu: Option<f64>,
+    memory: Option<f64>,
+    temperature: Option<f32>,
+    extras: serde_json::Value,
+}
+
+/// How long connecting to a backend may take.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a whole request may take, so a stalled backend counts as a failed attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Records waiting for an output's task; past this, new records for that output are dropped.
+const OUTPUT_QUEUE: usize = 1024;
+
+struct Scheduled {
+    collector: Box<dyn Collector>,
+    interval: Duration,
+    next: Instant,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config = AgentConfig::load()?;
+
+    // Source and server IDs are generated on first run and reused afterwards.
+    let identity = Identity::load_or_create(&config.state_file)?;
+    let client = Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+    let opened = config
+        .outputs
+        .iter()
+        .map(|output| Output::open(client.clone(), output, &config.spool))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Each output delivers from its own task, so a slow backend holds up neither sampling
+    // nor the other outputs.
+    let drain_timeout = Duration::from_secs(config.drain_timeout_s);
+    let mut outputs = Vec::new();
+    let mut tasks = Vec::new();
+    for (output, output_config) in opened.into_iter().zip(&config.outputs) {
+        let (sender, receiver) = mpsc::channel(OUTPUT_QUEUE);
+        outputs.push((output_config.name.as_str(), sender));
+        tasks.push(tokio::spawn(output.run(receiver, drain_timeout)));
+    }
+
+    // Each collector first runs one interval after start, so the host collector's rates cover
+    // a whole interval; CPU usage over a few milliseconds is noise.
+    let start = Instant::now();
+    let mut collectors: Vec<Scheduled> = config
+        .collectors
+        .iter()
+        .map(|collector| {
+            let interval = Duration::from_millis(collector.interval_ms(config.interval_ms));
+            Scheduled {
+                collector: collector::build(collector),
+                interval,
+                next: start + interval,
+            }
+        })
+        .collect();
+
+    let mut i: u64 = 0;
+    while config.count == 0 || i < config.count {
+        let next = collectors
+            .iter()
+            .map(|scheduled| scheduled.next)
+            .min()
+            .expect("the config always has a collector");
+        tokio::time::sleep_until(next).await;
+
+        for scheduled in collectors.iter_mut().filter(|s| s.next <= Instant::now()) {
+            // A collector that fell behind skips the missed runs rather than bursting.
+            scheduled.next = (scheduled.next + scheduled.interval).max(Instant::now());
+            if config.count != 0 && i >= config.count {
+                break;
+            }
+
+            let reading = match scheduled.collector.collect().await {
+                Ok(reading) => reading,
+                Err(e) => {
+                    eprintln!(
+                        "agent: collector {} failed: {e}",
+                        scheduled.collector.name()
+                    );
+                    continue;
+                }
+            };
+            let mut extras = reading.extras;
+            extras.extend(config.labels.clone());
+            extras.insert("collector".into(), scheduled.collector.name().into());
+            extras.insert("host_id".into(), config.host_id.clone().into());
+            extras.insert("seq".into(), i.into());
+            let payload = TelemetryPayload {
+                source_id: identity.source_id,
+                server_id: identity.server_id,
+                timestamp: Utc::now().to_rfc3339(),
+                cpu: reading.cpu,
+                memory: reading.memory,
+                temperature: reading.temperature,
+                extras: extras.into(),
+            };
+            let record = serde_json::to_string(&payload)?;
+            i += 1;
+
+            for (name, output) in &outputs {
+                match output.try_send(record.clone()) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        eprintln!("agent[{name}]: output is falling behind; dropping record");
+                    }
+                    Err(TrySendError::Closed(_)) => {
+                        eprintln!("agent[{name}]: output has stopped; dropping record");
+                    }
+                }
+            }
+        }
+    }
+
+    // Once COUNT records were taken, each output keeps emptying its spool for at most
+    // DRAIN_TIMEOUT_S.
+    drop(outputs);
+    for task in tasks {
+        task.await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,261 @@
+//! Bounded on-disk queue of records the backend has not accepted yet.
+//!
+//! Records are JSON lines appended to numbered segment files (`00000000000000000001.jsonl`,
+//! ...). A segment holds at most one batch, so draining means sending the oldest segment to
+//! `POST /telemetry/batch` and deleting it once the backend has answered. When the spool grows
+//! past its byte limit the oldest segments are dropped first.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const SEGMENT_EXTENSION: &str = "jsonl";
+
+#[derive(Debug)]
+struct Segment {
+    seq: u64,
+    bytes: u64,
+    records: usize,
+}
+
+/// Oldest spooled records, read by [`Spool::oldest`].
+#[derive(Debug)]
+pub struct Batch {
+    seq: u64,
+    /// One JSON document per record, in the order they were spooled.
+    pub records: Vec<String>,
+}
+
+impl Batch {
+    /// The records as a JSON array, the body of `POST /telemetry/batch`.
+    pub fn body(&self) -> String {
+        format!("[{}]", self.records.join(","))
+    }
+}
+
+/// Segments on disk, oldest first; the last one is still being appended to unless sealed.
+pub struct Spool {
+    dir: PathBuf,
+    max_bytes: u64,
+    batch_size: usize,
+    segments: VecDeque<Segment>,
+    sealed: bool,
+    next_seq: u64,
+}
+
+impl Spool {
+    /// Opens the spool in `dir`, picking up segments left by a previous run.
+    ///
+    /// `max_bytes` bounds the spool; `batch_size` is the number of records per segment.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64, batch_size: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+                continue;
+            }
+            let Some(seq) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let text = fs::read_to_string(&path)?;
+            segments.push(Segment {
+                seq,
+                bytes: text.len() as u64,
+                records: text.lines().count(),
+            });
+        }
+        segments.sort_by_key(|segment| segment.seq);
+        let next_seq = segments.last().map_or(1, |segment| segment.seq + 1);
+
+        Ok(Self {
+            dir,
+            max_bytes,
+            batch_size: batch_size.max(1),
+            segments: segments.into(),
+            // Segments from a previous run are never appended to.
+            sealed: true,
+            next_seq,
+        })
+    }
+
+    fn path(&self, seq: u64) -> PathBuf {
+        segment_path(&self.dir, seq)
+    }
+
+    /// Number of records waiting.
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.records).sum()
+    }
+
+    /// Whether nothing is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Appends `record` (one JSON document, no newline) and syncs it to disk.
+    ///
+    /// Returns how many old records were dropped to stay under the byte limit.
+    pub fn push(&mut self, record: &str) -> io::Result<usize> {
+        let full = self
+            .segments
+            .back()
+            .is_none_or(|segment| segment.records >= self.batch_size);
+        if self.sealed || full {
+            self.segments.push_back(Segment {
+                seq: self.next_seq,
+                bytes: 0,
+                records: 0,
+            });
+            self.next_seq += 1;
+            self.sealed = false;
+        }
+
+        let segment = self.segments.back_mut().expect("segment was just ensured");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&self.dir, segment.seq))?;
+        file.write_all(format!("{record}\n").as_bytes())?;
+        file.sync_data()?;
+        segment.bytes += record.len() as u64 + 1;
+        segment.records += 1;
+
+        let mut dropped = 0;
+        while self.segments.len() > 1
+            && self
+                .segments
+                .iter()
+                .map(|segment| segment.bytes)
+                .sum::<u64>()
+                > self.max_bytes
+        {
+            dropped += self.remove_oldest()?;
+        }
+        Ok(dropped)
+    }
+
+    /// Reads the oldest segment, which stays spooled until [`ack`](Self::ack)ed.
+    ///
+    /// Lines that are not valid JSON (a write cut short by a crash) are skipped so they cannot
+    /// make the backend reject the whole batch.
+    pub fn oldest(&self) -> io::Result<Option<Batch>> {
+        let Some(segment) = self.segments.front() else {
+            return Ok(None);
+        };
+        let seq = segment.seq;
+        let text = fs::read_to_string(self.path(seq))?;
+        let records = text
+            .lines()
+            .filter(|line| serde_json::from_str::<serde_json::Value>(line).is_ok())
+            .map(str::to_string)
+            .collect();
+        Ok(Some(Batch { seq, records }))
+    }
+
+    /// Deletes `batch` once the backend has answered for it.
+    ///
+    /// Nothing may be pushed between reading a batch and acknowledging it: when the batch is the
+    /// segment still being appended to, those records would be deleted with it.
+    pub fn ack(&mut self, batch: &Batch) -> io::Result<()> {
+        if self
+            .segments
+            .front()
+            .is_some_and(|segment| segment.seq == batch.seq)
+        {
+            self.remove_oldest()?;
+        }
+        Ok(())
+    }
+
+    fn remove_oldest(&mut self) -> io::Result<usize> {
+        let Some(segment) = self.segments.pop_front() else {
+            return Ok(0);
+        };
+        match fs::remove_file(self.path(segment.seq)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        Ok(segment.records)
+    }
+}
+
+fn segment_path(dir: &Path, seq: u64) -> PathBuf {
+    dir.join(format!("{seq:020}.{SEGMENT_EXTENSION}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("rustpulse-agent-spool-{}", Uuid::new_v4()))
+    }
+
+    fn record(seq: u64) -> String {
+        serde_json::json!({"seq": seq, "timestamp": "2026-02-18T10:15:30Z"}).to_string()
+    }
+
+    #[test]
+    fn test_spool_drains_in_batches_in_order_and_survives_reopen() {
+        let dir = temp_dir();
+        let mut spool = Spool::open(&dir, u64::MAX, 2).unwrap();
+        for seq in 0..3 {
+            assert_eq!(spool.push(&record(seq)).unwrap(), 0);
+        }
+        assert_eq!(spool.len(), 3);
+
+        let first = spool.oldest().unwrap().unwrap();
+        assert_eq!(first.records, vec![record(0), record(1)]);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&first.body()).unwrap()[1]["seq"],
+            1
+        );
+        spool.ack(&first).unwrap();
+
+        // A torn final line from a crash is skipped; a new run appends to a new segment.
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(spool.path(first.seq + 1))
+            .unwrap();
+        file.write_all(b"{\"seq\":").unwrap();
+        drop(spool);
+        let mut spool = Spool::open(&dir, u64::MAX, 2).unwrap();
+        spool.push(&record(3)).unwrap();
+
+        let second = spool.oldest().unwrap().unwrap();
+        assert_eq!(second.records, vec![record(2)]);
+        spool.ack(&second).unwrap();
+        let third = spool.oldest().unwrap().unwrap();
+        assert_eq!(third.records, vec![record(3)]);
+        spool.ack(&third).unwrap();
+        assert!(spool.is_empty());
+        assert!(spool.oldest().unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_spool_drops_oldest_segments_past_max_bytes() {
+        let dir = temp_dir();
+        let line = record(0).len() as u64 + 1;
+        let mut spool = Spool::open(&dir, 3 * line, 1).unwrap();
+
+        let dropped: usize = (0..5).map(|seq| spool.push(&record(seq)).unwrap()).sum();
+        let oldest = spool.oldest().unwrap().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(dropped, 2);
+        assert_eq!(spool.len(), 3);
+        assert_eq!(oldest.records, vec![record(2)]);
+    }
+}
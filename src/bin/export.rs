@@ -0,0 +1,52 @@
+//! Exports a time range of telemetry from the configured storage to a Parquet file.
+//!
+//! Storage is selected with the same variables as the backend (`RUSTPULSE_STORAGE`, `DATABASE_URL`, ...).
+
+use chrono::{DateTime, Utc};
+use rustpulse::adapters::output::parquet_archive::{ExtrasLayout, export_range};
+use rustpulse::config::Config;
+use rustpulse::core::application::telemetry::TelemetryQuery;
+use rustpulse::core::domains::tenant::TenantId;
+use rustpulse::infra::startup::build_repositories;
+
+fn env_time(key: &str) -> anyhow::Result<Option<DateTime<Utc>>> {
+    std::env::var(key)
+        .ok()
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(&raw)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| anyhow::anyhow!("{key} must be an RFC 3339 timestamp: {e}"))
+        })
+        .transpose()
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config = Config::from_env()?;
+
+    // Range is [EXPORT_FROM, EXPORT_TO); either bound may be omitted.
+    let query = TelemetryQuery {
+        source_id: std::env::var("EXPORT_SOURCE_ID").ok(),
+        from: env_time("EXPORT_FROM")?,
+        to: env_time("EXPORT_TO")?,
+        ..Default::default()
+    };
+    let tenant = match std::env::var("EXPORT_TENANT") {
+        Ok(raw) => TenantId::parse(&raw)?,
+        Err(_) => TenantId::default(),
+    };
+    let out_path = std::env::var("EXPORT_OUT").unwrap_or_else(|_| "telemetry.parquet".to_string());
+    let layout: ExtrasLayout = std::env::var("EXPORT_EXTRAS")
+        .ok()
+        .or_else(|| config.parquet_extras.clone())
+        .as_deref()
+        .unwrap_or("json")
+        .parse()?;
+
+    let repos = build_repositories(&config).await?;
+    let out = std::io::BufWriter::new(std::fs::File::create(&out_path)?);
+    let rows = export_range(repos.telemetry.as_ref(), &tenant, query, out, &layout).await?;
+
+    println!("export: wrote {rows} records to {out_path}");
+    Ok(())
+}
@@ -19,8 +19,16 @@
 //! # }
 //! ```
 
+use crate::adapters::input::http::request_signing::{
+    DEFAULT_MAX_SKEW_SECS, DEFAULT_NONCE_CACHE_SIZE, SigningConfig,
+};
+use crate::adapters::output::postgres_partitions::PartitionInterval;
+use crate::core::application::retention::RetentionPolicy;
+use crate::core::domains::tenant::TenantId;
 use crate::errors::ConfigError;
+use chrono::TimeDelta;
 use dotenvy::dotenv;
+use std::collections::HashMap;
 use std::env;
 use std::str::FromStr;
 use tracing::instrument;
@@ -117,6 +125,10 @@ pub enum StorageMode {
     Jsonl,
     /// Persist telemetry in Postgres.
     Postgres,
+    /// Persist telemetry in an embedded SQLite database.
+    Sqlite,
+    /// Keep the most recent telemetry in memory only (lost on restart).
+    Memory,
 }
 
 impl FromStr for StorageMode {
@@ -126,6 +138,8 @@ impl FromStr for StorageMode {
         match value.to_ascii_lowercase().as_str() {
             "" | "jsonl" => Ok(Self::Jsonl),
             "postgres" | "pg" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "memory" => Ok(Self::Memory),
             other => Err(ConfigError::InvalidStorageMode(other.to_string())),
         }
     }
@@ -146,6 +160,10 @@ pub struct ConfigInput {
     pub storage_mode: Option<String>,
     /// Raw `DATABASE_URL` value.
     pub database_url: Option<String>,
+    /// Raw `RUSTPULSE_SQLITE_PATH` value.
+    pub sqlite_path: Option<String>,
+    /// Raw `RUSTPULSE_MEMORY_CAPACITY` value.
+    pub memory_capacity: Option<String>,
     /// Raw `RUST_LOG` value.
     pub rust_log: Option<String>,
     /// Raw `JWT_SECRET` value.
@@ -154,6 +172,36 @@ pub struct ConfigInput {
     pub allow_local_bind: Option<String>,
     /// Raw `RUSTPULSE_ALLOW_LOCAL_DB` value.
     pub allow_local_db: Option<String>,
+    /// Raw `RUSTPULSE_TENANT_RLS` value.
+    pub tenant_rls: Option<String>,
+    /// Raw `RUSTPULSE_RATE_LIMIT_PER_SEC` value.
+    pub rate_limit_per_sec: Option<String>,
+    /// Raw `RUSTPULSE_RATE_LIMIT_BURST` value.
+    pub rate_limit_burst: Option<String>,
+    /// Raw `RUSTPULSE_DAILY_QUOTA` value.
+    pub daily_quota: Option<String>,
+    /// Raw `RUSTPULSE_INGEST_SIGNING_KEYS` value.
+    pub ingest_signing_keys: Option<String>,
+    /// Raw `RUSTPULSE_SIGNATURE_MAX_SKEW_SECS` value.
+    pub signature_max_skew_secs: Option<String>,
+    /// Raw `RUSTPULSE_NONCE_CACHE_SIZE` value.
+    pub nonce_cache_size: Option<String>,
+    /// Raw `RUSTPULSE_WAL_DIR` value.
+    pub wal_dir: Option<String>,
+    /// Raw `RUSTPULSE_WAL_MAX_BYTES` value.
+    pub wal_max_bytes: Option<String>,
+    /// Raw `RUSTPULSE_RETENTION_DAYS` value.
+    pub retention_days: Option<String>,
+    /// Raw `RUSTPULSE_RETENTION_OVERRIDES` value.
+    pub retention_overrides: Option<String>,
+    /// Raw `RUSTPULSE_PARTITION_INTERVAL` value.
+    pub partition_interval: Option<String>,
+    /// Raw `RUSTPULSE_PARTITION_DETACH_AFTER_DAYS` value.
+    pub partition_detach_after_days: Option<String>,
+    /// Raw `RUSTPULSE_PARQUET_ARCHIVE_DIR` value.
+    pub parquet_archive_dir: Option<String>,
+    /// Raw `RUSTPULSE_PARQUET_EXTRAS` value.
+    pub parquet_extras: Option<String>,
 }
 
 impl ConfigInput {
@@ -174,10 +222,27 @@ impl ConfigInput {
             log_json: env::var("LOG_JSON").ok(),
             storage_mode: env::var("RUSTPULSE_STORAGE").ok(),
             database_url: env::var("DATABASE_URL").ok(),
+            sqlite_path: env::var("RUSTPULSE_SQLITE_PATH").ok(),
+            memory_capacity: env::var("RUSTPULSE_MEMORY_CAPACITY").ok(),
             rust_log: env::var("RUST_LOG").ok(),
             jwt_secret: env::var("JWT_SECRET").ok(),
             allow_local_bind: env::var("RUSTPULSE_ALLOW_LOCAL_BIND").ok(),
             allow_local_db: env::var("RUSTPULSE_ALLOW_LOCAL_DB").ok(),
+            tenant_rls: env::var("RUSTPULSE_TENANT_RLS").ok(),
+            rate_limit_per_sec: env::var("RUSTPULSE_RATE_LIMIT_PER_SEC").ok(),
+            rate_limit_burst: env::var("RUSTPULSE_RATE_LIMIT_BURST").ok(),
+            daily_quota: env::var("RUSTPULSE_DAILY_QUOTA").ok(),
+            ingest_signing_keys: env::var("RUSTPULSE_INGEST_SIGNING_KEYS").ok(),
+            signature_max_skew_secs: env::var("RUSTPULSE_SIGNATURE_MAX_SKEW_SECS").ok(),
+            nonce_cache_size: env::var("RUSTPULSE_NONCE_CACHE_SIZE").ok(),
+            wal_dir: env::var("RUSTPULSE_WAL_DIR").ok(),
+            wal_max_bytes: env::var("RUSTPULSE_WAL_MAX_BYTES").ok(),
+            retention_days: env::var("RUSTPULSE_RETENTION_DAYS").ok(),
+            retention_overrides: env::var("RUSTPULSE_RETENTION_OVERRIDES").ok(),
+            partition_interval: env::var("RUSTPULSE_PARTITION_INTERVAL").ok(),
+            partition_detach_after_days: env::var("RUSTPULSE_PARTITION_DETACH_AFTER_DAYS").ok(),
+            parquet_archive_dir: env::var("RUSTPULSE_PARQUET_ARCHIVE_DIR").ok(),
+            parquet_extras: env::var("RUSTPULSE_PARQUET_EXTRAS").ok(),
         }
     }
 }
@@ -197,10 +262,38 @@ pub struct Config {
     pub storage_mode: StorageMode,
     /// Database connection string (required for Postgres storage).
     pub database_url: Option<String>,
+    /// SQLite database file (SQLite storage only; `None` uses `rustpulse.db` in the crate directory).
+    pub sqlite_path: Option<String>,
+    /// Records kept by the memory storage (`None` uses the repository default).
+    pub memory_capacity: Option<usize>,
     /// Optional `tracing_subscriber` filter string or log level.
     pub rust_log: Option<String>,
     /// Optional JWT secret (required in production).
     pub jwt_secret: Option<String>,
+    /// Expose the tenant to Postgres row-level security (`rustpulse.tenant_id`).
+    pub tenant_rls: bool,
+    /// Ingest requests refilled per second for each caller (`None` disables rate limiting).
+    pub rate_limit_per_sec: Option<u32>,
+    /// Ingest requests a caller may send back to back (defaults to the per-second rate).
+    pub rate_limit_burst: Option<u32>,
+    /// Telemetry records each tenant may ingest per UTC day (`None` means unlimited).
+    pub daily_quota: Option<u64>,
+    /// Keys and replay window of signed ingest (`None` accepts unsigned requests).
+    pub ingest_signing: Option<SigningConfig>,
+    /// Directory of the ingest write-ahead log (`None` writes to storage synchronously).
+    pub wal_dir: Option<String>,
+    /// Write-ahead log size above which ingest is rejected with `503` (`None` uses the default).
+    pub wal_max_bytes: Option<u64>,
+    /// How long telemetry is kept (empty keeps it forever).
+    pub retention: RetentionPolicy,
+    /// Span of each Postgres telemetry partition.
+    pub partition_interval: PartitionInterval,
+    /// Age after which Postgres partitions are detached (`None` keeps them attached).
+    pub partition_detach_after: Option<TimeDelta>,
+    /// Directory of the hourly Parquet archive (`None` disables archiving; needs the `parquet` feature).
+    pub parquet_archive_dir: Option<String>,
+    /// `extras` layout of Parquet files: `json` or `<key>:<number|text|bool>,...` (`None` means `json`).
+    pub parquet_extras: Option<String>,
 }
 
 impl Config {
@@ -268,7 +361,7 @@ impl Config {
             .parse::<StorageMode>()?;
 
         let database_url = match storage_mode {
-            StorageMode::Jsonl => input.database_url,
+            StorageMode::Jsonl | StorageMode::Sqlite | StorageMode::Memory => input.database_url,
             StorageMode::Postgres => Some(
                 input
                     .database_url
@@ -276,6 +369,10 @@ impl Config {
             ),
         };
 
+        let sqlite_path = input.sqlite_path.filter(|path| !path.trim().is_empty());
+        let memory_capacity =
+            parse_positive::<usize>("RUSTPULSE_MEMORY_CAPACITY", input.memory_capacity)?;
+
         let log_json = input
             .log_json
             .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE"))
@@ -300,6 +397,63 @@ impl Config {
 
         let allow_local_bind = input.allow_local_bind.as_deref() == Some("1");
         let allow_local_db = input.allow_local_db.as_deref() == Some("1");
+        let tenant_rls = input.tenant_rls.as_deref() == Some("1");
+
+        let rate_limit_per_sec =
+            parse_positive::<u32>("RUSTPULSE_RATE_LIMIT_PER_SEC", input.rate_limit_per_sec)?;
+        let rate_limit_burst =
+            parse_positive::<u32>("RUSTPULSE_RATE_LIMIT_BURST", input.rate_limit_burst)?;
+        let daily_quota = parse_positive::<u64>("RUSTPULSE_DAILY_QUOTA", input.daily_quota)?;
+        let ingest_signing = parse_signing_keys(input.ingest_signing_keys)?
+            .map(|keys| -> Result<SigningConfig, ConfigError> {
+                Ok(SigningConfig {
+                    keys,
+                    max_skew_secs: parse_positive::<u64>(
+                        "RUSTPULSE_SIGNATURE_MAX_SKEW_SECS",
+                        input.signature_max_skew_secs,
+                    )?
+                    .unwrap_or(DEFAULT_MAX_SKEW_SECS),
+                    nonce_cache_size: parse_positive::<usize>(
+                        "RUSTPULSE_NONCE_CACHE_SIZE",
+                        input.nonce_cache_size,
+                    )?
+                    .unwrap_or(DEFAULT_NONCE_CACHE_SIZE),
+                })
+            })
+            .transpose()?;
+        let wal_dir = input.wal_dir.filter(|dir| !dir.trim().is_empty());
+        let wal_max_bytes = parse_positive::<u64>("RUSTPULSE_WAL_MAX_BYTES", input.wal_max_bytes)?;
+        let retention = parse_retention(input.retention_days, input.retention_overrides)?;
+        let partition_interval = input
+            .partition_interval
+            .map(|raw| raw.parse::<PartitionInterval>())
+            .transpose()
+            .map_err(|_| {
+                ConfigError::Validation(
+                    "RUSTPULSE_PARTITION_INTERVAL must be one of: daily, weekly".to_string(),
+                )
+            })?
+            .unwrap_or_default();
+        let partition_detach_after = parse_positive::<i64>(
+            "RUSTPULSE_PARTITION_DETACH_AFTER_DAYS",
+            input.partition_detach_after_days,
+        )?
+        .map(TimeDelta::days);
+        let parquet_archive_dir = input
+            .parquet_archive_dir
+            .filter(|dir| !dir.trim().is_empty());
+        if cfg!(not(feature = "parquet")) && parquet_archive_dir.is_some() {
+            return Err(ConfigError::Validation(
+                "RUSTPULSE_PARQUET_ARCHIVE_DIR requires building with the `parquet` feature"
+                    .to_string(),
+            ));
+        }
+        let parquet_extras = input.parquet_extras.filter(|spec| !spec.trim().is_empty());
+        #[cfg(feature = "parquet")]
+        if let Some(spec) = &parquet_extras {
+            spec.parse::<crate::adapters::output::parquet_archive::ExtrasLayout>()
+                .map_err(|e| ConfigError::Validation(format!("RUSTPULSE_PARQUET_EXTRAS: {e}")))?;
+        }
 
         let config = Self {
             app_env,
@@ -308,8 +462,22 @@ impl Config {
             port,
             storage_mode,
             database_url,
+            sqlite_path,
+            memory_capacity,
             rust_log,
             jwt_secret,
+            tenant_rls,
+            rate_limit_per_sec,
+            rate_limit_burst,
+            daily_quota,
+            ingest_signing,
+            wal_dir,
+            wal_max_bytes,
+            retention,
+            partition_interval,
+            partition_detach_after,
+            parquet_archive_dir,
+            parquet_extras,
         };
 
         Self::validate(&config, allow_local_bind, allow_local_db)?;
@@ -361,6 +529,15 @@ impl Config {
                 ));
             }
 
+            if let Some(signing) = &config.ingest_signing
+                && signing.keys.values().any(|secret| secret.len() < 32)
+            {
+                return Err(ConfigError::Validation(
+                    "RUSTPULSE_INGEST_SIGNING_KEYS secrets must be at least 32 characters in prod"
+                        .to_string(),
+                ));
+            }
+
             if let Some(rust_log) = &config.rust_log
                 && rust_log == "debug"
             {
@@ -390,3 +567,93 @@ impl Config {
         Ok(())
     }
 }
+
+/// Parses an optional strictly positive integer setting.
+fn parse_positive<T>(name: &str, raw: Option<String>) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr + PartialOrd + Default,
+{
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    match raw.trim().parse::<T>() {
+        Ok(value) if value > T::default() => Ok(Some(value)),
+        _ => Err(ConfigError::Validation(format!(
+            "{name} must be a positive integer"
+        ))),
+    }
+}
+
+/// Parses `RUSTPULSE_INGEST_SIGNING_KEYS`, a comma-separated list of `<key_id>:<secret>`.
+fn parse_signing_keys(
+    raw: Option<String>,
+) -> Result<Option<HashMap<String, Vec<u8>>>, ConfigError> {
+    let Some(raw) = raw.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let mut keys = HashMap::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (key_id, secret) = entry
+            .split_once(':')
+            .map(|(key_id, secret)| (key_id.trim(), secret.trim()))
+            .filter(|(key_id, secret)| !key_id.is_empty() && !secret.is_empty())
+            .ok_or_else(|| {
+                ConfigError::Validation(
+                    "RUSTPULSE_INGEST_SIGNING_KEYS entries must look like <key_id>:<secret>"
+                        .to_string(),
+                )
+            })?;
+        if keys
+            .insert(key_id.to_string(), secret.as_bytes().to_vec())
+            .is_some()
+        {
+            return Err(ConfigError::Validation(format!(
+                "RUSTPULSE_INGEST_SIGNING_KEYS lists key id {key_id:?} twice"
+            )));
+        }
+    }
+    Ok(Some(keys))
+}
+
+/// Builds the retention policy from `RUSTPULSE_RETENTION_DAYS` and a comma-separated
+/// `RUSTPULSE_RETENTION_OVERRIDES` list of `tenant:<id>=<days>` / `source:<uuid>=<days>`.
+fn parse_retention(
+    days: Option<String>,
+    overrides: Option<String>,
+) -> Result<RetentionPolicy, ConfigError> {
+    let default = parse_positive::<i64>("RUSTPULSE_RETENTION_DAYS", days)?.map(TimeDelta::days);
+    let mut policy = RetentionPolicy::new(default);
+
+    let invalid = |entry: &str| {
+        ConfigError::Validation(format!(
+            "RUSTPULSE_RETENTION_OVERRIDES entry `{entry}` must be tenant:<id>=<days> or source:<uuid>=<days>"
+        ))
+    };
+    for entry in overrides
+        .iter()
+        .flat_map(|raw| raw.split(','))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (scope, days) = entry.split_once('=').ok_or_else(|| invalid(entry))?;
+        let retention = match days.trim().parse::<i64>() {
+            Ok(days) if days > 0 => TimeDelta::days(days),
+            _ => return Err(invalid(entry)),
+        };
+        policy = match scope.trim().split_once(':') {
+            Some(("tenant", id)) => {
+                policy.with_tenant(TenantId::parse(id).map_err(|_| invalid(entry))?, retention)
+            }
+            Some(("source", id)) => policy.with_source(
+                uuid::Uuid::parse_str(id).map_err(|_| invalid(entry))?,
+                retention,
+            ),
+            _ => return Err(invalid(entry)),
+        };
+    }
+    Ok(policy)
+}
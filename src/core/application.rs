@@ -1,3 +1,6 @@
 //! Application layer (use cases and ports).
 
+pub mod api_keys;
+pub mod health;
+pub mod retention;
 pub mod telemetry;
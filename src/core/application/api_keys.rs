@@ -0,0 +1,13 @@
+//! API key use cases and ports.
+
+pub mod ports;
+pub mod usecases;
+
+/// Use case for managing API keys.
+pub use ports::input::api_key_admin_usecase::{ApiKeyAdminCase, CreatedApiKey, NewApiKey};
+/// Use case for authenticating callers by API key.
+pub use ports::input::api_key_auth_usecase::ApiKeyAuthCase;
+/// Output port for API key persistence.
+pub use ports::output::api_key_repository::ApiKeyRepository;
+/// Default API key use case implementation.
+pub use usecases::api_key_service::{API_KEY_PREFIX, API_KEY_SCOPES, ApiKeyError, ApiKeyService};
@@ -0,0 +1,4 @@
+//! Port definitions for the API key application module.
+
+pub mod input;
+pub mod output;
@@ -0,0 +1,4 @@
+//! Input ports for API key use cases.
+
+pub mod api_key_admin_usecase;
+pub mod api_key_auth_usecase;
@@ -0,0 +1,42 @@
+//! Input port for API key administration.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::domains::api_key::ApiKey;
+use crate::core::domains::tenant::TenantId;
+
+#[derive(Debug, Clone, Deserialize)]
+/// Request to mint a new API key.
+pub struct NewApiKey {
+    /// Human-readable label.
+    pub name: String,
+    /// Scopes to grant (`telemetry:read`, `telemetry:write`).
+    pub scopes: Vec<String>,
+    /// Server the key is bound to, if any.
+    #[serde(default)]
+    pub server_id: Option<Uuid>,
+    /// Tenant the key acts for; defaults to [`TenantId::DEFAULT`].
+    #[serde(default)]
+    pub tenant_id: TenantId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+/// A freshly minted key. `secret` is only ever returned here.
+pub struct CreatedApiKey {
+    /// Stored key metadata.
+    pub key: ApiKey,
+    /// Full key string to hand to the agent.
+    pub secret: String,
+}
+
+#[async_trait::async_trait]
+/// Use case for creating, listing and revoking API keys.
+pub trait ApiKeyAdminCase: Send + Sync {
+    /// Creates a key and returns its one-time secret.
+    async fn create(&self, request: NewApiKey) -> anyhow::Result<CreatedApiKey>;
+    /// Lists all keys, including revoked ones, newest first.
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>>;
+    /// Revokes a key; returns `false` when no active key has this id.
+    async fn revoke(&self, id: Uuid) -> anyhow::Result<bool>;
+}
@@ -0,0 +1,10 @@
+//! Input port for authenticating callers by API key.
+
+use crate::core::domains::api_key::ApiKey;
+
+#[async_trait::async_trait]
+/// Use case that resolves a presented key string to an active key.
+pub trait ApiKeyAuthCase: Send + Sync {
+    /// Returns the key when `presented` is a valid, unrevoked key.
+    async fn authenticate(&self, presented: &str) -> anyhow::Result<Option<ApiKey>>;
+}
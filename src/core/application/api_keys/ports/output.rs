@@ -0,0 +1,3 @@
+//! Output ports used by API key use cases.
+
+pub mod api_key_repository;
@@ -0,0 +1,19 @@
+//! Output port for API key persistence.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::domains::api_key::{ApiKey, StoredApiKey};
+
+#[async_trait::async_trait]
+/// Repository abstraction for storing and looking up API keys.
+pub trait ApiKeyRepository: Send + Sync {
+    /// Persists a new key.
+    async fn insert(&self, key: StoredApiKey) -> anyhow::Result<()>;
+    /// Lists all keys, newest first.
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>>;
+    /// Finds a key (active or revoked) by its public prefix.
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<StoredApiKey>>;
+    /// Marks an active key as revoked at `at`; returns `false` if none matched.
+    async fn revoke(&self, id: Uuid, at: DateTime<Utc>) -> anyhow::Result<bool>;
+}
@@ -0,0 +1,3 @@
+//! API key use case implementations.
+
+pub mod api_key_service;
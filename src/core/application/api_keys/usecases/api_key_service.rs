@@ -0,0 +1,316 @@
+//! Default API key service: minting, hashing, lookup and revocation.
+//!
+//! Keys look like `rpk_<prefix>_<secret>`. The prefix is stored in clear for lookup;
+//! only `sha256(salt || secret)` is stored for the secret.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use rustpulse::core::application::api_keys::{
+//!     ApiKeyAdminCase as _, ApiKeyAuthCase as _, ApiKeyRepository, ApiKeyService, NewApiKey,
+//! };
+//! use rustpulse::core::domains::api_key::{ApiKey, StoredApiKey};
+//! use std::sync::Arc;
+//!
+//! # struct Repo;
+//! # #[async_trait::async_trait]
+//! # impl ApiKeyRepository for Repo {
+//! #     async fn insert(&self, _key: StoredApiKey) -> anyhow::Result<()> { Ok(()) }
+//! #     async fn list(&self) -> anyhow::Result<Vec<ApiKey>> { Ok(Vec::new()) }
+//! #     async fn find_by_prefix(&self, _p: &str) -> anyhow::Result<Option<StoredApiKey>> { Ok(None) }
+//! #     async fn revoke(&self, _id: uuid::Uuid, _at: chrono::DateTime<chrono::Utc>) -> anyhow::Result<bool> { Ok(false) }
+//! # }
+//! let service = ApiKeyService::new(Arc::new(Repo));
+//! let created = service
+//!     .create(NewApiKey {
+//!         name: "edge-agent-01".to_string(),
+//!         scopes: vec!["telemetry:write".to_string()],
+//!         server_id: None,
+//!         tenant_id: Default::default(),
+//!     })
+//!     .await?;
+//!
+//! let _key = service.authenticate(&created.secret).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::core::application::api_keys::ports::input::api_key_admin_usecase::{
+    ApiKeyAdminCase, CreatedApiKey, NewApiKey,
+};
+use crate::core::application::api_keys::ports::input::api_key_auth_usecase::ApiKeyAuthCase;
+use crate::core::application::api_keys::ports::output::api_key_repository::ApiKeyRepository;
+use crate::core::domains::api_key::{ApiKey, StoredApiKey};
+
+/// Marker every API key starts with.
+pub const API_KEY_PREFIX: &str = "rpk_";
+
+/// Scopes that may be granted to an API key.
+pub const API_KEY_SCOPES: [&str; 2] = ["telemetry:read", "telemetry:write"];
+
+const PREFIX_BYTES: usize = 4;
+const SECRET_BYTES: usize = 32;
+const SALT_BYTES: usize = 16;
+
+#[derive(Debug, thiserror::Error)]
+/// Validation errors when creating an API key.
+pub enum ApiKeyError {
+    /// The key name is empty.
+    #[error("api key name must not be empty")]
+    EmptyName,
+    /// No scopes were requested.
+    #[error("api key must have at least one scope")]
+    NoScopes,
+    /// A requested scope cannot be granted to API keys.
+    #[error("scope not allowed for api keys: {0}")]
+    InvalidScope(String),
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hash_secret(salt: &str, secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(secret.as_bytes());
+    to_hex(&hasher.finalize())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Splits `rpk_<prefix>_<secret>` into its parts.
+fn split_key(presented: &str) -> Option<(&str, &str)> {
+    let rest = presented.strip_prefix(API_KEY_PREFIX)?;
+    let (prefix, secret) = rest.split_once('_')?;
+    (prefix.len() == PREFIX_BYTES * 2 && !secret.is_empty()).then_some((prefix, secret))
+}
+
+/// API key use case implementation backed by an [`ApiKeyRepository`].
+pub struct ApiKeyService {
+    repo: Arc<dyn ApiKeyRepository>,
+}
+
+impl ApiKeyService {
+    /// Creates a new service using the provided repository implementation.
+    pub fn new(repo: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repo }
+    }
+
+    fn validate(request: &NewApiKey) -> Result<(), ApiKeyError> {
+        if request.name.trim().is_empty() {
+            return Err(ApiKeyError::EmptyName);
+        }
+        if request.scopes.is_empty() {
+            return Err(ApiKeyError::NoScopes);
+        }
+        if let Some(scope) = request
+            .scopes
+            .iter()
+            .find(|s| !API_KEY_SCOPES.contains(&s.as_str()))
+        {
+            return Err(ApiKeyError::InvalidScope(scope.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyAdminCase for ApiKeyService {
+    async fn create(&self, request: NewApiKey) -> anyhow::Result<CreatedApiKey> {
+        Self::validate(&request)?;
+
+        let mut prefix = [0u8; PREFIX_BYTES];
+        let mut secret = [0u8; SECRET_BYTES];
+        let mut salt = [0u8; SALT_BYTES];
+        rand::fill(&mut prefix);
+        rand::fill(&mut secret);
+        rand::fill(&mut salt);
+
+        let prefix = to_hex(&prefix);
+        let secret = URL_SAFE_NO_PAD.encode(secret);
+        let salt = to_hex(&salt);
+
+        let mut scopes = request.scopes;
+        scopes.sort();
+        scopes.dedup();
+
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            name: request.name.trim().to_string(),
+            prefix: prefix.clone(),
+            scopes,
+            server_id: request.server_id,
+            tenant_id: request.tenant_id,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+
+        self.repo
+            .insert(StoredApiKey {
+                key: key.clone(),
+                secret_hash: hash_secret(&salt, &secret),
+                salt,
+            })
+            .await?;
+
+        tracing::info!(key_id = %key.id, prefix = %key.prefix, "api key created");
+        Ok(CreatedApiKey {
+            secret: format!("{API_KEY_PREFIX}{prefix}_{secret}"),
+            key,
+        })
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+        self.repo.list().await
+    }
+
+    async fn revoke(&self, id: Uuid) -> anyhow::Result<bool> {
+        let revoked = self.repo.revoke(id, Utc::now()).await?;
+        if revoked {
+            tracing::info!(key_id = %id, "api key revoked");
+        }
+        Ok(revoked)
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyAuthCase for ApiKeyService {
+    async fn authenticate(&self, presented: &str) -> anyhow::Result<Option<ApiKey>> {
+        let Some((prefix, secret)) = split_key(presented) else {
+            return Ok(None);
+        };
+        let Some(stored) = self.repo.find_by_prefix(prefix).await? else {
+            return Ok(None);
+        };
+        if !stored.key.is_active() {
+            return Ok(None);
+        }
+
+        let expected = hash_secret(&stored.salt, secret);
+        Ok(
+            constant_time_eq(expected.as_bytes(), stored.secret_hash.as_bytes())
+                .then_some(stored.key),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemRepo {
+        keys: Mutex<Vec<StoredApiKey>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ApiKeyRepository for MemRepo {
+        async fn insert(&self, key: StoredApiKey) -> anyhow::Result<()> {
+            self.keys.lock().unwrap().push(key);
+            Ok(())
+        }
+
+        async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|k| k.key.clone())
+                .collect())
+        }
+
+        async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<StoredApiKey>> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|k| k.key.prefix == prefix)
+                .cloned())
+        }
+
+        async fn revoke(&self, id: Uuid, at: DateTime<Utc>) -> anyhow::Result<bool> {
+            let mut keys = self.keys.lock().unwrap();
+            match keys
+                .iter_mut()
+                .find(|k| k.key.id == id && k.key.is_active())
+            {
+                Some(k) => {
+                    k.key.revoked_at = Some(at);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+    }
+
+    fn request(scopes: &[&str]) -> NewApiKey {
+        NewApiKey {
+            name: "agent".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            server_id: Some(Uuid::new_v4()),
+            tenant_id: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_created_key_authenticates_and_secret_is_not_stored() {
+        let repo = Arc::new(MemRepo::default());
+        let service = ApiKeyService::new(repo.clone());
+
+        let created = service.create(request(&["telemetry:write"])).await.unwrap();
+        let stored = repo.keys.lock().unwrap()[0].clone();
+
+        assert!(created.secret.starts_with(API_KEY_PREFIX));
+        assert!(
+            !stored
+                .secret_hash
+                .contains(created.secret.rsplit('_').next().unwrap())
+        );
+
+        let key = service.authenticate(&created.secret).await.unwrap();
+        assert_eq!(key, Some(created.key));
+
+        let tampered = format!("{}x", created.secret);
+        assert_eq!(service.authenticate(&tampered).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_key_no_longer_authenticates() {
+        let service = ApiKeyService::new(Arc::new(MemRepo::default()));
+        let created = service.create(request(&["telemetry:read"])).await.unwrap();
+
+        assert!(service.revoke(created.key.id).await.unwrap());
+        assert!(!service.revoke(created.key.id).await.unwrap());
+        assert_eq!(service.authenticate(&created.secret).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_unknown_scopes() {
+        let service = ApiKeyService::new(Arc::new(MemRepo::default()));
+
+        let err = service
+            .create(request(&["admin:api-keys"]))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ApiKeyError>(),
+            Some(ApiKeyError::InvalidScope(_))
+        ));
+    }
+}
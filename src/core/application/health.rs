@@ -0,0 +1,13 @@
+//! Readiness use case and dependency probe port.
+
+pub mod ports;
+pub mod usecases;
+
+/// Use case for reporting whether the service can take traffic.
+pub use ports::input::readiness_usecase::{
+    CheckStatus, DependencyCheck, ReadinessCase, ReadinessReport,
+};
+/// Output port for checking one dependency.
+pub use ports::output::dependency_probe::DependencyProbe;
+/// Default readiness use case implementation.
+pub use usecases::readiness_service::{PROBE_TIMEOUT, ReadinessService};
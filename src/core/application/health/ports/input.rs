@@ -0,0 +1,3 @@
+//! Input ports for health use cases.
+
+pub mod readiness_usecase;
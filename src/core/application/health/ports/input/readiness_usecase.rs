@@ -0,0 +1,81 @@
+//! Input port for readiness checks.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// Result of a single check or of the whole report.
+pub enum CheckStatus {
+    /// Healthy.
+    Up,
+    /// Failing.
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Outcome of probing one dependency.
+pub struct DependencyCheck {
+    /// Dependency name, e.g. `postgres` or `jsonl`.
+    pub name: &'static str,
+    /// Whether the probe succeeded.
+    pub status: CheckStatus,
+    /// Whether a failure makes the service not ready.
+    pub critical: bool,
+    /// Time the probe took, in milliseconds.
+    ///
+    /// Failure details are only logged: the report is served unauthenticated.
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Readiness of the service and of each dependency.
+pub struct ReadinessReport {
+    /// [`CheckStatus::Down`] when any critical dependency is down.
+    pub status: CheckStatus,
+    /// One entry per probed dependency.
+    pub checks: Vec<DependencyCheck>,
+}
+
+impl ReadinessReport {
+    /// Builds a report, deriving the overall status from the critical checks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::core::application::health::{CheckStatus, DependencyCheck, ReadinessReport};
+    ///
+    /// let report = ReadinessReport::from_checks(vec![DependencyCheck {
+    ///     name: "cache",
+    ///     status: CheckStatus::Down,
+    ///     critical: false,
+    ///     latency_ms: 1,
+    /// }]);
+    ///
+    /// assert!(report.is_ready());
+    /// ```
+    pub fn from_checks(checks: Vec<DependencyCheck>) -> Self {
+        let down = checks
+            .iter()
+            .any(|c| c.critical && c.status == CheckStatus::Down);
+        Self {
+            status: if down {
+                CheckStatus::Down
+            } else {
+                CheckStatus::Up
+            },
+            checks,
+        }
+    }
+
+    /// Returns `true` when every critical dependency is up.
+    pub fn is_ready(&self) -> bool {
+        self.status == CheckStatus::Up
+    }
+}
+
+#[async_trait::async_trait]
+/// Use case that checks whether the service can serve traffic.
+pub trait ReadinessCase: Send + Sync {
+    /// Probes every dependency and reports their status.
+    async fn readiness(&self) -> ReadinessReport;
+}
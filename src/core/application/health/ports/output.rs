@@ -0,0 +1,3 @@
+//! Output ports used by health use cases.
+
+pub mod dependency_probe;
@@ -0,0 +1,16 @@
+//! Output port for checking one dependency the service needs.
+
+#[async_trait::async_trait]
+/// A dependency readiness can check (database, storage file, ...).
+pub trait DependencyProbe: Send + Sync {
+    /// Name shown in the readiness report, e.g. `postgres`.
+    fn name(&self) -> &'static str;
+
+    /// Whether a failure makes the whole service not ready.
+    fn critical(&self) -> bool {
+        true
+    }
+
+    /// Checks the dependency; the error message is shown in the readiness report.
+    async fn probe(&self) -> anyhow::Result<()>;
+}
@@ -0,0 +1,3 @@
+//! Health use case implementations.
+
+pub mod readiness_service;
@@ -0,0 +1,166 @@
+//! Default readiness service: probes every dependency concurrently with a timeout.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # async fn demo() {
+//! use rustpulse::core::application::health::{
+//!     DependencyProbe, ReadinessCase as _, ReadinessService,
+//! };
+//! use std::sync::Arc;
+//!
+//! struct AlwaysUp;
+//!
+//! #[async_trait::async_trait]
+//! impl DependencyProbe for AlwaysUp {
+//!     fn name(&self) -> &'static str {
+//!         "always-up"
+//!     }
+//!     async fn probe(&self) -> anyhow::Result<()> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let service = ReadinessService::new(vec![Arc::new(AlwaysUp)]);
+//! assert!(service.readiness().await.is_ready());
+//! # }
+//! ```
+
+use futures_util::future::join_all;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::core::application::health::ports::input::readiness_usecase::{
+    CheckStatus, DependencyCheck, ReadinessCase, ReadinessReport,
+};
+use crate::core::application::health::ports::output::dependency_probe::DependencyProbe;
+
+/// Longest a single probe may take before it is reported down.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Readiness use case implementation over a set of [`DependencyProbe`]s.
+pub struct ReadinessService {
+    probes: Vec<Arc<dyn DependencyProbe>>,
+    timeout: Duration,
+}
+
+impl ReadinessService {
+    /// Creates a service checking `probes` with the default [`PROBE_TIMEOUT`].
+    pub fn new(probes: Vec<Arc<dyn DependencyProbe>>) -> Self {
+        Self {
+            probes,
+            timeout: PROBE_TIMEOUT,
+        }
+    }
+
+    /// Overrides the per-probe timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn check(&self, probe: &dyn DependencyProbe) -> DependencyCheck {
+        let start = Instant::now();
+        let result = match tokio::time::timeout(self.timeout, probe.probe()).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!(
+                "timed out after {}ms",
+                self.timeout.as_millis()
+            )),
+        };
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        if let Err(err) = &result {
+            tracing::warn!(dependency = probe.name(), error = %err, "readiness probe failed");
+        }
+        DependencyCheck {
+            name: probe.name(),
+            status: if result.is_ok() {
+                CheckStatus::Up
+            } else {
+                CheckStatus::Down
+            },
+            critical: probe.critical(),
+            latency_ms,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReadinessCase for ReadinessService {
+    async fn readiness(&self) -> ReadinessReport {
+        let checks = join_all(self.probes.iter().map(|p| self.check(p.as_ref()))).await;
+        ReadinessReport::from_checks(checks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Probe {
+        name: &'static str,
+        critical: bool,
+        delay: Duration,
+        fail: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl DependencyProbe for Probe {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn critical(&self) -> bool {
+            self.critical
+        }
+
+        async fn probe(&self) -> anyhow::Result<()> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                anyhow::bail!("{} unreachable", self.name);
+            }
+            Ok(())
+        }
+    }
+
+    fn probe(name: &'static str, critical: bool, fail: bool) -> Arc<dyn DependencyProbe> {
+        Arc::new(Probe {
+            name,
+            critical,
+            delay: Duration::ZERO,
+            fail,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_only_critical_failures_make_the_service_not_ready() {
+        let service = ReadinessService::new(vec![
+            probe("postgres", true, false),
+            probe("cache", false, true),
+        ]);
+        let report = service.readiness().await;
+        assert!(report.is_ready());
+        assert_eq!(report.checks[1].status, CheckStatus::Down);
+        assert_eq!(report.checks[1].name, "cache");
+
+        let service = ReadinessService::new(vec![probe("postgres", true, true)]);
+        assert!(!service.readiness().await.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_slow_probe_is_reported_down_after_timeout() {
+        let service = ReadinessService::new(vec![Arc::new(Probe {
+            name: "postgres",
+            critical: true,
+            delay: Duration::from_secs(5),
+            fail: false,
+        })])
+        .with_timeout(Duration::from_millis(20));
+
+        let report = service.readiness().await;
+        assert!(!report.is_ready());
+        assert_eq!(report.checks[0].status, CheckStatus::Down);
+        assert!(report.checks[0].latency_ms < 5_000);
+    }
+}
@@ -0,0 +1,12 @@
+//! Retention policies and the background task that expires old telemetry.
+
+pub mod policy;
+pub mod ports;
+pub mod usecases;
+
+/// How long telemetry is kept, with per-tenant and per-source overrides.
+pub use policy::{ExpiryRule, RetentionPolicy};
+/// Output port for deleting expired telemetry.
+pub use ports::output::expiring_store::ExpiringStore;
+/// Periodic retention enforcement.
+pub use usecases::retention_service::{RETENTION_INTERVAL, RetentionService};
@@ -0,0 +1,212 @@
+//! Retention policy: how long telemetry is kept before it expires.
+//!
+//! A per-source override wins over a per-tenant override, which wins over the global default.
+//! Telemetry with no applicable retention is kept forever.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::{TimeDelta, TimeZone, Utc};
+//! use rustpulse::core::application::retention::RetentionPolicy;
+//! use rustpulse::core::domains::tenant::TenantId;
+//! use uuid::Uuid;
+//!
+//! let acme = TenantId::parse("acme").unwrap();
+//! let policy = RetentionPolicy::new(Some(TimeDelta::days(30)))
+//!     .with_tenant(acme.clone(), TimeDelta::days(90));
+//! let now = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+//!
+//! assert_eq!(
+//!     policy.cutoff(&acme, Uuid::nil(), now),
+//!     Some(now - TimeDelta::days(90))
+//! );
+//! assert_eq!(
+//!     policy.cutoff(&TenantId::default(), Uuid::nil(), now),
+//!     Some(now - TimeDelta::days(30))
+//! );
+//! ```
+
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::core::domains::tenant::TenantId;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Global retention plus per-tenant and per-source overrides.
+pub struct RetentionPolicy {
+    default: Option<TimeDelta>,
+    tenants: BTreeMap<TenantId, TimeDelta>,
+    sources: BTreeMap<Uuid, TimeDelta>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// One delete a SQL backend runs to enforce a [`RetentionPolicy`].
+///
+/// Matches rows older than `cutoff`, in `tenant` and of `source_id` when set, and
+/// outside `except_tenants` / `except_sources` (which have a more specific rule).
+pub struct ExpiryRule {
+    /// Only rows of this tenant (`None` means every tenant).
+    pub tenant: Option<TenantId>,
+    /// Only rows of this source (`None` means every source).
+    pub source_id: Option<Uuid>,
+    /// Rows strictly older than this expire.
+    pub cutoff: DateTime<Utc>,
+    /// Tenants governed by their own rule.
+    pub except_tenants: Vec<TenantId>,
+    /// Sources governed by their own rule.
+    pub except_sources: Vec<Uuid>,
+}
+
+impl RetentionPolicy {
+    /// Keeps telemetry for `default` (`None` keeps it forever unless overridden).
+    pub fn new(default: Option<TimeDelta>) -> Self {
+        Self {
+            default,
+            ..Default::default()
+        }
+    }
+
+    /// Keeps `tenant`'s telemetry for `retention` instead of the default.
+    pub fn with_tenant(mut self, tenant: TenantId, retention: TimeDelta) -> Self {
+        self.tenants.insert(tenant, retention);
+        self
+    }
+
+    /// Keeps `source_id`'s telemetry for `retention`, whatever its tenant.
+    pub fn with_source(mut self, source_id: Uuid, retention: TimeDelta) -> Self {
+        self.sources.insert(source_id, retention);
+        self
+    }
+
+    /// Returns `true` when nothing ever expires.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.tenants.is_empty() && self.sources.is_empty()
+    }
+
+    /// Instant before which `source_id`'s telemetry in `tenant` has expired, if it ever does.
+    pub fn cutoff(
+        &self,
+        tenant: &TenantId,
+        source_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let retention = self
+            .sources
+            .get(&source_id)
+            .or_else(|| self.tenants.get(tenant))
+            .or(self.default.as_ref())?;
+        Some(now - *retention)
+    }
+
+    /// Instant before which all telemetry has expired, whatever its tenant or source.
+    ///
+    /// `None` without a default retention, since unknown tenants then keep their data forever.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeDelta, Utc};
+    /// use rustpulse::core::application::retention::RetentionPolicy;
+    /// use uuid::Uuid;
+    ///
+    /// let now = Utc::now();
+    /// let policy = RetentionPolicy::new(Some(TimeDelta::days(30)))
+    ///     .with_source(Uuid::nil(), TimeDelta::days(90));
+    /// assert_eq!(policy.floor(now), Some(now - TimeDelta::days(90)));
+    /// assert_eq!(RetentionPolicy::default().floor(now), None);
+    /// ```
+    pub fn floor(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let longest = self
+            .tenants
+            .values()
+            .chain(self.sources.values())
+            .fold(self.default?, |longest, retention| longest.max(*retention));
+        Some(now - longest)
+    }
+
+    /// Splits the policy into non-overlapping deletes, most specific last.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeDelta, Utc};
+    /// use rustpulse::core::application::retention::RetentionPolicy;
+    /// use rustpulse::core::domains::tenant::TenantId;
+    ///
+    /// let acme = TenantId::parse("acme").unwrap();
+    /// let rules = RetentionPolicy::new(Some(TimeDelta::days(30)))
+    ///     .with_tenant(acme.clone(), TimeDelta::days(90))
+    ///     .rules(Utc::now());
+    ///
+    /// assert_eq!(rules.len(), 2);
+    /// assert_eq!(rules[0].except_tenants, vec![acme.clone()]);
+    /// assert_eq!(rules[1].tenant, Some(acme));
+    /// ```
+    pub fn rules(&self, now: DateTime<Utc>) -> Vec<ExpiryRule> {
+        let overridden_tenants: Vec<TenantId> = self.tenants.keys().cloned().collect();
+        let overridden_sources: Vec<Uuid> = self.sources.keys().copied().collect();
+
+        let mut rules = Vec::new();
+        if let Some(default) = self.default {
+            rules.push(ExpiryRule {
+                tenant: None,
+                source_id: None,
+                cutoff: now - default,
+                except_tenants: overridden_tenants,
+                except_sources: overridden_sources.clone(),
+            });
+        }
+        for (tenant, retention) in &self.tenants {
+            rules.push(ExpiryRule {
+                tenant: Some(tenant.clone()),
+                source_id: None,
+                cutoff: now - *retention,
+                except_tenants: Vec::new(),
+                except_sources: overridden_sources.clone(),
+            });
+        }
+        for (source_id, retention) in &self.sources {
+            rules.push(ExpiryRule {
+                tenant: None,
+                source_id: Some(*source_id),
+                cutoff: now - *retention,
+                except_tenants: Vec::new(),
+                except_sources: Vec::new(),
+            });
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_source_override_wins_over_tenant_and_default() {
+        let acme = TenantId::parse("acme").unwrap();
+        let noisy = Uuid::from_u128(7);
+        let policy = RetentionPolicy::new(None)
+            .with_tenant(acme.clone(), TimeDelta::days(90))
+            .with_source(noisy, TimeDelta::days(1));
+        let now = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            policy.cutoff(&acme, noisy, now),
+            Some(now - TimeDelta::days(1))
+        );
+        assert_eq!(
+            policy.cutoff(&acme, Uuid::nil(), now),
+            Some(now - TimeDelta::days(90))
+        );
+        assert_eq!(policy.cutoff(&TenantId::default(), Uuid::nil(), now), None);
+
+        let rules = policy.rules(now);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].except_sources, vec![noisy]);
+        assert_eq!(rules[1].source_id, Some(noisy));
+        assert!(RetentionPolicy::default().rules(now).is_empty());
+    }
+}
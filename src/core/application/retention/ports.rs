@@ -0,0 +1,3 @@
+//! Port definitions for the retention application module.
+
+pub mod output;
@@ -0,0 +1,3 @@
+//! Output ports for the retention application module.
+
+pub mod expiring_store;
@@ -0,0 +1,21 @@
+//! Output port for deleting telemetry that has outlived its retention.
+//!
+//! Unlike [`TelemetryRepository`](crate::core::application::telemetry::TelemetryRepository),
+//! this is a maintenance operation spanning every tenant.
+
+use chrono::{DateTime, Utc};
+
+use crate::core::application::retention::policy::RetentionPolicy;
+
+#[async_trait::async_trait]
+/// Storage that can expire telemetry according to a [`RetentionPolicy`].
+pub trait ExpiringStore: Send + Sync {
+    /// Name of the backend, used in logs and metrics, e.g. `postgres`.
+    fn name(&self) -> &'static str;
+
+    /// Deletes telemetry older than its retention at `now` and returns how many records went.
+    ///
+    /// Backends may keep expired records a little longer when they can only delete in
+    /// larger units (e.g. whole files), but must never delete a record still in retention.
+    async fn expire(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> anyhow::Result<u64>;
+}
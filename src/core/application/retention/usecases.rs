@@ -0,0 +1,3 @@
+//! Retention use case implementations.
+
+pub mod retention_service;
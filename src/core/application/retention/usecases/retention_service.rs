@@ -0,0 +1,144 @@
+//! Background enforcement of a [`RetentionPolicy`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn demo(store: std::sync::Arc<dyn rustpulse::core::application::retention::ExpiringStore>) {
+//! use chrono::TimeDelta;
+//! use rustpulse::core::application::retention::{RetentionPolicy, RetentionService};
+//! use std::sync::Arc;
+//!
+//! let policy = RetentionPolicy::new(Some(TimeDelta::days(30)));
+//! let _task = Arc::new(RetentionService::new(store, policy)).spawn();
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{Instrument, field::Empty};
+
+use crate::core::application::retention::policy::RetentionPolicy;
+use crate::core::application::retention::ports::output::expiring_store::ExpiringStore;
+
+/// Time between two retention runs.
+pub const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically deletes telemetry that has outlived its retention.
+pub struct RetentionService {
+    store: Arc<dyn ExpiringStore>,
+    policy: RetentionPolicy,
+    interval: Duration,
+}
+
+impl RetentionService {
+    /// Creates a service enforcing `policy` on `store` every [`RETENTION_INTERVAL`].
+    pub fn new(store: Arc<dyn ExpiringStore>, policy: RetentionPolicy) -> Self {
+        Self {
+            store,
+            policy,
+            interval: RETENTION_INTERVAL,
+        }
+    }
+
+    /// Overrides the time between runs.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Expires everything older than its retention at `now` and returns the number of records deleted.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let span = tracing::info_span!(
+            "usecase.retention.expire",
+            backend = self.store.name(),
+            expired = Empty,
+            outcome = Empty,
+        );
+        let result = self
+            .store
+            .expire(&self.policy, now)
+            .instrument(span.clone())
+            .await;
+
+        match &result {
+            Ok(expired) => {
+                span.record("expired", expired);
+                span.record("outcome", "ok");
+                metrics::counter!(
+                    "rustpulse_retention_expired_records_total",
+                    "backend" => self.store.name(),
+                )
+                .increment(*expired);
+                tracing::info!(parent: &span, expired, "retention run finished");
+            }
+            Err(err) => {
+                span.record("outcome", "error");
+                tracing::warn!(parent: &span, error = %err, "retention run failed");
+            }
+        }
+        result
+    }
+
+    /// Runs [`Self::run_once`] now and then every interval until the process exits.
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                // Failures are logged by `run_once`; the next tick retries.
+                let _ = self.run_once(Utc::now()).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingStore {
+        runs: Mutex<Vec<DateTime<Utc>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ExpiringStore for RecordingStore {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn expire(
+            &self,
+            policy: &RetentionPolicy,
+            now: DateTime<Utc>,
+        ) -> anyhow::Result<u64> {
+            self.runs.lock().unwrap().push(now);
+            Ok(policy.rules(now).len() as u64 * 10)
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_run_once_counts_expired_records() {
+        let recorder = crate::infra::metrics::build_recorder();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let store = Arc::new(RecordingStore::default());
+        let service = RetentionService::new(
+            store.clone(),
+            RetentionPolicy::new(Some(TimeDelta::days(7))),
+        );
+        let now = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(service.run_once(now).await.unwrap(), 10);
+        assert_eq!(*store.runs.lock().unwrap(), vec![now]);
+        assert!(
+            recorder
+                .handle()
+                .render()
+                .contains(r#"rustpulse_retention_expired_records_total{backend="recording"} 10"#)
+        );
+    }
+}
@@ -1,13 +1,33 @@
 //! Telemetry use cases and ports.
 
+pub mod aggregate;
+pub mod live;
 pub mod ports;
+pub mod query;
+pub mod quota;
 pub mod usecases;
 
+/// Aggregation query and result types.
+pub use aggregate::{AggregateBucket, AggregateQuery, BucketAggregator, BucketWidth, MetricStats};
+/// Live-tail subscription types.
+pub use live::{
+    LIVE_TAIL_CAPACITY, LiveTailChannels, LiveTailEvent, LiveTailFilter, LiveTailSubscription,
+};
+/// Use case for aggregating telemetry into time buckets.
+pub use ports::input::telemetry_aggregate_usecase::TelemetryAggregateCase;
 /// Use case for ingesting telemetry.
 pub use ports::input::telemetry_ingest_usecase::TelemetryIngestCase;
+/// Use case for following telemetry as it is ingested.
+pub use ports::input::telemetry_live_tail_usecase::TelemetryLiveTailCase;
 /// Use case for querying telemetry.
 pub use ports::input::telemetry_query_usecase::TelemetryQueryCase;
 /// Output port for telemetry persistence.
-pub use ports::output::telemetry_repository::TelemetryRepository;
+pub use ports::output::telemetry_repository::{
+    StorageBackpressure, TelemetryRepository, TelemetryStream,
+};
+/// Structured query types shared by the query ports.
+pub use query::{SortOrder, TelemetryCursor, TelemetryPage, TelemetryQuery, TelemetryQueryError};
+/// Daily per-tenant ingest quota.
+pub use quota::{DailyQuota, QuotaExceeded};
 /// Default telemetry use case implementation.
 pub use usecases::telemetry_service::TelemetryService;
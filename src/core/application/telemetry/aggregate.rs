@@ -0,0 +1,361 @@
+//! Time-bucketed aggregation of telemetry metrics (downsampling).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rustpulse::core::application::telemetry::{AggregateQuery, BucketAggregator, BucketWidth};
+//!
+//! let query = AggregateQuery {
+//!     bucket: BucketWidth::FiveMinutes,
+//!     ..Default::default()
+//! };
+//!
+//! let buckets = BucketAggregator::new(query.bucket).finish();
+//! assert!(buckets.is_empty());
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::core::application::telemetry::query::TelemetryQueryError;
+use crate::core::domains::telemetry::Telemetry;
+
+/// Percentiles reported for every metric, as fractions.
+pub const PERCENTILES: [f64; 3] = [0.5, 0.95, 0.99];
+
+/// Samples kept per metric and bucket for percentiles.
+///
+/// Up to this many samples percentiles are exact; past it they are computed from a
+/// uniform random sample of this size, so memory stays bounded however dense the data.
+/// `count`, `min`, `max` and `avg` are always exact.
+pub const MAX_PERCENTILE_SAMPLES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+/// Width of an aggregation bucket. Buckets are aligned to the Unix epoch.
+pub enum BucketWidth {
+    /// One minute.
+    #[serde(rename = "1m")]
+    #[default]
+    OneMinute,
+    /// Five minutes.
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    /// One hour.
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+impl BucketWidth {
+    /// Bucket width in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::core::application::telemetry::BucketWidth;
+    ///
+    /// assert_eq!(BucketWidth::OneHour.as_seconds(), 3600);
+    /// ```
+    pub fn as_seconds(self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 300,
+            Self::OneHour => 3600,
+        }
+    }
+
+    /// Postgres interval literal for this width.
+    pub fn as_pg_interval(self) -> &'static str {
+        match self {
+            Self::OneMinute => "1 minute",
+            Self::FiveMinutes => "5 minutes",
+            Self::OneHour => "1 hour",
+        }
+    }
+
+    /// Start of the bucket containing `timestamp`.
+    pub fn bucket_start(self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let width = self.as_seconds();
+        let start = timestamp.timestamp().div_euclid(width) * width;
+        DateTime::<Utc>::from_timestamp(start, 0).unwrap_or(timestamp)
+    }
+}
+
+impl FromStr for BucketWidth {
+    type Err = TelemetryQueryError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "1m" => Ok(Self::OneMinute),
+            "5m" => Ok(Self::FiveMinutes),
+            "1h" => Ok(Self::OneHour),
+            other => Err(TelemetryQueryError::InvalidBucket(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Filters and bucket width for an aggregation query.
+pub struct AggregateQuery {
+    /// Only aggregate telemetry for this source id (raw, validated by the repository).
+    pub source_id: Option<String>,
+    /// Inclusive lower bound on `timestamp`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `timestamp`.
+    pub to: Option<DateTime<Utc>>,
+    /// Bucket width.
+    pub bucket: BucketWidth,
+}
+
+impl AggregateQuery {
+    /// Parses the `source_id` filter.
+    pub fn source_filter(&self) -> Result<Option<Uuid>, uuid::Error> {
+        self.source_id.as_deref().map(Uuid::parse_str).transpose()
+    }
+
+    /// Returns `true` when `telemetry` passes the source and range filters.
+    pub fn matches(&self, telemetry: &Telemetry, source: Option<Uuid>) -> bool {
+        source.is_none_or(|id| telemetry.source_id == id)
+            && self.from.is_none_or(|from| telemetry.timestamp >= from)
+            && self.to.is_none_or(|to| telemetry.timestamp < to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Summary statistics of one metric within a bucket (null samples are ignored).
+pub struct MetricStats {
+    /// Number of non-null samples.
+    pub count: u64,
+    /// Smallest sample.
+    pub min: f64,
+    /// Largest sample.
+    pub max: f64,
+    /// Arithmetic mean.
+    pub avg: f64,
+    /// Median (continuous percentile).
+    pub p50: f64,
+    /// 95th percentile (continuous).
+    pub p95: f64,
+    /// 99th percentile (continuous).
+    pub p99: f64,
+}
+
+impl MetricStats {
+    /// Computes stats from raw samples; `None` when there are no samples.
+    pub fn from_samples(samples: Vec<f64>) -> Option<Self> {
+        let mut sketch = MetricSketch::default();
+        samples.into_iter().for_each(|v| sketch.push(v));
+        sketch.finish()
+    }
+}
+
+/// Running stats of one metric, keeping at most [`MAX_PERCENTILE_SAMPLES`] samples.
+#[derive(Default)]
+struct MetricSketch {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    // Reservoir sample (algorithm R): every value seen has the same chance to be in it.
+    reservoir: Vec<f64>,
+}
+
+impl MetricSketch {
+    fn push(&mut self, value: f64) {
+        if self.count == 0 {
+            (self.min, self.max) = (value, value);
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        self.sum += value;
+
+        if self.reservoir.len() < MAX_PERCENTILE_SAMPLES {
+            self.reservoir.push(value);
+        } else {
+            let slot = rand::random_range(0..self.count) as usize;
+            if let Some(kept) = self.reservoir.get_mut(slot) {
+                *kept = value;
+            }
+        }
+    }
+
+    fn finish(mut self) -> Option<MetricStats> {
+        if self.count == 0 {
+            return None;
+        }
+        self.reservoir.sort_by(f64::total_cmp);
+        Some(MetricStats {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            avg: self.sum / self.count as f64,
+            p50: percentile_cont(&self.reservoir, PERCENTILES[0]),
+            p95: percentile_cont(&self.reservoir, PERCENTILES[1]),
+            p99: percentile_cont(&self.reservoir, PERCENTILES[2]),
+        })
+    }
+}
+
+/// Linear-interpolated percentile over sorted samples (same definition as
+/// Postgres `percentile_cont`).
+fn percentile_cont(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Aggregated metrics for one source over one time bucket.
+pub struct AggregateBucket {
+    /// Source the samples came from.
+    pub source_id: Uuid,
+    /// Inclusive start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Number of telemetry rows in the bucket.
+    pub count: u64,
+    /// CPU statistics.
+    pub cpu: Option<MetricStats>,
+    /// Memory statistics.
+    pub memory: Option<MetricStats>,
+    /// Temperature statistics.
+    pub temperature: Option<MetricStats>,
+}
+
+#[derive(Default)]
+struct BucketSamples {
+    count: u64,
+    cpu: MetricSketch,
+    memory: MetricSketch,
+    temperature: MetricSketch,
+}
+
+/// Single-pass aggregator: feed telemetry in any order, then call [`BucketAggregator::finish`].
+///
+/// Memory grows with the number of buckets, not samples: see [`MAX_PERCENTILE_SAMPLES`].
+pub struct BucketAggregator {
+    width: BucketWidth,
+    buckets: BTreeMap<(Uuid, DateTime<Utc>), BucketSamples>,
+}
+
+impl BucketAggregator {
+    /// Creates an empty aggregator for the given bucket width.
+    pub fn new(width: BucketWidth) -> Self {
+        Self {
+            width,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Adds one telemetry sample.
+    pub fn push(&mut self, telemetry: &Telemetry) {
+        let key = (
+            telemetry.source_id,
+            self.width.bucket_start(telemetry.timestamp),
+        );
+        let samples = self.buckets.entry(key).or_default();
+        samples.count += 1;
+        telemetry.cpu.into_iter().for_each(|v| samples.cpu.push(v));
+        telemetry
+            .memory
+            .into_iter()
+            .for_each(|v| samples.memory.push(v));
+        if let Some(v) = telemetry.temperature {
+            samples.temperature.push(f64::from(v));
+        }
+    }
+
+    /// Returns buckets ordered by `(source_id, bucket_start)`.
+    pub fn finish(self) -> Vec<AggregateBucket> {
+        self.buckets
+            .into_iter()
+            .map(|((source_id, bucket_start), samples)| AggregateBucket {
+                source_id,
+                bucket_start,
+                count: samples.count,
+                cpu: samples.cpu.finish(),
+                memory: samples.memory.finish(),
+                temperature: samples.temperature.finish(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample(secs: i64, source_id: Uuid, cpu: Option<f64>) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: DateTime::<Utc>::from_timestamp(secs, 0).expect("valid timestamp"),
+            cpu,
+            memory: None,
+            temperature: Some(20.0),
+            extras: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_percentile_cont_matches_postgres_definition() {
+        let stats = MetricStats::from_samples(vec![4.0, 1.0, 3.0, 2.0]).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 4.0);
+        assert_eq!(stats.avg, 2.5);
+        assert_eq!(stats.p50, 2.5);
+        assert!((stats.p95 - 3.85).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregator_groups_by_source_and_epoch_aligned_bucket() {
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+        let mut agg = BucketAggregator::new(BucketWidth::OneMinute);
+        for t in [
+            sample(120, a, Some(1.0)),
+            sample(179, a, None),
+            sample(180, a, Some(3.0)),
+            sample(150, b, Some(5.0)),
+        ] {
+            agg.push(&t);
+        }
+
+        let buckets = agg.finish();
+        assert_eq!(buckets.len(), 3);
+
+        assert_eq!(buckets[0].source_id, a);
+        assert_eq!(buckets[0].bucket_start.timestamp(), 120);
+        assert_eq!(buckets[0].count, 2);
+        assert_eq!(buckets[0].cpu.as_ref().map(|s| s.count), Some(1));
+        assert_eq!(buckets[0].temperature.as_ref().map(|s| s.count), Some(2));
+        assert!(buckets[0].memory.is_none());
+
+        assert_eq!(buckets[1].bucket_start.timestamp(), 180);
+        assert_eq!(buckets[2].source_id, b);
+    }
+
+    #[test]
+    fn test_sketch_bounds_samples_and_keeps_exact_summary() {
+        let mut sketch = MetricSketch::default();
+        for i in 0..100_000 {
+            sketch.push(f64::from(i % 1000));
+        }
+        assert_eq!(sketch.reservoir.len(), MAX_PERCENTILE_SAMPLES);
+
+        let stats = sketch.finish().unwrap();
+        assert_eq!(stats.count, 100_000);
+        assert_eq!(stats.min, 0.0);
+        assert_eq!(stats.max, 999.0);
+        assert!((stats.avg - 499.5).abs() < 1e-9);
+        // Approximate past the cap; a uniform sample keeps the median close.
+        assert!((stats.p50 - 499.5).abs() < 100.0, "p50 = {}", stats.p50);
+    }
+}
@@ -0,0 +1,243 @@
+//! Live tail of accepted telemetry: per-tenant channels, filters and per-subscriber receive side.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rustpulse::core::application::telemetry::LiveTailFilter;
+//! use uuid::Uuid;
+//!
+//! let filter = LiveTailFilter {
+//!     source_id: Some(Uuid::nil()),
+//!     ..Default::default()
+//! };
+//!
+//! assert!(filter.source_id.is_some());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+
+/// Number of records buffered per subscriber before it starts lagging.
+pub const LIVE_TAIL_CAPACITY: usize = 256;
+
+/// One broadcast channel per tenant with live-tail subscribers.
+///
+/// Tenants never share a buffer, so a burst from one tenant cannot make another
+/// tenant's subscribers lag. A channel is dropped once its last subscriber is gone.
+///
+/// # Examples
+///
+/// ```rust
+/// use rustpulse::core::application::telemetry::{LiveTailChannels, LiveTailFilter};
+/// use rustpulse::core::domains::tenant::TenantId;
+///
+/// let channels = LiveTailChannels::default();
+/// let _sub = channels.subscribe(&TenantId::default(), LiveTailFilter::default());
+///
+/// assert_eq!(channels.tenants(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct LiveTailChannels {
+    senders: Mutex<HashMap<TenantId, broadcast::Sender<Telemetry>>>,
+}
+
+impl LiveTailChannels {
+    /// Opens a subscription to `tenant`'s records, creating its channel on first use.
+    pub fn subscribe(&self, tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+        let mut senders = self.senders.lock().unwrap_or_else(|e| e.into_inner());
+        // Channels of tenants that stopped tailing without publishing since.
+        senders.retain(|_, sender| sender.receiver_count() > 0);
+        let receiver = senders
+            .entry(tenant.clone())
+            .or_insert_with(|| broadcast::channel(LIVE_TAIL_CAPACITY).0)
+            .subscribe();
+        LiveTailSubscription::new(receiver, filter)
+    }
+
+    /// Delivers `telemetry` to the subscribers of `tenant`, if any.
+    pub fn publish(&self, tenant: &TenantId, telemetry: Telemetry) {
+        let mut senders = self.senders.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sender) = senders.get(tenant)
+            && sender.send(telemetry).is_err()
+        {
+            // Every subscriber is gone.
+            senders.remove(tenant);
+        }
+    }
+
+    /// Number of tenants that currently have a channel.
+    pub fn tenants(&self) -> usize {
+        self.senders.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Restricts a live-tail subscription to matching records.
+pub struct LiveTailFilter {
+    /// Only deliver telemetry from this source.
+    pub source_id: Option<Uuid>,
+    /// Only deliver telemetry reported by this server.
+    pub server_id: Option<Uuid>,
+}
+
+impl LiveTailFilter {
+    /// Returns `true` when `telemetry` passes the filter.
+    pub fn matches(&self, telemetry: &Telemetry) -> bool {
+        self.source_id.is_none_or(|id| telemetry.source_id == id)
+            && self.server_id.is_none_or(|id| telemetry.server_id == id)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Item delivered to a live-tail subscriber.
+pub enum LiveTailEvent {
+    /// A newly accepted telemetry record.
+    Telemetry(Telemetry),
+    /// The subscriber fell behind and `skipped` records were dropped for it.
+    Lagged {
+        /// Number of records the subscriber missed.
+        skipped: u64,
+    },
+}
+
+/// Receive side of a live-tail subscription.
+///
+/// Each subscriber has its own bounded view of its tenant's stream; a slow subscriber
+/// never blocks ingest or other subscribers, it gets [`LiveTailEvent::Lagged`] instead.
+pub struct LiveTailSubscription {
+    receiver: broadcast::Receiver<Telemetry>,
+    filter: LiveTailFilter,
+}
+
+impl LiveTailSubscription {
+    /// Wraps a receiver on one tenant's channel with a filter.
+    pub fn new(receiver: broadcast::Receiver<Telemetry>, filter: LiveTailFilter) -> Self {
+        Self { receiver, filter }
+    }
+
+    /// Waits for the next matching event; `None` once the publisher is gone.
+    pub async fn recv(&mut self) -> Option<LiveTailEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(telemetry) if self.filter.matches(&telemetry) => {
+                    return Some(LiveTailEvent::Telemetry(telemetry));
+                }
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    return Some(LiveTailEvent::Lagged { skipped });
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn telemetry(source_id: Uuid) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: Utc::now(),
+            cpu: None,
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscription_skips_non_matching_and_foreign_tenant_records() {
+        let wanted = Uuid::new_v4();
+        let channels = LiveTailChannels::default();
+        let mut sub = channels.subscribe(
+            &TenantId::default(),
+            LiveTailFilter {
+                source_id: Some(wanted),
+                ..Default::default()
+            },
+        );
+
+        let other_tenant = TenantId::parse("other").unwrap();
+        channels.publish(&TenantId::default(), telemetry(Uuid::new_v4()));
+        channels.publish(&other_tenant, telemetry(wanted));
+        channels.publish(&TenantId::default(), telemetry(wanted));
+        drop(channels);
+
+        match sub.recv().await {
+            Some(LiveTailEvent::Telemetry(t)) => assert_eq!(t.source_id, wanted),
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert!(sub.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_burst_from_one_tenant_does_not_lag_another() {
+        let channels = LiveTailChannels::default();
+        let noisy = TenantId::parse("noisy").unwrap();
+        let mut quiet_sub = channels.subscribe(&TenantId::default(), LiveTailFilter::default());
+        let mut noisy_sub = channels.subscribe(&noisy, LiveTailFilter::default());
+
+        channels.publish(&TenantId::default(), telemetry(Uuid::nil()));
+        for _ in 0..LIVE_TAIL_CAPACITY + 10 {
+            channels.publish(&noisy, telemetry(Uuid::new_v4()));
+        }
+
+        assert!(matches!(
+            quiet_sub.recv().await,
+            Some(LiveTailEvent::Telemetry(t)) if t.source_id == Uuid::nil()
+        ));
+        assert!(matches!(
+            noisy_sub.recv().await,
+            Some(LiveTailEvent::Lagged { skipped: 10 })
+        ));
+    }
+
+    #[test]
+    fn test_channels_without_subscribers_are_removed() {
+        let channels = LiveTailChannels::default();
+        let other = TenantId::parse("other").unwrap();
+        let sub = channels.subscribe(&TenantId::default(), LiveTailFilter::default());
+        let other_sub = channels.subscribe(&other, LiveTailFilter::default());
+        assert_eq!(channels.tenants(), 2);
+
+        drop(sub);
+        channels.publish(&TenantId::default(), telemetry(Uuid::nil()));
+        assert_eq!(channels.tenants(), 1);
+
+        // A tenant that goes quiet is pruned on the next subscribe.
+        drop(other_sub);
+        let _sub = channels.subscribe(&TenantId::default(), LiveTailFilter::default());
+        assert_eq!(channels.tenants(), 1);
+        channels.publish(&other, telemetry(Uuid::nil()));
+        assert_eq!(channels.tenants(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_gets_lagged_event_then_resumes() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut sub = LiveTailSubscription::new(rx, LiveTailFilter::default());
+
+        for _ in 0..5 {
+            tx.send(telemetry(Uuid::nil())).unwrap();
+        }
+
+        assert!(matches!(
+            sub.recv().await,
+            Some(LiveTailEvent::Lagged { skipped: 3 })
+        ));
+        assert!(matches!(
+            sub.recv().await,
+            Some(LiveTailEvent::Telemetry(_))
+        ));
+    }
+}
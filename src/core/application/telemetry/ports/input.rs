@@ -1,4 +1,6 @@
 //! Input ports for telemetry use cases.
 
+pub mod telemetry_aggregate_usecase;
 pub mod telemetry_ingest_usecase;
+pub mod telemetry_live_tail_usecase;
 pub mod telemetry_query_usecase;
@@ -0,0 +1,15 @@
+//! Input port for telemetry aggregation.
+
+use crate::core::application::telemetry::aggregate::{AggregateBucket, AggregateQuery};
+use crate::core::domains::tenant::TenantId;
+
+#[async_trait::async_trait]
+/// Use case that downsamples stored telemetry into per-source time buckets.
+pub trait TelemetryAggregateCase: Send + Sync {
+    /// Aggregates `tenant`'s telemetry matching `query`, ordered by source then bucket start.
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>>;
+}
@@ -1,10 +1,13 @@
 //! Input port for telemetry ingestion.
 
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
 
 #[async_trait::async_trait]
-/// Use case that accepts telemetry and persists it.
+/// Use case that accepts telemetry and persists it on behalf of a tenant.
 pub trait TelemetryIngestCase {
-    /// Ingests a telemetry datapoint.
-    async fn ingest(&self, telemetry: Telemetry) -> anyhow::Result<()>;
+    /// Ingests a telemetry datapoint owned by `tenant`.
+    async fn ingest(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()>;
+    /// Ingests a batch of telemetry datapoints owned by `tenant` as a single write.
+    async fn ingest_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()>;
 }
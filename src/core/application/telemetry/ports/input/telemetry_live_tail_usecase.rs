@@ -0,0 +1,10 @@
+//! Input port for following telemetry as it is ingested.
+
+use crate::core::application::telemetry::live::{LiveTailFilter, LiveTailSubscription};
+use crate::core::domains::tenant::TenantId;
+
+/// Use case that streams newly accepted telemetry to subscribers.
+pub trait TelemetryLiveTailCase: Send + Sync {
+    /// Subscribes to `tenant`'s telemetry accepted from now on that matches `filter`.
+    fn subscribe(&self, tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription;
+}
@@ -1,10 +1,36 @@
 //! Input port for telemetry queries.
 
+use crate::core::application::telemetry::ports::output::telemetry_repository::TelemetryStream;
+use crate::core::application::telemetry::query::{TelemetryPage, TelemetryQuery};
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
 
 #[async_trait::async_trait]
-/// Use case that queries stored telemetry.
+/// Use case that queries stored telemetry. Every query is confined to one tenant.
 pub trait TelemetryQueryCase: Send + Sync {
-    /// Fetches all telemetry, optionally filtered by a node/source identifier.
-    async fn fetch_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>>;
+    /// Fetches all of `tenant`'s telemetry, optionally filtered by a node/source identifier.
+    async fn fetch_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>>;
+    /// Fetches one page of `tenant`'s telemetry matching a structured query.
+    async fn fetch_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage>;
+    /// Streams all of `tenant`'s telemetry matching a structured query, record by record.
+    ///
+    /// The default implementation streams the result of [`Self::fetch_page`].
+    async fn fetch_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        let page = self.fetch_page(tenant, query).await?;
+        Ok(Box::pin(futures_util::stream::iter(
+            page.items.into_iter().map(Ok),
+        )))
+    }
 }
@@ -1,12 +1,103 @@
 //! Output port for telemetry persistence.
+//!
+//! Every method is scoped to a single tenant; implementations must never return
+//! or aggregate rows stored for another tenant.
 
+use crate::core::application::telemetry::aggregate::{
+    AggregateBucket, AggregateQuery, BucketAggregator,
+};
+use crate::core::application::telemetry::query::{TelemetryPage, TelemetryQuery};
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+use futures_util::stream::BoxStream;
+
+/// Telemetry yielded one record at a time; an `Err` item ends the stream.
+pub type TelemetryStream = BoxStream<'static, anyhow::Result<Telemetry>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("telemetry storage is saturated; retry in {retry_after_secs}s")]
+/// Returned by a write when storage cannot take more data right now.
+///
+/// The write was not accepted; callers should retry later rather than treat it as a failure.
+pub struct StorageBackpressure {
+    /// Suggested delay before retrying.
+    pub retry_after_secs: u64,
+}
 
 #[async_trait::async_trait]
 /// Repository abstraction for storing and retrieving telemetry.
 pub trait TelemetryRepository {
-    /// Persists a telemetry datapoint.
-    async fn save(&self, telemetry: Telemetry) -> anyhow::Result<()>;
-    /// Retrieves all telemetry, optionally filtered by a node/source identifier.
-    async fn query_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>>;
+    /// Persists a telemetry datapoint owned by `tenant`.
+    async fn save(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()>;
+    /// Persists a batch of telemetry datapoints owned by `tenant`.
+    ///
+    /// The default implementation calls [`Self::save`] once per item;
+    /// storage backends should override it with a single bulk write.
+    async fn save_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        for telemetry in batch {
+            self.save(tenant, telemetry).await?;
+        }
+        Ok(())
+    }
+    /// Whether a failed [`Self::save_batch`] is guaranteed to have stored none of the batch.
+    ///
+    /// Only then may a caller retry the whole batch; otherwise the retry would duplicate
+    /// the items written before the failure. The default per-item implementation is not.
+    fn atomic_batches(&self) -> bool {
+        false
+    }
+    /// Retrieves all of `tenant`'s telemetry, optionally filtered by a node/source identifier.
+    async fn query_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>>;
+    /// Retrieves one page of `tenant`'s telemetry matching `query`.
+    ///
+    /// The default implementation filters the result of [`Self::query_all`] in memory;
+    /// storage backends should override it to push the filters down.
+    async fn query_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let items = self.query_all(tenant, query.source_id.clone()).await?;
+        query.paginate(items)
+    }
+    /// Streams `tenant`'s telemetry matching `query` without holding the whole result in memory.
+    ///
+    /// Filters, `cursor` and `limit` apply as in [`Self::query_page`] (no `limit` streams every
+    /// match). Records follow `query.order` unless the backend documents otherwise.
+    ///
+    /// The default implementation materializes the result of [`Self::query_page`];
+    /// backends that can read incrementally should override it.
+    async fn query_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        let page = self.query_page(tenant, query).await?;
+        Ok(Box::pin(futures_util::stream::iter(
+            page.items.into_iter().map(Ok),
+        )))
+    }
+
+    /// Aggregates `tenant`'s telemetry matching `query` into per-source time buckets.
+    ///
+    /// The default implementation aggregates the result of [`Self::query_all`]
+    /// in memory; storage backends should override it to push the work down.
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let source = query.source_filter()?;
+        let mut aggregator = BucketAggregator::new(query.bucket);
+        for telemetry in self.query_all(tenant, query.source_id.clone()).await? {
+            if query.matches(&telemetry, source) {
+                aggregator.push(&telemetry);
+            }
+        }
+        Ok(aggregator.finish())
+    }
 }
@@ -0,0 +1,374 @@
+//! Structured telemetry queries: time range, limit, cursor and sort order.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rustpulse::core::application::telemetry::{SortOrder, TelemetryQuery};
+//!
+//! let query = TelemetryQuery {
+//!     limit: Some(50),
+//!     order: SortOrder::Desc,
+//!     ..Default::default()
+//! };
+//!
+//! assert_eq!(query.limit, Some(50));
+//! ```
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::cmp::Ordering;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::core::domains::telemetry::Telemetry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Ordering of query results by `(timestamp, source_id)`, then storage position.
+pub enum SortOrder {
+    /// Oldest first.
+    #[default]
+    Asc,
+    /// Newest first.
+    Desc,
+}
+
+impl SortOrder {
+    /// Returns the SQL keyword for this order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::core::application::telemetry::SortOrder;
+    ///
+    /// assert_eq!(SortOrder::Desc.as_sql(), "DESC");
+    /// ```
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = TelemetryQueryError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(TelemetryQueryError::InvalidOrder(value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+/// Errors produced while building a [`TelemetryQuery`].
+pub enum TelemetryQueryError {
+    /// The cursor is not one this server issued.
+    #[error("invalid cursor")]
+    InvalidCursor,
+    /// The sort order is neither `asc` nor `desc`.
+    #[error("invalid sort order: {0}")]
+    InvalidOrder(String),
+    /// The bucket width is not one of `1m`, `5m`, `1h`.
+    #[error("invalid bucket width: {0}")]
+    InvalidBucket(String),
+    /// The storage cannot return records in the requested order.
+    #[error("unsupported sort order: {}", .0.as_sql())]
+    UnsupportedOrder(SortOrder),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Keyset position of the last item returned by a page.
+///
+/// Items are ordered by `(timestamp, source_id, id)`, where `id` is the storage position of
+/// a record (the Postgres `id` column, the SQLite `rowid`, the in-memory sequence number or
+/// the position in a JSONL scan), so records sharing a timestamp and source are never skipped.
+///
+/// Clients only ever see the opaque string produced by [`TelemetryCursor::encode`].
+pub struct TelemetryCursor {
+    /// Timestamp of the last returned item.
+    pub timestamp: DateTime<Utc>,
+    /// Source id of the last returned item (tie-breaker for equal timestamps).
+    pub source_id: Uuid,
+    /// Storage position of the last returned item (tie-breaker for equal timestamp and source).
+    pub id: i64,
+}
+
+impl TelemetryCursor {
+    /// Builds the cursor pointing just past `telemetry`, stored at position `id`.
+    pub fn after(id: i64, telemetry: &Telemetry) -> Self {
+        Self {
+            timestamp: telemetry.timestamp,
+            source_id: telemetry.source_id,
+            id,
+        }
+    }
+
+    /// Encodes the cursor as an opaque URL-safe token.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::Utc;
+    /// use rustpulse::core::application::telemetry::TelemetryCursor;
+    /// use uuid::Uuid;
+    ///
+    /// let cursor = TelemetryCursor { timestamp: Utc::now(), source_id: Uuid::new_v4(), id: 42 };
+    /// let decoded = TelemetryCursor::decode(&cursor.encode()).unwrap();
+    /// assert_eq!(decoded, cursor);
+    /// ```
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}|{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            self.source_id,
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Decodes a token produced by [`TelemetryCursor::encode`].
+    pub fn decode(token: &str) -> Result<Self, TelemetryQueryError> {
+        let raw = URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .map_err(|_| TelemetryQueryError::InvalidCursor)?;
+        let raw = String::from_utf8(raw).map_err(|_| TelemetryQueryError::InvalidCursor)?;
+        let mut parts = raw.splitn(3, '|');
+        let (Some(timestamp), Some(source_id), Some(id)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(TelemetryQueryError::InvalidCursor);
+        };
+
+        Ok(Self {
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| TelemetryQueryError::InvalidCursor)?
+                .with_timezone(&Utc),
+            source_id: Uuid::parse_str(source_id)
+                .map_err(|_| TelemetryQueryError::InvalidCursor)?,
+            id: id.parse().map_err(|_| TelemetryQueryError::InvalidCursor)?,
+        })
+    }
+
+    fn cmp_key(&self, id: i64, telemetry: &Telemetry) -> Ordering {
+        (telemetry.timestamp, telemetry.source_id, id).cmp(&(
+            self.timestamp,
+            self.source_id,
+            self.id,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Filters and paging options for a telemetry query.
+pub struct TelemetryQuery {
+    /// Only return telemetry for this source id (raw, validated by the repository).
+    pub source_id: Option<String>,
+    /// Inclusive lower bound on `timestamp`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `timestamp`.
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of items to return (`None` means unbounded).
+    pub limit: Option<usize>,
+    /// Resume after this position (exclusive).
+    pub cursor: Option<TelemetryCursor>,
+    /// Result ordering.
+    pub order: SortOrder,
+}
+
+impl TelemetryQuery {
+    /// Page size used by the HTTP layer when the client does not send `limit`.
+    pub const DEFAULT_LIMIT: usize = 100;
+    /// Largest page size the HTTP layer accepts.
+    pub const MAX_LIMIT: usize = 1000;
+
+    /// Parses the `source_id` filter.
+    pub fn source_filter(&self) -> Result<Option<Uuid>, uuid::Error> {
+        self.source_id.as_deref().map(Uuid::parse_str).transpose()
+    }
+
+    /// Returns `true` when `telemetry` passes the source and range filters.
+    pub fn matches(&self, telemetry: &Telemetry, source: Option<Uuid>) -> bool {
+        if source.is_some_and(|id| telemetry.source_id != id) {
+            return false;
+        }
+        if self.from.is_some_and(|from| telemetry.timestamp < from) {
+            return false;
+        }
+        self.to.is_none_or(|to| telemetry.timestamp < to)
+    }
+
+    /// Returns `true` when `telemetry`, stored at position `id`, comes after the cursor.
+    pub fn after_cursor(&self, id: i64, telemetry: &Telemetry) -> bool {
+        match (&self.cursor, self.order) {
+            (None, _) => true,
+            (Some(cursor), SortOrder::Asc) => cursor.cmp_key(id, telemetry) == Ordering::Greater,
+            (Some(cursor), SortOrder::Desc) => cursor.cmp_key(id, telemetry) == Ordering::Less,
+        }
+    }
+
+    /// Drops items up to the cursor, sorts the rest and cuts them down to one page.
+    ///
+    /// `items` already passed [`Self::matches`] and are paired with their storage position.
+    pub fn finish(&self, items: Vec<(i64, Telemetry)>) -> TelemetryPage {
+        let mut items: Vec<_> = items
+            .into_iter()
+            .filter(|(id, t)| self.after_cursor(*id, t))
+            .collect();
+        items.sort_by(|(a_id, a), (b_id, b)| {
+            let ord = (a.timestamp, a.source_id, a_id).cmp(&(b.timestamp, b.source_id, b_id));
+            match self.order {
+                SortOrder::Asc => ord,
+                SortOrder::Desc => ord.reverse(),
+            }
+        });
+
+        let next_cursor = match self.limit {
+            Some(limit) if items.len() > limit => {
+                items.truncate(limit);
+                items.last().map(|(id, t)| TelemetryCursor::after(*id, t))
+            }
+            _ => None,
+        };
+
+        TelemetryPage {
+            items: items.into_iter().map(|(_, t)| t).collect(),
+            next_cursor,
+        }
+    }
+
+    /// Applies the whole query to an in-memory set of telemetry, using each item's index in
+    /// `items` as its storage position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rustpulse::core::application::telemetry::TelemetryQuery;
+    ///
+    /// let page = TelemetryQuery::default().paginate(Vec::new()).unwrap();
+    /// assert!(page.items.is_empty());
+    /// assert!(page.next_cursor.is_none());
+    /// ```
+    pub fn paginate(&self, items: Vec<Telemetry>) -> anyhow::Result<TelemetryPage> {
+        let source = self.source_filter()?;
+        let items = items
+            .into_iter()
+            .filter(|t| self.matches(t, source))
+            .enumerate()
+            .map(|(i, t)| (i as i64, t))
+            .collect();
+        Ok(self.finish(items))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// One page of query results.
+pub struct TelemetryPage {
+    /// Items in the requested order.
+    pub items: Vec<Telemetry>,
+    /// Cursor for the next page, or `None` when this page is the last one.
+    pub next_cursor: Option<TelemetryCursor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(secs: i64, source_id: Uuid) -> Telemetry {
+        Telemetry {
+            source_id,
+            server_id: Uuid::nil(),
+            timestamp: DateTime::<Utc>::from_timestamp(secs, 0).expect("valid timestamp"),
+            cpu: None,
+            memory: None,
+            temperature: None,
+            extras: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_cursor_roundtrips_and_rejects_garbage() {
+        let cursor = TelemetryCursor::after(7, &at(1_700_000_000, Uuid::new_v4()));
+        assert_eq!(TelemetryCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(TelemetryCursor::decode("not-a-cursor").is_err());
+    }
+
+    #[test]
+    fn test_paginate_walks_all_pages_without_gaps_or_duplicates() {
+        let source = Uuid::new_v4();
+        let items: Vec<_> = (0..5).map(|i| at(1_700_000_000 + i, source)).collect();
+
+        for order in [SortOrder::Asc, SortOrder::Desc] {
+            let mut query = TelemetryQuery {
+                limit: Some(2),
+                order,
+                ..Default::default()
+            };
+            let mut seen = Vec::new();
+            loop {
+                let page = query.paginate(items.clone()).unwrap();
+                seen.extend(page.items.iter().map(|t| t.timestamp.timestamp()));
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor),
+                    None => break,
+                }
+            }
+
+            let mut expected: Vec<i64> = (0..5).map(|i| 1_700_000_000 + i).collect();
+            if order == SortOrder::Desc {
+                expected.reverse();
+            }
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn test_paginate_does_not_skip_records_sharing_timestamp_and_source() {
+        let source = Uuid::new_v4();
+        let items: Vec<_> = (0..5)
+            .map(|i| {
+                let mut t = at(1_700_000_000, source);
+                t.cpu = Some(i as f64);
+                t
+            })
+            .collect();
+
+        for order in [SortOrder::Asc, SortOrder::Desc] {
+            let mut query = TelemetryQuery {
+                limit: Some(2),
+                order,
+                ..Default::default()
+            };
+            let mut seen = Vec::new();
+            loop {
+                let page = query.paginate(items.clone()).unwrap();
+                seen.extend(page.items.iter().filter_map(|t| t.cpu));
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor),
+                    None => break,
+                }
+            }
+            seen.sort_by(f64::total_cmp);
+            assert_eq!(seen, vec![0.0, 1.0, 2.0, 3.0, 4.0], "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_paginate_applies_half_open_time_range() {
+        let source = Uuid::new_v4();
+        let items: Vec<_> = (0..5).map(|i| at(1_700_000_000 + i, source)).collect();
+        let query = TelemetryQuery {
+            from: DateTime::<Utc>::from_timestamp(1_700_000_001, 0),
+            to: DateTime::<Utc>::from_timestamp(1_700_000_003, 0),
+            ..Default::default()
+        };
+
+        let page = query.paginate(items).unwrap();
+        let got: Vec<i64> = page.items.iter().map(|t| t.timestamp.timestamp()).collect();
+        assert_eq!(got, vec![1_700_000_001, 1_700_000_002]);
+    }
+}
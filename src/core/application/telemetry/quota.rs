@@ -0,0 +1,140 @@
+//! Daily per-tenant ingest quotas.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use rustpulse::core::application::telemetry::DailyQuota;
+//! use rustpulse::core::domains::tenant::TenantId;
+//!
+//! let quota = DailyQuota::new(100);
+//! let tenant = TenantId::default();
+//! let now = Utc.with_ymd_and_hms(2026, 2, 18, 23, 0, 0).unwrap();
+//!
+//! assert!(quota.try_consume(&tenant, 100, now).is_ok());
+//! let exceeded = quota.try_consume(&tenant, 1, now).unwrap_err();
+//! assert_eq!(exceeded.retry_after_secs, 3600);
+//! ```
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::core::domains::tenant::TenantId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("daily quota of {limit} records exhausted; resets in {retry_after_secs}s")]
+/// A tenant has used up its records for the current UTC day.
+pub struct QuotaExceeded {
+    /// Records a tenant may ingest per UTC day.
+    pub limit: u64,
+    /// Seconds until the quota resets at the next UTC midnight.
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug)]
+/// Record-count quota per tenant, reset at UTC midnight.
+///
+/// Counters live in memory, so each server instance enforces its own quota.
+pub struct DailyQuota {
+    limit: u64,
+    used: Mutex<HashMap<TenantId, (NaiveDate, u64)>>,
+}
+
+impl DailyQuota {
+    /// Allows each tenant `limit` records per UTC day.
+    pub fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            used: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a tenant may ingest per UTC day.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Reserves `records` for `tenant`, or rejects all of them when they do not fit.
+    pub fn try_consume(
+        &self,
+        tenant: &TenantId,
+        records: u64,
+        now: DateTime<Utc>,
+    ) -> Result<(), QuotaExceeded> {
+        let today = now.date_naive();
+        let mut used = self.used.lock().expect("quota lock poisoned");
+        // Drop yesterday's counters so the map only holds tenants active today.
+        used.retain(|_, (day, _)| *day == today);
+
+        let count = &mut used.entry(tenant.clone()).or_insert((today, 0)).1;
+        if count.saturating_add(records) > self.limit {
+            return Err(QuotaExceeded {
+                limit: self.limit,
+                retry_after_secs: seconds_until_next_day(now),
+            });
+        }
+        *count += records;
+        Ok(())
+    }
+
+    /// Gives back `records` reserved by [`DailyQuota::try_consume`] that were never stored.
+    pub fn release(&self, tenant: &TenantId, records: u64, now: DateTime<Utc>) {
+        let mut used = self.used.lock().expect("quota lock poisoned");
+        if let Some((day, count)) = used.get_mut(tenant)
+            && *day == now.date_naive()
+        {
+            *count = count.saturating_sub(records);
+        }
+    }
+}
+
+fn seconds_until_next_day(now: DateTime<Utc>) -> u64 {
+    let next_midnight = now
+        .date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc());
+    next_midnight.map_or(0, |midnight| {
+        (midnight - now).num_seconds().max(1).unsigned_abs()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_quota_is_tracked_per_tenant_and_resets_at_midnight() {
+        let quota = DailyQuota::new(3);
+        let acme = TenantId::parse("acme").unwrap();
+        let globex = TenantId::parse("globex").unwrap();
+        let evening = Utc.with_ymd_and_hms(2026, 2, 18, 23, 59, 0).unwrap();
+
+        assert!(quota.try_consume(&acme, 2, evening).is_ok());
+        assert_eq!(
+            quota.try_consume(&acme, 2, evening),
+            Err(QuotaExceeded {
+                limit: 3,
+                retry_after_secs: 60
+            })
+        );
+        assert!(quota.try_consume(&globex, 3, evening).is_ok());
+
+        let next_day = evening + chrono::Duration::minutes(2);
+        assert!(quota.try_consume(&acme, 3, next_day).is_ok());
+    }
+
+    #[test]
+    fn test_release_returns_unstored_records() {
+        let quota = DailyQuota::new(2);
+        let tenant = TenantId::default();
+        let now = Utc.with_ymd_and_hms(2026, 2, 18, 12, 0, 0).unwrap();
+
+        quota.try_consume(&tenant, 2, now).unwrap();
+        quota.release(&tenant, 2, now);
+
+        assert!(quota.try_consume(&tenant, 2, now).is_ok());
+    }
+}
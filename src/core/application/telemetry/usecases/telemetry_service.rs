@@ -6,30 +6,47 @@
 //! # async fn demo() -> anyhow::Result<()> {
 //! use rustpulse::core::application::telemetry::{TelemetryQueryCase as _, TelemetryService, TelemetryRepository};
 //! use rustpulse::core::domains::telemetry::Telemetry;
+//! use rustpulse::core::domains::tenant::TenantId;
 //! use std::sync::Arc;
 //!
 //! struct NoopRepo;
 //!
 //! #[async_trait::async_trait]
 //! impl TelemetryRepository for NoopRepo {
-//!     async fn save(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
+//!     async fn save(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
 //!         Ok(())
 //!     }
-//!     async fn query_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+//!     async fn query_all(
+//!         &self,
+//!         _tenant: &TenantId,
+//!         _node_id: Option<String>,
+//!     ) -> anyhow::Result<Vec<Telemetry>> {
 //!         Ok(Vec::new())
 //!     }
 //! }
 //!
 //! let service = TelemetryService::new(Arc::new(NoopRepo));
-//! let _ = service.fetch_all(None).await?;
+//! let _ = service.fetch_all(&TenantId::default(), None).await?;
 //! # Ok(())
 //! # }
 //! ```
 
+use crate::core::application::telemetry::aggregate::{AggregateBucket, AggregateQuery};
+use crate::core::application::telemetry::live::{
+    LiveTailChannels, LiveTailFilter, LiveTailSubscription,
+};
+use crate::core::application::telemetry::ports::input::telemetry_aggregate_usecase::TelemetryAggregateCase;
 use crate::core::application::telemetry::ports::input::telemetry_ingest_usecase::TelemetryIngestCase;
+use crate::core::application::telemetry::ports::input::telemetry_live_tail_usecase::TelemetryLiveTailCase;
 use crate::core::application::telemetry::ports::input::telemetry_query_usecase::TelemetryQueryCase;
-use crate::core::application::telemetry::ports::output::telemetry_repository::TelemetryRepository;
+use crate::core::application::telemetry::ports::output::telemetry_repository::{
+    StorageBackpressure, TelemetryRepository, TelemetryStream,
+};
+use crate::core::application::telemetry::query::{TelemetryPage, TelemetryQuery};
+use crate::core::application::telemetry::quota::{DailyQuota, QuotaExceeded};
 use crate::core::domains::telemetry::Telemetry;
+use crate::core::domains::tenant::TenantId;
+use chrono::Utc;
 use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 use tracing::Instrument as _;
@@ -42,6 +59,10 @@ fn classify_anyhow_error(err: &anyhow::Error) -> (&'static str, &'static str) {
         ("io", "std::io::Error")
     } else if err.is::<serde_json::Error>() {
         ("serde_json", "serde_json::Error")
+    } else if err.is::<QuotaExceeded>() {
+        ("quota_exceeded", "QuotaExceeded")
+    } else if err.is::<StorageBackpressure>() {
+        ("backpressure", "StorageBackpressure")
     } else {
         ("unknown", "unknown")
     }
@@ -58,6 +79,35 @@ fn truncate_for_span(value: String, max_len: usize) -> String {
     out
 }
 
+/// Counts ingested records by outcome (`ok`, `quota_exceeded`, `backpressure` or `error`).
+fn count_ingested(records: usize, result: &anyhow::Result<()>) {
+    let outcome = match result {
+        Ok(()) => "ok",
+        Err(err) if err.is::<QuotaExceeded>() => "quota_exceeded",
+        Err(err) if err.is::<StorageBackpressure>() => "backpressure",
+        Err(_) => "error",
+    };
+    metrics::counter!("rustpulse_ingest_records_total", "outcome" => outcome)
+        .increment(records as u64);
+}
+
+fn record_outcome<T>(span: &tracing::Span, result: &anyhow::Result<T>) {
+    match result {
+        Ok(_) => {
+            span.record("outcome", "ok");
+        }
+        Err(err) => {
+            let (error_code, error_type) = classify_anyhow_error(err);
+            let message = truncate_for_span(err.to_string(), 200);
+            span.record("outcome", "error");
+            span.record("otel.status_code", "ERROR");
+            span.record("error.type", error_type);
+            span.record("error.code", error_code);
+            span.record("exception.message", message.as_str());
+        }
+    }
+}
+
 const INGEST_MAX_ATTEMPTS: usize = 3;
 const INGEST_BASE_BACKOFF_MS: u64 = 1;
 const INGEST_MAX_BACKOFF_MS: u64 = 8;
@@ -79,6 +129,50 @@ fn is_transient_ingest_error(err: &anyhow::Error) -> bool {
     err.is::<std::io::Error>()
 }
 
+/// Runs `save` until it succeeds, fails permanently, or the retry budget is spent.
+async fn save_with_retry<F, Fut>(mut save: F) -> anyhow::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let mut attempt: usize = 1;
+
+    loop {
+        match save().await {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                let is_transient = is_transient_ingest_error(&err);
+
+                if !is_transient {
+                    return Err(err);
+                }
+
+                if attempt >= INGEST_MAX_ATTEMPTS {
+                    return Err(anyhow::Error::new(TelemetryIngestError::RetryExhausted {
+                        attempts: INGEST_MAX_ATTEMPTS,
+                        last_error: err.to_string(),
+                    }));
+                }
+
+                let exp = (attempt - 1) as u32;
+                let backoff_ms = (INGEST_BASE_BACKOFF_MS.saturating_mul(2u64.saturating_pow(exp)))
+                    .min(INGEST_MAX_BACKOFF_MS);
+
+                attempt += 1;
+                metrics::counter!("rustpulse_ingest_retries_total").increment(1);
+                tracing::info!(
+                    attempt,
+                    backoff_ms,
+                    reason = "transient_error",
+                    "retrying telemetry ingest"
+                );
+
+                sleep(Duration::from_millis(backoff_ms)).await;
+            }
+        }
+    }
+}
+
 //use-case engine
 //output port dependencies holding: what rustpulse needs from the outside
 //to complete this use case, I need someone that can save/query telemetry.
@@ -92,6 +186,9 @@ fn is_transient_ingest_error(err: &anyhow::Error) -> bool {
 /// Telemetry use case implementation backed by a [`TelemetryRepository`].
 pub struct TelemetryService {
     repo: Arc<dyn TelemetryRepository + Send + Sync>,
+    // Fan-out of accepted records to their tenant's live-tail subscribers.
+    live: LiveTailChannels,
+    quota: Option<DailyQuota>,
 }
 
 //dependency injection
@@ -103,7 +200,84 @@ impl TelemetryService {
     /// Creates a new service using the provided repository implementation.
     pub fn new(repo: Arc<dyn TelemetryRepository + Send + Sync>) -> Self {
         // Accept Arc instead of plain type
-        Self { repo }
+        Self {
+            repo,
+            live: LiveTailChannels::default(),
+            quota: None,
+        }
+    }
+
+    /// Limits every tenant to `records_per_day` ingested records per UTC day.
+    ///
+    /// Ingest over the limit fails with [`QuotaExceeded`] and nothing is stored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use rustpulse::core::application::telemetry::{TelemetryRepository, TelemetryService};
+    /// use std::sync::Arc;
+    ///
+    /// # fn demo(repo: Arc<dyn TelemetryRepository + Send + Sync>) {
+    /// let _service = TelemetryService::new(repo).with_daily_quota(1_000_000);
+    /// # }
+    /// ```
+    pub fn with_daily_quota(mut self, records_per_day: u64) -> Self {
+        self.quota = Some(DailyQuota::new(records_per_day));
+        self
+    }
+
+    /// Runs `save` once `records` fit in the tenant's quota, giving them back if it fails.
+    async fn save_within_quota(
+        &self,
+        tenant: &TenantId,
+        records: usize,
+        save: impl Future<Output = anyhow::Result<()>>,
+    ) -> anyhow::Result<()> {
+        let Some(quota) = &self.quota else {
+            return save.await;
+        };
+
+        let records = records as u64;
+        quota.try_consume(tenant, records, Utc::now())?;
+        let result = save.await;
+        if result.is_err() {
+            quota.release(tenant, records, Utc::now());
+        }
+        result
+    }
+
+    /// Stores `batch`, retrying transient failures without writing any item twice.
+    ///
+    /// Only an atomic `save_batch` is retried as a whole; otherwise items are saved one
+    /// by one and a retry resumes at the item that failed.
+    async fn save_batch_with_retry(
+        &self,
+        tenant: &TenantId,
+        batch: &[Telemetry],
+        span: &tracing::Span,
+    ) -> anyhow::Result<()> {
+        if self.repo.atomic_batches() {
+            return save_with_retry(|| {
+                self.repo
+                    .save_batch(tenant, batch.to_vec())
+                    .instrument(span.clone())
+            })
+            .await;
+        }
+
+        for telemetry in batch {
+            save_with_retry(|| {
+                self.repo
+                    .save(tenant, telemetry.clone())
+                    .instrument(span.clone())
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn publish(&self, tenant: &TenantId, telemetry: Telemetry) {
+        self.live.publish(tenant, telemetry);
     }
 }
 
@@ -117,9 +291,14 @@ impl TelemetryService {
 //on the service.
 #[async_trait::async_trait]
 impl TelemetryQueryCase for TelemetryService {
-    async fn fetch_all(&self, node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+    async fn fetch_all(
+        &self,
+        tenant: &TenantId,
+        node_id: Option<String>,
+    ) -> anyhow::Result<Vec<Telemetry>> {
         let span = tracing::info_span!(
             "usecase.telemetry.fetch_all",
+            tenant = %tenant,
             outcome = field::Empty,
             "error.type" = field::Empty,
             "error.code" = field::Empty,
@@ -127,31 +306,114 @@ impl TelemetryQueryCase for TelemetryService {
             "exception.message" = field::Empty,
         );
 
-        let result = self.repo.query_all(node_id).instrument(span.clone()).await;
+        let result = self
+            .repo
+            .query_all(tenant, node_id)
+            .instrument(span.clone())
+            .await;
 
-        match &result {
-            Ok(_) => {
-                span.record("outcome", "ok");
-            }
-            Err(err) => {
-                let (error_code, error_type) = classify_anyhow_error(err);
-                let message = truncate_for_span(err.to_string(), 200);
-                span.record("outcome", "error");
-                span.record("otel.status_code", "ERROR");
-                span.record("error.type", error_type);
-                span.record("error.code", error_code);
-                span.record("exception.message", message.as_str());
-            }
+        record_outcome(&span, &result);
+        result
+    }
+
+    async fn fetch_page(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryPage> {
+        let span = tracing::info_span!(
+            "usecase.telemetry.fetch_page",
+            tenant = %tenant,
+            limit = query.limit,
+            order = query.order.as_sql(),
+            item_count = field::Empty,
+            outcome = field::Empty,
+            "error.type" = field::Empty,
+            "error.code" = field::Empty,
+            "otel.status_code" = field::Empty,
+            "exception.message" = field::Empty,
+        );
+
+        let result = self
+            .repo
+            .query_page(tenant, query)
+            .instrument(span.clone())
+            .await;
+
+        if let Ok(page) = &result {
+            span.record("item_count", page.items.len());
         }
+        record_outcome(&span, &result);
+        result
+    }
+
+    async fn fetch_stream(
+        &self,
+        tenant: &TenantId,
+        query: TelemetryQuery,
+    ) -> anyhow::Result<TelemetryStream> {
+        // Covers opening the stream; records are read after the span closes.
+        let span = tracing::info_span!(
+            "usecase.telemetry.fetch_stream",
+            tenant = %tenant,
+            limit = query.limit,
+            order = query.order.as_sql(),
+            outcome = field::Empty,
+            "error.type" = field::Empty,
+            "error.code" = field::Empty,
+            "otel.status_code" = field::Empty,
+            "exception.message" = field::Empty,
+        );
+
+        let result = self
+            .repo
+            .query_stream(tenant, query)
+            .instrument(span.clone())
+            .await;
+
+        record_outcome(&span, &result);
+        result
+    }
+}
+#[async_trait::async_trait]
+impl TelemetryAggregateCase for TelemetryService {
+    async fn aggregate(
+        &self,
+        tenant: &TenantId,
+        query: AggregateQuery,
+    ) -> anyhow::Result<Vec<AggregateBucket>> {
+        let span = tracing::info_span!(
+            "usecase.telemetry.aggregate",
+            tenant = %tenant,
+            bucket = query.bucket.as_pg_interval(),
+            bucket_count = field::Empty,
+            outcome = field::Empty,
+            "error.type" = field::Empty,
+            "error.code" = field::Empty,
+            "otel.status_code" = field::Empty,
+            "exception.message" = field::Empty,
+        );
+
+        let result = self
+            .repo
+            .aggregate(tenant, query)
+            .instrument(span.clone())
+            .await;
 
+        if let Ok(buckets) = &result {
+            span.record("bucket_count", buckets.len());
+        }
+        record_outcome(&span, &result);
         result
     }
 }
+
 #[async_trait::async_trait]
 impl TelemetryIngestCase for TelemetryService {
-    async fn ingest(&self, telemetry: Telemetry) -> anyhow::Result<()> {
+    async fn ingest(&self, tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
         let span = tracing::info_span!(
             "usecase.telemetry.ingest",
+            tenant = %tenant,
             outcome = field::Empty,
             "error.type" = field::Empty,
             "error.code" = field::Empty,
@@ -159,74 +421,65 @@ impl TelemetryIngestCase for TelemetryService {
             "exception.message" = field::Empty,
         );
 
-        let result = {
-            let original = telemetry;
-            let mut attempt: usize = 1;
+        let result = self
+            .save_within_quota(
+                tenant,
+                1,
+                save_with_retry(|| {
+                    self.repo
+                        .save(tenant, telemetry.clone())
+                        .instrument(span.clone())
+                }),
+            )
+            .await;
+
+        count_ingested(1, &result);
+        if result.is_ok() {
+            self.publish(tenant, telemetry);
+        }
+        record_outcome(&span, &result);
+        result
+    }
 
-            loop {
-                let result = self
-                    .repo
-                    .save(original.clone())
-                    .instrument(span.clone())
-                    .await;
-
-                match result {
-                    Ok(()) => break Ok(()),
-                    Err(err) => {
-                        let is_transient = is_transient_ingest_error(&err);
-
-                        if !is_transient {
-                            break Err(err);
-                        }
-
-                        if attempt >= INGEST_MAX_ATTEMPTS {
-                            break Err(anyhow::Error::new(TelemetryIngestError::RetryExhausted {
-                                attempts: INGEST_MAX_ATTEMPTS,
-                                last_error: err.to_string(),
-                            }));
-                        }
-
-                        let exp = (attempt - 1) as u32;
-                        let backoff_ms = (INGEST_BASE_BACKOFF_MS
-                            .saturating_mul(2u64.saturating_pow(exp)))
-                        .min(INGEST_MAX_BACKOFF_MS);
-
-                        attempt += 1;
-                        tracing::info!(
-                            attempt,
-                            backoff_ms,
-                            reason = "transient_error",
-                            "retrying telemetry ingest"
-                        );
-
-                        sleep(Duration::from_millis(backoff_ms)).await;
-                    }
-                }
-            }
-        };
+    async fn ingest_batch(&self, tenant: &TenantId, batch: Vec<Telemetry>) -> anyhow::Result<()> {
+        let span = tracing::info_span!(
+            "usecase.telemetry.ingest_batch",
+            tenant = %tenant,
+            batch_size = batch.len(),
+            outcome = field::Empty,
+            "error.type" = field::Empty,
+            "error.code" = field::Empty,
+            "otel.status_code" = field::Empty,
+            "exception.message" = field::Empty,
+        );
 
-        match &result {
-            Ok(_) => {
-                span.record("outcome", "ok");
-            }
-            Err(err) => {
-                let (error_code, error_type) = classify_anyhow_error(err);
-                let message = truncate_for_span(err.to_string(), 200);
-                span.record("outcome", "error");
-                span.record("otel.status_code", "ERROR");
-                span.record("error.type", error_type);
-                span.record("error.code", error_code);
-                span.record("exception.message", message.as_str());
-            }
+        let result = self
+            .save_within_quota(
+                tenant,
+                batch.len(),
+                self.save_batch_with_retry(tenant, &batch, &span),
+            )
+            .await;
+
+        count_ingested(batch.len(), &result);
+        if result.is_ok() {
+            batch.into_iter().for_each(|t| self.publish(tenant, t));
         }
-
+        record_outcome(&span, &result);
         result
     }
 }
 
+impl TelemetryLiveTailCase for TelemetryService {
+    fn subscribe(&self, tenant: &TenantId, filter: LiveTailFilter) -> LiveTailSubscription {
+        self.live.subscribe(tenant, filter)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::output::in_memory_telemetry_repo::InMemoryTelemetryRepo;
     use anyhow::anyhow;
     use chrono::Utc;
     use std::collections::VecDeque;
@@ -335,27 +588,19 @@ mod tests {
             .map(|(id, span)| (*id, span.clone()))
     }
 
-    struct OkRepo;
     struct ErrQueryRepo;
 
-    #[async_trait::async_trait]
-    impl TelemetryRepository for OkRepo {
-        async fn save(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn query_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
-            Ok(vec![])
-        }
-    }
-
     #[async_trait::async_trait]
     impl TelemetryRepository for ErrQueryRepo {
-        async fn save(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
+        async fn save(&self, _tenant: &TenantId, _telemetry: Telemetry) -> anyhow::Result<()> {
             Ok(())
         }
 
-        async fn query_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+        async fn query_all(
+            &self,
+            _tenant: &TenantId,
+            _node_id: Option<String>,
+        ) -> anyhow::Result<Vec<Telemetry>> {
             Err(anyhow!("boom"))
         }
     }
@@ -368,11 +613,14 @@ mod tests {
         let subscriber = tracing_subscriber::registry().with(CaptureLayer::new(captured.clone()));
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let repo = Arc::new(OkRepo);
+        let repo = Arc::new(InMemoryTelemetryRepo::default());
         let service = TelemetryService::new(repo);
 
         let parent = tracing::info_span!("http.request");
-        let result = service.fetch_all(None).instrument(parent.clone()).await;
+        let result = service
+            .fetch_all(&TenantId::default(), None)
+            .instrument(parent.clone())
+            .await;
         assert!(result.is_ok());
 
         let (parent_id, _) =
@@ -399,7 +647,10 @@ mod tests {
         let service = TelemetryService::new(repo);
 
         let parent = tracing::info_span!("http.request");
-        let result = service.fetch_all(None).instrument(parent.clone()).await;
+        let result = service
+            .fetch_all(&TenantId::default(), None)
+            .instrument(parent.clone())
+            .await;
         assert!(result.is_err());
 
         let (parent_id, _) =
@@ -436,7 +687,7 @@ mod tests {
         let subscriber = tracing_subscriber::registry().with(CaptureLayer::new(captured.clone()));
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let repo = Arc::new(OkRepo);
+        let repo = Arc::new(InMemoryTelemetryRepo::default());
         let service = TelemetryService::new(repo);
 
         let telemetry = Telemetry {
@@ -450,7 +701,10 @@ mod tests {
         };
 
         let parent = tracing::info_span!("http.request");
-        let result = service.ingest(telemetry).instrument(parent.clone()).await;
+        let result = service
+            .ingest(&TenantId::default(), telemetry)
+            .instrument(parent.clone())
+            .await;
         assert!(result.is_ok());
 
         let (parent_id, _) =
@@ -473,6 +727,7 @@ mod tests {
     struct ScriptedSaveRepo {
         calls: AtomicUsize,
         script: Mutex<VecDeque<anyhow::Result<()>>>,
+        saved: Mutex<Vec<Telemetry>>,
     }
 
     impl ScriptedSaveRepo {
@@ -480,26 +735,43 @@ mod tests {
             Self {
                 calls: AtomicUsize::new(0),
                 script: Mutex::new(script.into()),
+                saved: Mutex::new(Vec::new()),
             }
         }
 
         fn calls(&self) -> usize {
             self.calls.load(Ordering::SeqCst)
         }
+
+        fn saved_cpus(&self) -> Vec<f64> {
+            let saved = self.saved.lock().expect("saved lock poisoned");
+            saved.iter().filter_map(|t| t.cpu).collect()
+        }
     }
 
     #[async_trait::async_trait]
     impl TelemetryRepository for ScriptedSaveRepo {
-        async fn save(&self, _telemetry: Telemetry) -> anyhow::Result<()> {
+        async fn save(&self, _tenant: &TenantId, telemetry: Telemetry) -> anyhow::Result<()> {
             self.calls.fetch_add(1, Ordering::SeqCst);
 
             let mut locked = self.script.lock().expect("script lock poisoned");
-            locked
+            let result = locked
                 .pop_front()
-                .unwrap_or_else(|| Err(anyhow!("script exhausted")))
+                .unwrap_or_else(|| Err(anyhow!("script exhausted")));
+            if result.is_ok() {
+                self.saved
+                    .lock()
+                    .expect("saved lock poisoned")
+                    .push(telemetry);
+            }
+            result
         }
 
-        async fn query_all(&self, _node_id: Option<String>) -> anyhow::Result<Vec<Telemetry>> {
+        async fn query_all(
+            &self,
+            _tenant: &TenantId,
+            _node_id: Option<String>,
+        ) -> anyhow::Result<Vec<Telemetry>> {
             Ok(vec![])
         }
     }
@@ -603,7 +875,9 @@ mod tests {
         ]));
         let service = TelemetryService::new(repo.clone());
 
-        let result = service.ingest(sample_telemetry_for_retry_tests()).await;
+        let result = service
+            .ingest(&TenantId::default(), sample_telemetry_for_retry_tests())
+            .await;
         assert!(result.is_ok());
         assert_eq!(repo.calls(), 3);
 
@@ -636,7 +910,7 @@ mod tests {
         let service = TelemetryService::new(repo.clone());
 
         let err = service
-            .ingest(sample_telemetry_for_retry_tests())
+            .ingest(&TenantId::default(), sample_telemetry_for_retry_tests())
             .await
             .expect_err("expected retry exhausted error");
 
@@ -664,11 +938,171 @@ mod tests {
         let service = TelemetryService::new(repo.clone());
 
         let _ = service
-            .ingest(sample_telemetry_for_retry_tests())
+            .ingest(&TenantId::default(), sample_telemetry_for_retry_tests())
             .await
             .expect_err("expected permanent error");
 
         assert_eq!(repo.calls(), 1);
         assert!(!has_retry_event(&captured, "2"));
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_batch_resumes_non_atomic_batch_at_failed_item() {
+        let captured = CapturedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(CaptureEventLayer {
+            captured: captured.clone(),
+        });
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // The default `save_batch` is not atomic: the second item fails once, and
+        // only that item is retried, so the first one is not stored twice.
+        let repo = Arc::new(ScriptedSaveRepo::new(vec![
+            Ok(()),
+            Err(anyhow::Error::new(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "transient",
+            ))),
+            Ok(()),
+            Ok(()),
+        ]));
+        let service = TelemetryService::new(repo.clone());
+
+        let batch: Vec<Telemetry> = (0..3)
+            .map(|i| Telemetry {
+                cpu: Some(i as f64),
+                ..sample_telemetry_for_retry_tests()
+            })
+            .collect();
+        service
+            .ingest_batch(&TenantId::default(), batch)
+            .await
+            .unwrap();
+
+        assert_eq!(repo.calls(), 4);
+        assert_eq!(repo.saved_cpus(), vec![0.0, 1.0, 2.0]);
+        assert!(has_retry_event(&captured, "2"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_live_tail_receives_only_accepted_records_of_its_tenant() {
+        use crate::core::application::telemetry::live::LiveTailEvent;
+
+        let repo = Arc::new(ScriptedSaveRepo::new(vec![
+            Err(anyhow!("permanent")),
+            Ok(()),
+            Ok(()),
+        ]));
+        let service = TelemetryService::new(repo);
+        let tenant = TenantId::default();
+        let mut sub = service.subscribe(&tenant, LiveTailFilter::default());
+
+        let rejected = Telemetry {
+            cpu: Some(1.0),
+            ..sample_telemetry_for_retry_tests()
+        };
+        let foreign = Telemetry {
+            cpu: Some(3.0),
+            ..sample_telemetry_for_retry_tests()
+        };
+        let accepted = Telemetry {
+            cpu: Some(2.0),
+            ..sample_telemetry_for_retry_tests()
+        };
+        let _ = service
+            .ingest(&tenant, rejected)
+            .await
+            .expect_err("expected failure");
+        service
+            .ingest(&TenantId::parse("other").unwrap(), foreign)
+            .await
+            .unwrap();
+        service.ingest(&tenant, accepted.clone()).await.unwrap();
+
+        match sub.recv().await {
+            Some(LiveTailEvent::Telemetry(t)) => assert_eq!(t.cpu, accepted.cpu),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_quota_rejects_ingest_without_storing_and_refunds_failures() {
+        let repo = Arc::new(ScriptedSaveRepo::new(vec![
+            Err(anyhow!("permanent")),
+            Ok(()),
+            Ok(()),
+        ]));
+        let service = TelemetryService::new(repo.clone()).with_daily_quota(2);
+        let tenant = TenantId::default();
+
+        // The failed write does not count against the quota.
+        let _ = service
+            .ingest(&tenant, sample_telemetry_for_retry_tests())
+            .await
+            .expect_err("expected failure");
+        service
+            .ingest_batch(
+                &tenant,
+                vec![
+                    sample_telemetry_for_retry_tests(),
+                    sample_telemetry_for_retry_tests(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let err = service
+            .ingest(&tenant, sample_telemetry_for_retry_tests())
+            .await
+            .expect_err("expected quota error");
+        assert!(err.is::<QuotaExceeded>());
+        assert_eq!(repo.calls(), 3);
+
+        service
+            .ingest(
+                &TenantId::parse("other").unwrap(),
+                sample_telemetry_for_retry_tests(),
+            )
+            .await
+            .expect_err("script exhausted, but the quota let it through");
+        assert_eq!(repo.calls(), 4);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_ingest_counts_records_by_outcome_and_retries() {
+        let recorder = crate::infra::metrics::build_recorder();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let repo = Arc::new(ScriptedSaveRepo::new(vec![
+            Err(anyhow::Error::new(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "transient",
+            ))),
+            Ok(()),
+            Err(anyhow!("permanent")),
+        ]));
+        let service = TelemetryService::new(repo).with_daily_quota(2);
+        let tenant = TenantId::default();
+
+        service
+            .ingest(&tenant, sample_telemetry_for_retry_tests())
+            .await
+            .unwrap();
+        let _ = service
+            .ingest(&tenant, sample_telemetry_for_retry_tests())
+            .await;
+        let _ = service
+            .ingest_batch(
+                &tenant,
+                vec![
+                    sample_telemetry_for_retry_tests(),
+                    sample_telemetry_for_retry_tests(),
+                ],
+            )
+            .await;
+
+        let rendered = recorder.handle().render();
+        assert!(rendered.contains(r#"rustpulse_ingest_records_total{outcome="ok"} 1"#));
+        assert!(rendered.contains(r#"rustpulse_ingest_records_total{outcome="error"} 1"#));
+        assert!(rendered.contains(r#"rustpulse_ingest_records_total{outcome="quota_exceeded"} 2"#));
+        assert!(rendered.contains("rustpulse_ingest_retries_total 1"));
+    }
 }
@@ -1,3 +1,5 @@
 //! Domain model types.
 
+pub mod api_key;
 pub mod telemetry;
+pub mod tenant;
@@ -0,0 +1,68 @@
+//! API key domain model.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::Utc;
+//! use rustpulse::core::domains::api_key::ApiKey;
+//! use rustpulse::core::domains::tenant::TenantId;
+//! use uuid::Uuid;
+//!
+//! let key = ApiKey {
+//!     id: Uuid::new_v4(),
+//!     name: "edge-agent-01".to_string(),
+//!     prefix: "1a2b3c4d".to_string(),
+//!     scopes: vec!["telemetry:write".to_string()],
+//!     server_id: Some(Uuid::new_v4()),
+//!     tenant_id: TenantId::default(),
+//!     created_at: Utc::now(),
+//!     revoked_at: None,
+//! };
+//!
+//! assert!(key.is_active());
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::core::domains::tenant::TenantId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+/// Metadata of a long-lived API key. The secret itself is never stored.
+pub struct ApiKey {
+    /// Unique identifier of the key.
+    pub id: Uuid,
+    /// Human-readable label (e.g. the agent host name).
+    pub name: String,
+    /// Public lookup prefix embedded in the key string.
+    pub prefix: String,
+    /// Scopes granted to callers presenting this key.
+    pub scopes: Vec<String>,
+    /// Server the key is bound to; telemetry for other servers is rejected.
+    pub server_id: Option<Uuid>,
+    /// Tenant the key reads and writes telemetry for.
+    pub tenant_id: TenantId,
+    /// Creation time.
+    pub created_at: DateTime<Utc>,
+    /// Revocation time, if the key has been revoked.
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Returns `true` while the key has not been revoked.
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An API key as persisted: metadata plus the salted secret hash.
+pub struct StoredApiKey {
+    /// Key metadata.
+    pub key: ApiKey,
+    /// Hex-encoded random salt.
+    pub salt: String,
+    /// Hex-encoded `sha256(salt || secret)`.
+    pub secret_hash: String,
+}
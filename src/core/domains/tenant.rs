@@ -0,0 +1,112 @@
+//! Tenant identity: the owner of a slice of telemetry.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rustpulse::core::domains::tenant::TenantId;
+//!
+//! let tenant = TenantId::parse("acme-prod")?;
+//! assert_eq!(tenant.as_str(), "acme-prod");
+//! assert!(TenantId::parse("../etc").is_err());
+//! assert_eq!(TenantId::default().as_str(), TenantId::DEFAULT);
+//! # Ok::<(), rustpulse::core::domains::tenant::TenantIdError>(())
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest accepted tenant id.
+pub const TENANT_ID_MAX_LEN: usize = 64;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+/// Reasons a tenant id is rejected.
+pub enum TenantIdError {
+    /// The id is empty or longer than [`TENANT_ID_MAX_LEN`].
+    #[error("tenant id must be 1 to {TENANT_ID_MAX_LEN} characters")]
+    Length,
+    /// The id contains something other than `a-z`, `0-9`, `-` or `_`.
+    #[error("tenant id may only contain a-z, 0-9, '-' and '_'")]
+    Charset,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+/// Validated tenant identifier.
+///
+/// Restricted to `[a-z0-9_-]{1,64}` so it is safe to embed in file names and SQL settings.
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Tenant used when the caller's identity does not name one.
+    pub const DEFAULT: &'static str = "default";
+
+    /// Validates and wraps a tenant id.
+    pub fn parse(value: &str) -> Result<Self, TenantIdError> {
+        if value.is_empty() || value.len() > TENANT_ID_MAX_LEN {
+            return Err(TenantIdError::Length);
+        }
+        if !value
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+        {
+            return Err(TenantIdError::Charset);
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    /// Returns the id as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` for the [`TenantId::DEFAULT`] tenant.
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = TenantIdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(value: TenantId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rejects_path_and_sql_metacharacters() {
+        for bad in ["", "Acme", "a/b", "..", "a b", "x'; --", &"a".repeat(65)] {
+            assert!(TenantId::parse(bad).is_err(), "{bad:?} should be rejected");
+        }
+        assert!(TenantId::parse("team_42-eu").is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_validates() {
+        let ok: TenantId = serde_json::from_str(r#""acme""#).unwrap();
+        assert_eq!(ok.as_str(), "acme");
+        assert!(serde_json::from_str::<TenantId>(r#""ACME""#).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Infrastructure wiring and runtime services.
 
 pub mod logging;
+pub mod metrics;
 pub mod mock_telemetry;
 pub mod startup;
 pub mod tracing;
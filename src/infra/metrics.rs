@@ -0,0 +1,103 @@
+//! Prometheus exposition of RustPulse's own operational metrics.
+//!
+//! Call sites record through the [`metrics`] facade macros; this module installs the
+//! process-wide Prometheus recorder that `/internal/metrics` renders.
+//!
+//! | Metric | Type | Labels |
+//! | --- | --- | --- |
+//! | `rustpulse_ingest_records_total` | counter | `outcome` (`ok`, `quota_exceeded`, `backpressure`, `error`) |
+//! | `rustpulse_ingest_retries_total` | counter | |
+//! | `rustpulse_crc_check_failures_total` | counter | |
+//! | `rustpulse_signature_failures_total` | counter | `code` (`missing_signature`, `unknown_signing_key`, `invalid_signature`, `stale_timestamp`, `replayed_nonce`) |
+//! | `rustpulse_nonce_cache_evictions_total` | counter | |
+//! | `rustpulse_http_request_duration_seconds` | histogram | `method`, `route`, `status` |
+//! | `rustpulse_repo_duration_seconds` | histogram | `backend`, `operation`, `outcome` |
+//! | `rustpulse_fault_injection_decisions_total` | counter | `decision` (`pass`, `drop`, `corrupt`) |
+//! | `rustpulse_wal_backlog_bytes` | gauge | |
+//! | `rustpulse_wal_flushed_records_total` | counter | |
+//! | `rustpulse_wal_flush_failures_total` | counter | |
+//! | `rustpulse_wal_dead_lettered_records_total` | counter | |
+//! | `rustpulse_retention_expired_records_total` | counter | `backend` |
+//! | `rustpulse_memory_evicted_records_total` | counter | |
+//! | `rustpulse_parquet_archived_records_total` | counter | |
+//! | `rustpulse_parquet_skipped_records_total` | counter | |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rustpulse::infra::metrics;
+//!
+//! let handle = metrics::install_recorder();
+//! ::metrics::counter!("rustpulse_ingest_retries_total").increment(1);
+//! assert!(handle.render().contains("rustpulse_ingest_retries_total"));
+//! ```
+
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle, PrometheusRecorder};
+use std::sync::OnceLock;
+
+/// Histogram buckets, in seconds, shared by every duration metric.
+pub const DURATION_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Builds a Prometheus recorder with RustPulse's bucket layout, without installing it.
+///
+/// # Examples
+///
+/// ```rust
+/// let recorder = rustpulse::infra::metrics::build_recorder();
+/// let _guard = metrics::set_default_local_recorder(&recorder);
+/// metrics::counter!("rustpulse_crc_check_failures_total").increment(1);
+/// assert!(recorder.handle().render().contains("rustpulse_crc_check_failures_total 1"));
+/// ```
+pub fn build_recorder() -> PrometheusRecorder {
+    PrometheusBuilder::new()
+        .set_buckets(&DURATION_BUCKETS)
+        .expect("duration buckets are not empty")
+        .build_recorder()
+}
+
+/// Installs the global recorder on first call and returns a handle to render it.
+///
+/// If another global recorder was installed first, the returned handle renders nothing.
+pub fn install_recorder() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let recorder = build_recorder();
+            let handle = recorder.handle();
+            if metrics::set_global_recorder(recorder).is_err() {
+                tracing::warn!(
+                    "metrics recorder already installed; /internal/metrics will be empty"
+                );
+            }
+            handle
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_durations_render_as_histograms() {
+        let recorder = build_recorder();
+        metrics::with_local_recorder(&recorder, || {
+            metrics::histogram!(
+                "rustpulse_http_request_duration_seconds",
+                "method" => "GET",
+                "route" => "/metrics",
+                "status" => "200",
+            )
+            .record(0.003);
+        });
+
+        let rendered = recorder.handle().render();
+        assert!(rendered.contains("# TYPE rustpulse_http_request_duration_seconds histogram"));
+        assert!(rendered.contains(
+            r#"rustpulse_http_request_duration_seconds_bucket{method="GET",route="/metrics",status="200",le="0.005"} 1"#
+        ));
+    }
+}
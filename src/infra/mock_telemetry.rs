@@ -66,6 +66,7 @@ mod tests {
     use super::*;
     use crate::adapters::output::jsonl_repo::JsonlTelemetryRepo;
     use crate::core::application::telemetry::TelemetryRepository;
+    use crate::core::domains::tenant::TenantId;
     use std::path::PathBuf;
     use tokio::runtime::Runtime;
 
@@ -83,7 +84,7 @@ mod tests {
         // Test the repository
         rt.block_on(async {
             let repo = JsonlTelemetryRepo::new(PathBuf::from(&temp_file_path));
-            let result = repo.query_all(None).await;
+            let result = repo.query_all(&TenantId::default(), None).await;
 
             assert!(result.is_ok());
             let data = result.unwrap();
@@ -1,15 +1,29 @@
 //! Application startup and infrastructure wiring.
 
 use crate::adapters::input::http;
+use crate::adapters::input::http::auth::{self, Authenticator, JwtVerifier, RequireScope, Scope};
+use crate::adapters::input::http::rate_limit::{self, RateLimitConfig, RateLimiter};
+use crate::adapters::input::http::request_signing::{self, RequestVerifier};
+use crate::adapters::output::in_memory_api_key_repo::InMemoryApiKeyRepo;
+use crate::adapters::output::in_memory_telemetry_repo::InMemoryTelemetryRepo;
 use crate::adapters::output::jsonl_repo::JsonlTelemetryRepo;
+use crate::adapters::output::postgres_api_key_repo::PostgresApiKeyRepo;
 use crate::adapters::output::postgres_db;
+use crate::adapters::output::postgres_partitions::PartitionManager;
 use crate::adapters::output::postgres_telemetry_repo::PostgresTelemetryRepo;
+use crate::adapters::output::sqlite_telemetry_repo::{SqliteRepoError, SqliteTelemetryRepo};
+use crate::adapters::output::wal_repo::{WalConfig, WalTelemetryRepo};
 use crate::config::{Config, StorageMode};
+use crate::core::application::api_keys::{ApiKeyRepository, ApiKeyService};
+use crate::core::application::health::{DependencyProbe, ReadinessCase, ReadinessService};
+use crate::core::application::retention::{ExpiringStore, RetentionService};
 use crate::core::application::telemetry::{
-    TelemetryIngestCase, TelemetryQueryCase, TelemetryService,
+    TelemetryAggregateCase, TelemetryIngestCase, TelemetryLiveTailCase, TelemetryQueryCase,
+    TelemetryService,
 };
 use crate::infra::mock_telemetry::MockDataGenerator;
-use axum::Router;
+use axum::{Router, middleware};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::instrument;
@@ -34,6 +48,10 @@ pub enum InfraBootError {
     #[error(transparent)]
     Sqlx(#[from] sqlx::Error),
 
+    /// SQLite storage could not be opened or migrated.
+    #[error(transparent)]
+    Sqlite(#[from] SqliteRepoError),
+
     /// File or network I/O error.
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -83,8 +101,22 @@ pub async fn init_postgres_schema(pool: &sqlx::PgPool) -> Result<(), InfraBootEr
 ///     port: 3000,
 ///     storage_mode: StorageMode::Jsonl,
 ///     database_url: None,
+///     sqlite_path: None,
+///     memory_capacity: None,
 ///     rust_log: None,
 ///     jwt_secret: None,
+///     tenant_rls: false,
+///     rate_limit_per_sec: None,
+///     rate_limit_burst: None,
+///     daily_quota: None,
+///     ingest_signing: None,
+///     wal_dir: None,
+///     wal_max_bytes: None,
+///     retention: Default::default(),
+///     partition_interval: Default::default(),
+///     partition_detach_after: None,
+///     parquet_archive_dir: None,
+///     parquet_extras: None,
 /// };
 ///
 /// let _repo = build_telemetry_repository(&cfg).await?;
@@ -97,11 +129,79 @@ pub async fn build_telemetry_repository(
     Arc<dyn crate::core::application::telemetry::TelemetryRepository + Send + Sync>,
     InfraBootError,
 > {
+    Ok(build_repositories(config).await?.telemetry)
+}
+
+/// Repository implementations selected by the storage mode.
+pub struct Repositories {
+    /// Telemetry storage.
+    pub telemetry: Arc<dyn crate::core::application::telemetry::TelemetryRepository + Send + Sync>,
+    /// API key storage (in memory unless Postgres is configured).
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+    /// Storage dependencies checked by `/health/ready`.
+    pub probes: Vec<Arc<dyn DependencyProbe>>,
+    /// Telemetry storage as seen by the retention task.
+    pub expiring: Arc<dyn ExpiringStore>,
+    /// Postgres partition maintenance (`None` for other backends).
+    pub partitions: Option<Arc<PartitionManager>>,
+}
+
+/// Builds every repository from configuration, sharing one Postgres pool when applicable.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn demo() -> anyhow::Result<()> {
+/// use rustpulse::config::Config;
+/// use rustpulse::infra::startup::build_repositories;
+///
+/// let cfg = Config::from_env()?;
+/// let repos = build_repositories(&cfg).await?;
+/// let _ = repos.api_keys;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn build_repositories(config: &Config) -> Result<Repositories, InfraBootError> {
     match config.storage_mode {
         StorageMode::Jsonl => {
             let temp_file_path: PathBuf =
                 PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("metrics_data.jsonl");
-            Ok(Arc::new(JsonlTelemetryRepo::new(temp_file_path)))
+            let telemetry = Arc::new(JsonlTelemetryRepo::new(temp_file_path));
+            Ok(Repositories {
+                telemetry: telemetry.clone(),
+                api_keys: Arc::new(InMemoryApiKeyRepo::default()),
+                probes: vec![telemetry.clone()],
+                expiring: telemetry,
+                partitions: None,
+            })
+        }
+        StorageMode::Memory => {
+            let telemetry = Arc::new(
+                config
+                    .memory_capacity
+                    .map_or_else(InMemoryTelemetryRepo::default, InMemoryTelemetryRepo::new),
+            );
+            Ok(Repositories {
+                telemetry: telemetry.clone(),
+                api_keys: Arc::new(InMemoryApiKeyRepo::default()),
+                probes: vec![telemetry.clone()],
+                expiring: telemetry,
+                partitions: None,
+            })
+        }
+        StorageMode::Sqlite => {
+            let path = config.sqlite_path.as_ref().map_or_else(
+                || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("rustpulse.db"),
+                PathBuf::from,
+            );
+            let telemetry = Arc::new(SqliteTelemetryRepo::open(path).await?);
+            Ok(Repositories {
+                telemetry: telemetry.clone(),
+                api_keys: Arc::new(InMemoryApiKeyRepo::default()),
+                probes: vec![telemetry.clone()],
+                expiring: telemetry,
+                partitions: None,
+            })
         }
         StorageMode::Postgres => {
             let database_url = config
@@ -110,7 +210,22 @@ pub async fn build_telemetry_repository(
                 .ok_or(InfraBootError::MissingDatabaseUrl)?;
             let pool = postgres_db::connect_pool(database_url).await?;
             init_postgres_schema(&pool).await?;
-            Ok(Arc::new(PostgresTelemetryRepo::new(pool)))
+            let mut partitions = PartitionManager::new(pool.clone(), config.partition_interval);
+            if let Some(age) = config.partition_detach_after {
+                partitions = partitions.with_detach_after(age);
+            }
+            // Ensure the current partitions exist before accepting writes.
+            partitions.run_once(chrono::Utc::now()).await?;
+            let telemetry = Arc::new(
+                PostgresTelemetryRepo::new(pool.clone()).with_row_level_security(config.tenant_rls),
+            );
+            Ok(Repositories {
+                telemetry: telemetry.clone(),
+                api_keys: Arc::new(PostgresApiKeyRepo::new(pool)),
+                probes: vec![telemetry.clone()],
+                expiring: telemetry,
+                partitions: Some(Arc::new(partitions)),
+            })
         }
     }
 }
@@ -132,6 +247,9 @@ pub async fn build_telemetry_repository(
 /// # }
 /// ```
 pub async fn start_server(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    // Installed first so nothing recorded during startup is lost.
+    let metrics_handle = crate::infra::metrics::install_recorder();
+
     let temp_file_path: PathBuf =
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("metrics_data.jsonl");
 
@@ -142,19 +260,125 @@ pub async fn start_server(config: &Config) -> Result<(), Box<dyn std::error::Err
         MockDataGenerator::generate_mock_data(&temp_file_path, 20)?;
     }
 
-    let repo = build_telemetry_repository(config)
+    let mut repos = build_repositories(config)
         .await
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
-    let service = Arc::new(TelemetryService::new(repo.clone()));
+    // Archived below the write-ahead log, so only records that reached storage are archived.
+    #[cfg(feature = "parquet")]
+    let mut parquet_archive = None;
+    #[cfg(feature = "parquet")]
+    if let Some(archive_dir) = &config.parquet_archive_dir {
+        use crate::adapters::output::parquet_archive::{ParquetArchiveConfig, ParquetArchiveRepo};
+        let archive_config = ParquetArchiveConfig {
+            layout: config.parquet_extras.as_deref().unwrap_or("json").parse()?,
+            ..ParquetArchiveConfig::default()
+        };
+        let archive = Arc::new(ParquetArchiveRepo::open(
+            archive_dir,
+            repos.telemetry.clone(),
+            archive_config,
+        )?);
+        archive.clone().spawn_roller();
+        tracing::info!(%archive_dir, "Archiving telemetry into hourly Parquet files");
+        repos.telemetry = archive.clone();
+        parquet_archive = Some(archive);
+    }
+    if let Some(wal_dir) = &config.wal_dir {
+        let mut wal_config = WalConfig::default();
+        if let Some(max_bytes) = config.wal_max_bytes {
+            wal_config.max_bytes = max_bytes;
+        }
+        let wal = Arc::new(WalTelemetryRepo::open(
+            wal_dir,
+            repos.telemetry.clone(),
+            wal_config,
+        )?);
+        wal.clone().spawn_flusher();
+        tracing::info!(%wal_dir, "Buffering ingest through the write-ahead log");
+        repos.telemetry = wal.clone();
+        repos.probes.push(wal);
+    }
+    if let Some(partitions) = &repos.partitions {
+        partitions.clone().spawn();
+    }
+    if !config.retention.is_empty() {
+        Arc::new(RetentionService::new(
+            repos.expiring.clone(),
+            config.retention.clone(),
+        ))
+        .spawn();
+    }
+    let mut service = TelemetryService::new(repos.telemetry.clone());
+    if let Some(records_per_day) = config.daily_quota {
+        service = service.with_daily_quota(records_per_day);
+    }
+    let service = Arc::new(service);
+    let api_key_service = Arc::new(ApiKeyService::new(repos.api_keys.clone()));
     let query_service: Arc<dyn TelemetryQueryCase> = service.clone();
     let ingest_service: Arc<dyn TelemetryIngestCase + Send + Sync> = service.clone();
+    let aggregate_service: Arc<dyn TelemetryAggregateCase> = service.clone();
+    let live_tail_service: Arc<dyn TelemetryLiveTailCase> = service.clone();
+    let readiness_service: Arc<dyn ReadinessCase> =
+        Arc::new(ReadinessService::new(repos.probes.clone()));
+
+    // Bearer auth (JWT or API key) is enforced whenever a JWT secret is configured (always in prod).
+    let authenticator = config.jwt_secret.as_ref().map(|secret| {
+        Arc::new(
+            Authenticator::new(JwtVerifier::new(secret)).with_api_keys(api_key_service.clone()),
+        )
+    });
+    if authenticator.is_none() {
+        tracing::warn!(
+            "JWT_SECRET not set: telemetry endpoints are unauthenticated and /admin/api-keys is disabled"
+        );
+    }
+
+    let read_routes = Router::new()
+        .merge(http::telemetry_handler::routes(query_service)) // now injecting state
+        .merge(http::telemetry_handler::aggregate_routes(aggregate_service))
+        .merge(http::telemetry_stream_handler::routes(live_tail_service));
+    let mut write_routes = http::telemetry_handler::ingest_routes(ingest_service);
+    // Innermost, so rate-limited or unauthenticated requests are not buffered for it.
+    if let Some(signing) = &config.ingest_signing {
+        let verifier = Arc::new(RequestVerifier::new(signing.clone()));
+        write_routes = write_routes.route_layer(middleware::from_fn_with_state(
+            verifier,
+            request_signing::verify_signature,
+        ));
+    }
+    // Added before `protect` so it runs after auth and can key on the caller's identity.
+    if let Some(per_second) = config.rate_limit_per_sec {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            per_second,
+            burst: config.rate_limit_burst.unwrap_or(per_second),
+        }));
+        write_routes = write_routes.route_layer(middleware::from_fn_with_state(
+            limiter,
+            rate_limit::rate_limit,
+        ));
+    }
+    let admin_routes = admin(
+        http::api_key_handler::routes(api_key_service),
+        authenticator.as_ref(),
+    );
 
     //Build Router
     let app = Router::new()
         .merge(http::root_handler::routes())
         .merge(http::health_handler::routes())
-        .merge(http::telemetry_handler::routes(query_service)) // now injecting state
-        .merge(http::telemetry_handler::ingest_routes(ingest_service))
+        .merge(http::health_handler::readiness_routes(readiness_service))
+        .merge(http::internal_metrics_handler::routes(metrics_handle))
+        .merge(protect(
+            read_routes,
+            authenticator.as_ref(),
+            Scope::TelemetryRead,
+        ))
+        .merge(protect(
+            write_routes,
+            authenticator.as_ref(),
+            Scope::TelemetryWrite,
+        ))
+        .merge(admin_routes)
         .merge(http::favicon_handler::routes());
 
     let addr = format!("{}:{}", config.host, config.port);
@@ -164,11 +388,91 @@ pub async fn start_server(config: &Config) -> Result<(), Box<dyn std::error::Err
 
     //Start Server
     //Listener handles network → Router handles logic
-    axum::serve(listener, app).await?;
+    let (signalled, on_signal) = tokio::sync::oneshot::channel();
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        let _ = signalled.send(());
+    });
+    // Live-tail streams never end on their own: stop waiting for them after a grace period.
+    tokio::select! {
+        result = server => result?,
+        _ = async {
+            if on_signal.await.is_err() {
+                std::future::pending::<()>().await;
+            }
+            tokio::time::sleep(SHUTDOWN_GRACE).await;
+        } => tracing::warn!("connections still open after the shutdown grace period"),
+    }
+
+    // Buffered hours would otherwise be missing from the archive.
+    #[cfg(feature = "parquet")]
+    if let Some(archive) = parquet_archive {
+        match archive.flush_all().await {
+            Ok(records) => tracing::info!(records, "archive.parquet.flushed"),
+            Err(e) => tracing::warn!(error = %e, "parquet archive flush on shutdown failed"),
+        }
+    }
+    tracing::info!("shutdown.complete");
 
     Ok(())
 }
 
+/// Longest the server waits for open connections once asked to shut down.
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Resolves on Ctrl-C or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "cannot listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "cannot listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("shutdown.signal");
+}
+
+/// Requires a bearer token with `scope` on every route of `router` (no-op without an authenticator).
+fn protect(router: Router, authenticator: Option<&Arc<Authenticator>>, scope: Scope) -> Router {
+    match authenticator {
+        Some(authenticator) => router.route_layer(middleware::from_fn_with_state(
+            RequireScope::new(authenticator.clone(), scope),
+            auth::require_scope,
+        )),
+        None => router,
+    }
+}
+
+/// Guards `router` with [`Scope::ApiKeysAdmin`]; without an authenticator it is not mounted at all.
+fn admin(router: Router, authenticator: Option<&Arc<Authenticator>>) -> Router {
+    match authenticator {
+        Some(_) => protect(router, authenticator, Scope::ApiKeysAdmin),
+        None => Router::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, Utc};
@@ -177,6 +481,7 @@ mod tests {
 
     use super::*;
     use crate::core::domains::telemetry::Telemetry;
+    use crate::core::domains::tenant::TenantId;
 
     fn fixed_time() -> DateTime<Utc> {
         DateTime::<Utc>::from_timestamp(1_700_000_000, 0).expect("valid timestamp")
@@ -191,14 +496,89 @@ mod tests {
             port: 0,
             storage_mode: StorageMode::Jsonl,
             database_url: None,
+            sqlite_path: None,
+            memory_capacity: None,
             rust_log: None,
             jwt_secret: None,
+            tenant_rls: false,
+            rate_limit_per_sec: None,
+            rate_limit_burst: None,
+            daily_quota: None,
+            ingest_signing: None,
+            wal_dir: None,
+            wal_max_bytes: None,
+            retention: Default::default(),
+            partition_interval: Default::default(),
+            partition_detach_after: None,
+            parquet_archive_dir: None,
+            parquet_extras: None,
         };
 
         let repo = build_telemetry_repository(&config).await;
         assert!(repo.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_infra_protect_requires_token_only_when_secret_configured() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let router = || Router::new().route("/metrics", axum::routing::get(|| async { "[]" }));
+        let request = || {
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let open = protect(router(), None, Scope::TelemetryRead);
+        assert_eq!(
+            open.oneshot(request()).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        let authenticator = Arc::new(Authenticator::new(JwtVerifier::new(
+            "test-secret-test-secret-test-secret",
+        )));
+        let guarded = protect(router(), Some(&authenticator), Scope::TelemetryRead);
+        assert_eq!(
+            guarded.oneshot(request()).await.unwrap().status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_infra_admin_routes_are_not_mounted_without_secret() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let router =
+            || Router::new().route("/admin/api-keys", axum::routing::get(|| async { "[]" }));
+        let request = || {
+            Request::builder()
+                .uri("/admin/api-keys")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let unmounted = admin(router(), None);
+        assert_eq!(
+            unmounted.oneshot(request()).await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let authenticator = Arc::new(Authenticator::new(JwtVerifier::new(
+            "test-secret-test-secret-test-secret",
+        )));
+        let guarded = admin(router(), Some(&authenticator));
+        assert_eq!(
+            guarded.oneshot(request()).await.unwrap().status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
     #[tokio::test]
     async fn test_infra_postgres_boot_runs_schema_init_idempotently() {
         let Some(database_url) = std::env::var("DATABASE_URL").ok() else {
@@ -237,8 +617,22 @@ mod tests {
             port: 0,
             storage_mode: StorageMode::Postgres,
             database_url: Some(database_url.clone()),
+            sqlite_path: None,
+            memory_capacity: None,
             rust_log: None,
             jwt_secret: None,
+            tenant_rls: false,
+            rate_limit_per_sec: None,
+            rate_limit_burst: None,
+            daily_quota: None,
+            ingest_signing: None,
+            wal_dir: None,
+            wal_max_bytes: None,
+            retention: Default::default(),
+            partition_interval: Default::default(),
+            partition_detach_after: None,
+            parquet_archive_dir: None,
+            parquet_extras: None,
         };
 
         let repo = build_telemetry_repository(&config).await.unwrap();
@@ -253,8 +647,9 @@ mod tests {
             extras: json!({"hello":"world"}),
         };
 
-        repo.save(telemetry.clone()).await.unwrap();
-        let got = repo.query_all(None).await.unwrap();
+        let tenant = TenantId::default();
+        repo.save(&tenant, telemetry.clone()).await.unwrap();
+        let got = repo.query_all(&tenant, None).await.unwrap();
         assert_eq!(got.len(), 1);
         assert_eq!(got[0].source_id, telemetry.source_id);
         assert_eq!(got[0].server_id, telemetry.server_id);